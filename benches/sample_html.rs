@@ -4,6 +4,41 @@ use std::fmt::Write;
 
 pub fn benchmark(c: &mut Criterion) {
     c.bench_function("sample html", |b| b.iter(|| sample_html().unwrap()));
+    c.bench_function("long text", |b| b.iter(|| long_text().unwrap()));
+    c.bench_function("long text (ascii)", |b| {
+        b.iter(|| long_text_ascii().unwrap())
+    });
+}
+
+fn long_text() -> Result<String, Box<dyn std::error::Error>> {
+    let mut buf = Buffer::new();
+    let mut body = buf.body();
+    for i in 0..100 {
+        writeln!(
+            body.p().attr_value("class", "paragraph"),
+            "Paragraph {}: Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+             sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.",
+            i,
+        )?;
+    }
+    writeln!(body.p(), "Fish & chips <3")?;
+    Ok(buf.finish())
+}
+
+fn long_text_ascii() -> Result<String, Box<dyn std::error::Error>> {
+    let mut buf = Buffer::new().encoding(Encoding::Ascii);
+    let mut body = buf.body();
+    for i in 0..100 {
+        writeln!(
+            body.p().attr_value("class", "paragraph"),
+            "Paragraph {}: Lorem ipsum dolor sit amet, consectetur adipiscing elit, \
+             sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. \
+             Crème brûlée, café au lait.",
+            i,
+        )?;
+    }
+    writeln!(body.p(), "Fish & chips <3")?;
+    Ok(buf.finish())
 }
 
 fn sample_html() -> Result<String, Box<dyn std::error::Error>> {
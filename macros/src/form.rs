@@ -0,0 +1,47 @@
+use crate::opts::{named_fields, FieldOpts};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::DeriveInput;
+
+pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let fields = named_fields(&input, "HtmlForm")?;
+    let mut writes = vec![];
+    for field in fields {
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let opts = FieldOpts::parse(&field.attrs)?;
+        if opts.skip {
+            continue;
+        }
+        let name = ident.to_string();
+        let label = opts.rename.unwrap_or_else(|| name.clone());
+        let control = if let Some(options) = &opts.select {
+            quote!(::html_builder::__private::select(&mut div, #name, &self.#ident, &[#(#options),*]))
+        } else if opts.textarea {
+            quote!(::html_builder::__private::textarea(&mut div, #name, &self.#ident))
+        } else if let Some(type_) = &opts.type_ {
+            quote!(::html_builder::__private::input(&mut div, #name, #type_, &self.#ident))
+        } else {
+            quote! {
+                <#ty as ::html_builder::FormField>::write_input(
+                    ::std::option::Option::Some(&self.#ident),
+                    &mut div,
+                    #name,
+                )
+            }
+        };
+        writes.push(quote! {
+            let mut div = ::html_builder::__private::field(form, #name, #label);
+            #control;
+        });
+    }
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::html_builder::HtmlForm for #name #ty_generics #where_clause {
+            fn write_fields(&self, form: &mut ::html_builder::Node) {
+                #({ #writes })*
+            }
+        }
+    })
+}
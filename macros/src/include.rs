@@ -0,0 +1,178 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::path::Path;
+use syn::parse::{Parse, ParseStream};
+use syn::{Expr, LitStr, Token};
+
+/// The elements which never have any contents or an end tag
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// The elements whose contents aren't parsed as markup
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style", "textarea", "title"];
+
+pub struct Input {
+    target: Expr,
+    path: LitStr,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Input> {
+        let target = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let path = input.parse()?;
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        }
+        Ok(Input { target, path })
+    }
+}
+
+pub fn expand(input: Input) -> syn::Result<TokenStream> {
+    let error = |msg: String| syn::Error::new(input.path.span(), msg);
+    let dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| error("CARGO_MANIFEST_DIR isn't set".into()))?;
+    let path = Path::new(&dir).join(input.path.value());
+    let html = std::fs::read_to_string(&path)
+        .map_err(|e| error(format!("couldn't read {}: {}", path.display(), e)))?;
+    check(&html)
+        .map_err(|(line, msg)| error(format!("{}, line {}: {}", input.path.value(), line, msg)))?;
+    let path = path
+        .to_str()
+        .ok_or_else(|| error("the path isn't valid UTF-8".into()))?;
+    let target = &input.target;
+    // Including the file with `include_str!` means it's rebuilt when the
+    // file changes
+    Ok(quote! {
+        ::html_builder::__private::include(&mut *#target, ::std::include_str!(#path))
+    })
+}
+
+/// Checks that some markup is well-formed: every element is closed, in the
+/// right order, and tags, attributes, and comments are complete.  Returns
+/// the line number of the problem and a description.
+fn check(html: &str) -> Result<(), (usize, String)> {
+    let line = |pos: usize| html[..pos].matches('\n').count() + 1;
+    let mut stack: Vec<(String, usize)> = vec![];
+    let mut pos = 0;
+    while let Some(offset) = html[pos..].find('<') {
+        let start = pos + offset;
+        let rest = &html[start..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment
+                .find("-->")
+                .ok_or_else(|| (line(start), "unterminated comment".to_string()))?;
+            pos = start + 4 + end + 3;
+        } else if let Some(decl) = rest.strip_prefix("<!") {
+            let end = decl
+                .find('>')
+                .ok_or_else(|| (line(start), "unterminated declaration".to_string()))?;
+            pos = start + 2 + end + 1;
+        } else if let Some(tag) = rest.strip_prefix("</") {
+            let end = tag
+                .find('>')
+                .ok_or_else(|| (line(start), "unterminated end tag".to_string()))?;
+            let name = tag[..end].trim_end().to_ascii_lowercase();
+            match stack.pop() {
+                Some((open, _)) if open == name => (),
+                Some((open, opened)) => {
+                    let msg = format!(
+                        "expected </{}> (opened on line {}), found </{}>",
+                        open, opened, name
+                    );
+                    return Err((line(start), msg));
+                }
+                None => {
+                    let msg = format!("</{}> doesn't match any open element", name);
+                    return Err((line(start), msg));
+                }
+            }
+            pos = start + 2 + end + 1;
+        } else if rest[1..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+            let (name, len, self_closing) =
+                start_tag(&rest[1..]).map_err(|(offset, msg)| (line(start + 1 + offset), msg))?;
+            pos = start + 1 + len;
+            if self_closing || VOID_ELEMENTS.contains(&name.as_str()) {
+                continue;
+            }
+            if RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+                // Skip to the end tag
+                let close = format!("</{}", name);
+                let end = html[pos..]
+                    .to_ascii_lowercase()
+                    .find(&close)
+                    .ok_or_else(|| (line(start), format!("unclosed <{}>", name)))?;
+                pos += end;
+            }
+            stack.push((name, line(start)));
+        } else {
+            return Err((line(start), "stray `<`; use `&lt;` instead".to_string()));
+        }
+    }
+    match stack.pop() {
+        Some((name, opened)) => Err((opened, format!("unclosed <{}>", name))),
+        None => Ok(()),
+    }
+}
+
+/// Parses a start tag after the `<`.  Returns the tag name, the length of
+/// the tag, and whether it ends with `/>`; or the offset of the problem
+/// and a description.
+fn start_tag(tag: &str) -> Result<(String, usize, bool), (usize, String)> {
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == ':';
+    let name_len = tag.find(|c| !is_name_char(c)).unwrap_or(tag.len());
+    let name = tag[..name_len].to_ascii_lowercase();
+    let unterminated = || (0, format!("unterminated <{}> tag", name));
+    let mut pos = name_len;
+    loop {
+        let rest = tag[pos..].trim_start();
+        pos = tag.len() - rest.len();
+        if rest.starts_with('>') {
+            return Ok((name, pos + 1, false));
+        } else if rest.starts_with("/>") {
+            return Ok((name, pos + 2, true));
+        } else if rest.is_empty() {
+            return Err(unterminated());
+        }
+        // An attribute name
+        let attr_len = rest
+            .find(|c: char| c.is_ascii_whitespace() || "/>=\"'<".contains(c))
+            .unwrap_or(rest.len());
+        if attr_len == 0 {
+            let msg = format!("unexpected `{}` in <{}> tag", &rest[..1], name);
+            return Err((pos, msg));
+        }
+        pos += attr_len;
+        let rest = tag[pos..].trim_start();
+        let Some(value) = rest.strip_prefix('=') else {
+            continue;
+        };
+        // An attribute value
+        let value = value.trim_start();
+        pos = tag.len() - value.len();
+        match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let end = value[1..].find(quote).ok_or_else(|| {
+                    (
+                        pos,
+                        format!("unterminated attribute value in <{}> tag", name),
+                    )
+                })?;
+                pos += end + 2;
+            }
+            Some(_) => {
+                let end = value
+                    .find(|c: char| c.is_ascii_whitespace() || c == '>')
+                    .unwrap_or(value.len());
+                if value[..end].contains(|c| "\"'<=`".contains(c)) {
+                    let msg = format!("attribute value in <{}> tag should be quoted", name);
+                    return Err((pos, msg));
+                }
+                pos += end;
+            }
+            None => return Err(unterminated()),
+        }
+    }
+}
@@ -0,0 +1,554 @@
+//! Procedural macros for [html-builder].  Enable the `macros` feature of
+//! html-builder to use them.
+//!
+//! [html-builder]: https://docs.rs/html-builder
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::{braced, bracketed, parenthesized, token, Expr, Ident, LitInt, LitStr, Pat, Token};
+
+mod form;
+mod include;
+mod opts;
+mod table;
+
+/// Writes markup into a [`Buffer`] or [`Node`] using a concise syntax.
+///
+/// The first argument is the buffer or node to write into (a `Buffer`, or a
+/// `&mut Node`); the rest is markup.  The macro expands to calls on the
+/// node, so the output is exactly the same as if you'd written them by
+/// hand, and it evaluates to a `std::fmt::Result`.
+///
+/// ```
+/// use html_builder::*;
+///
+/// let title = "Fish & chips";
+/// let items = ["cod", "chips"];
+/// let mut buf = Buffer::new();
+/// html!(buf, div.card #main {
+///     h1 { (title) }
+///     ul {
+///         @for item in &items {
+///             li.item { (item) }
+///         }
+///     }
+///     @if items.is_empty() {
+///         p { "Nothing to eat" }
+///     }
+///     input type="checkbox" checked;
+/// })?;
+/// assert_eq!(buf.finish(), "\
+/// <div class=\"card\" id=\"main\">
+///  <h1>
+/// Fish &amp; chips
+///  </h1>
+///  <ul>
+///   <li class=\"item\">
+/// cod
+///   </li>
+///   <li class=\"item\">
+/// chips
+///   </li>
+///  </ul>
+///  <input type=\"checkbox\" checked>
+/// </div>
+/// ");
+/// # Ok::<(), std::fmt::Error>(())
+/// ```
+///
+/// ## Syntax
+///
+/// * `"text"` and `(expr)` write text, which is escaped as usual.  `expr`
+///   can be anything which implements `Display`.
+/// * `tag { ... }` writes an element and its contents; `tag;` writes a void
+///   element.  Tag names may contain hyphens.
+/// * An element's attributes go between its name and its contents:
+///     - `.class` adds a class, and `#id` sets the id
+///     - `name="value"` or `name=(expr)` sets an attribute
+///     - `name` sets a boolean attribute, and `name[cond]` sets it if
+///       `cond` is true
+///
+///   Class names, ids, and attribute names may contain hyphens; a class or
+///   id can also be given as a string literal or as `(expr)`.
+/// * `@if cond { ... } @else { ... }` and `@for pat in iter { ... }` work
+///   like their Rust equivalents.
+///
+/// [`Buffer`]: https://docs.rs/html-builder/latest/html_builder/struct.Buffer.html
+/// [`Node`]: https://docs.rs/html-builder/latest/html_builder/struct.Node.html
+#[proc_macro]
+pub fn html(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as Input);
+    input.into_token_stream().into()
+}
+
+/// Writes the markup in a file into a [`Buffer`] or [`Node`], checking
+/// it at compile time.
+///
+/// The first argument is the buffer or node to write into (a `Buffer`, or
+/// a `&mut Node`), as with [`html!`]; the second is the path of the file,
+/// relative to the crate's `Cargo.toml`.  The file is embedded with
+/// `include_str!`, and the macro evaluates to a `std::fmt::Result`.
+///
+/// The file is checked when the crate is compiled, so that a malformed
+/// partial fails the build rather than breaking the page.  Every element
+/// must be closed explicitly, in the right order (void elements such as
+/// `<br>` don't need closing, and `<tag/>` is allowed); and tags,
+/// attributes, and comments must be complete.  A literal `<` must be
+/// written as `&lt;`.
+///
+/// ```compile_fail
+/// use html_builder::*;
+///
+/// // error: tests/partials/broken.html, line 3: expected </p> (opened on
+/// // line 2), found </div>
+/// let mut buf = Buffer::new();
+/// include_html!(buf, "tests/partials/broken.html")?;
+/// # Ok::<(), std::fmt::Error>(())
+/// ```
+///
+/// The markup is written verbatim, but re-indented to the depth of the
+/// node, as with `Format::indent_raw()`.
+///
+/// ```
+/// use html_builder::*;
+///
+/// // tests/partials/footer.html contains:
+/// //
+/// // <footer>
+/// //   <p>&copy; Example Ltd</p>
+/// // </footer>
+/// let mut buf = Buffer::new();
+/// let mut body = buf.body();
+/// include_html!(&mut body, "tests/partials/footer.html")?;
+/// assert_eq!(buf.finish(), "\
+/// <body>
+///  <footer>
+///    <p>&copy; Example Ltd</p>
+///  </footer>
+/// </body>
+/// ");
+/// # Ok::<(), std::fmt::Error>(())
+/// ```
+///
+/// [`Buffer`]: https://docs.rs/html-builder/latest/html_builder/struct.Buffer.html
+/// [`Node`]: https://docs.rs/html-builder/latest/html_builder/struct.Node.html
+#[proc_macro]
+pub fn include_html(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as include::Input);
+    include::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `ToHtmlTable` for a struct with named fields.
+///
+/// Each field becomes a column, headed by the field's name; the cells
+/// contain the field's `Display` output.  The following field attributes
+/// are supported:
+///
+/// * `#[html(rename = "...")]` changes the column's heading
+/// * `#[html(skip)]` leaves the field out
+///
+/// ```
+/// use html_builder::*;
+///
+/// #[derive(ToHtmlTable)]
+/// struct User {
+///     #[html(rename = "Name")]
+///     name: String,
+///     #[html(skip)]
+///     password: String,
+/// }
+///
+/// let users = [User { name: "Alice".into(), password: "hunter2".into() }];
+/// let mut buf = Buffer::new();
+/// User::to_html_table(&users, &mut buf);
+/// let out = buf.finish();
+/// assert!(out.contains("Name") && out.contains("Alice"));
+/// assert!(!out.contains("hunter2"));
+/// ```
+#[proc_macro_derive(ToHtmlTable, attributes(html))]
+pub fn derive_to_html_table(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    table::derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `HtmlForm` for a struct with named fields.
+///
+/// Each field gets a `<label>` and a control, pre-filled with the field's
+/// value.  By default the control is an `<input>` whose type depends on the
+/// field's type (see `FormField`).  The following field attributes are
+/// supported:
+///
+/// * `#[html(rename = "...")]` changes the label
+/// * `#[html(skip)]` leaves the field out
+/// * `#[html(type = "...")]` sets the `type` of the `<input>`
+/// * `#[html(textarea)]` uses a `<textarea>`
+/// * `#[html(select = ["a", "b"])]` uses a `<select>` with the given
+///   options
+///
+/// Fields with one of the last three attributes only need to implement
+/// `Display`.
+///
+/// ```
+/// use html_builder::*;
+///
+/// #[derive(HtmlForm)]
+/// struct Profile {
+///     #[html(rename = "Name")]
+///     name: String,
+///     #[html(type = "email")]
+///     email: String,
+///     #[html(select = ["light", "dark"])]
+///     theme: &'static str,
+///     newsletter: bool,
+/// }
+///
+/// let profile = Profile {
+///     name: "Alice".into(),
+///     email: "alice@example.com".into(),
+///     theme: "dark",
+///     newsletter: true,
+/// };
+/// let mut buf = Buffer::new();
+/// profile.to_html_form(&mut buf, "/profile");
+/// let out = buf.finish();
+/// assert!(out.contains(r#"<input type="email" name="email" id="email" value="alice@example.com">"#));
+/// assert!(out.contains(r#"<option value="dark" selected>"#));
+/// assert!(out.contains(r#"<input type="checkbox" name="newsletter" id="newsletter" checked>"#));
+/// ```
+#[proc_macro_derive(HtmlForm, attributes(html))]
+pub fn derive_html_form(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    form::derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+struct Input {
+    target: Expr,
+    markup: Markup,
+}
+
+struct Markup(Vec<Item>);
+
+enum Item {
+    Text(Expr),
+    Element(Element),
+    If(If),
+    For(For),
+}
+
+struct Element {
+    tag: String,
+    attrs: Vec<Attr>,
+    /// `None` for a void element
+    children: Option<Markup>,
+}
+
+enum Attr {
+    Value(String, Expr),
+    Flag(String, Expr),
+}
+
+struct If {
+    cond: Expr,
+    then: Markup,
+    else_: Option<Markup>,
+}
+
+struct For {
+    pat: Pat,
+    iter: Expr,
+    body: Markup,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Input> {
+        let target = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let markup = input.parse()?;
+        Ok(Input { target, markup })
+    }
+}
+
+impl Parse for Markup {
+    fn parse(input: ParseStream) -> syn::Result<Markup> {
+        let mut items = vec![];
+        while !input.is_empty() {
+            items.push(input.parse()?);
+        }
+        Ok(Markup(items))
+    }
+}
+
+impl Parse for Item {
+    fn parse(input: ParseStream) -> syn::Result<Item> {
+        if input.peek(LitStr) {
+            Ok(Item::Text(Expr::Lit(input.parse()?)))
+        } else if input.peek(token::Paren) {
+            Ok(Item::Text(parse_paren(input)?))
+        } else if input.peek(Token![@]) {
+            input.parse::<Token![@]>()?;
+            if input.peek(Token![if]) {
+                Ok(Item::If(input.parse()?))
+            } else if input.peek(Token![for]) {
+                Ok(Item::For(input.parse()?))
+            } else {
+                Err(input.error("expected `@if` or `@for`"))
+            }
+        } else {
+            Ok(Item::Element(input.parse()?))
+        }
+    }
+}
+
+impl Parse for Element {
+    fn parse(input: ParseStream) -> syn::Result<Element> {
+        let tag = parse_name(input)?;
+        let mut attrs = vec![];
+        loop {
+            if input.peek(token::Brace) {
+                let content;
+                braced!(content in input);
+                let children = Some(content.parse()?);
+                return Ok(Element {
+                    tag,
+                    attrs,
+                    children,
+                });
+            } else if input.peek(Token![;]) {
+                input.parse::<Token![;]>()?;
+                return Ok(Element {
+                    tag,
+                    attrs,
+                    children: None,
+                });
+            } else if input.peek(Token![.]) {
+                input.parse::<Token![.]>()?;
+                attrs.push(Attr::Value("class".into(), parse_value(input)?));
+            } else if input.peek(Token![#]) {
+                input.parse::<Token![#]>()?;
+                attrs.push(Attr::Value("id".into(), parse_value(input)?));
+            } else if input.is_empty() {
+                return Err(input.error("expected `{` or `;` after the element"));
+            } else {
+                let name = parse_name(input)?;
+                if input.peek(Token![=]) {
+                    input.parse::<Token![=]>()?;
+                    let value = if input.peek(token::Paren) {
+                        parse_paren(input)?
+                    } else {
+                        Expr::Lit(input.parse()?)
+                    };
+                    attrs.push(Attr::Value(name, value));
+                } else if input.peek(token::Bracket) {
+                    let content;
+                    bracketed!(content in input);
+                    attrs.push(Attr::Flag(name, content.parse()?));
+                } else {
+                    attrs.push(Attr::Flag(name, syn::parse_quote!(true)));
+                }
+            }
+        }
+    }
+}
+
+impl Parse for If {
+    fn parse(input: ParseStream) -> syn::Result<If> {
+        input.parse::<Token![if]>()?;
+        let cond = Expr::parse_without_eager_brace(input)?;
+        let then = parse_block(input)?;
+        let else_ = if input.peek(Token![@]) && input.peek2(Token![else]) {
+            input.parse::<Token![@]>()?;
+            input.parse::<Token![else]>()?;
+            if input.peek(Token![if]) {
+                Some(Markup(vec![Item::If(input.parse()?)]))
+            } else {
+                Some(parse_block(input)?)
+            }
+        } else {
+            None
+        };
+        Ok(If { cond, then, else_ })
+    }
+}
+
+impl Parse for For {
+    fn parse(input: ParseStream) -> syn::Result<For> {
+        input.parse::<Token![for]>()?;
+        let pat = Pat::parse_single(input)?;
+        input.parse::<Token![in]>()?;
+        let iter = Expr::parse_without_eager_brace(input)?;
+        let body = parse_block(input)?;
+        Ok(For { pat, iter, body })
+    }
+}
+
+fn parse_block(input: ParseStream) -> syn::Result<Markup> {
+    let content;
+    braced!(content in input);
+    content.parse()
+}
+
+fn parse_paren(input: ParseStream) -> syn::Result<Expr> {
+    let content;
+    parenthesized!(content in input);
+    content.parse()
+}
+
+/// A tag, attribute, class, or id, which may contain hyphens
+fn parse_name(input: ParseStream) -> syn::Result<String> {
+    let mut name = parse_name_part(input)?;
+    while input.peek(Token![-]) {
+        input.parse::<Token![-]>()?;
+        name.push('-');
+        name.push_str(&parse_name_part(input)?);
+    }
+    Ok(name)
+}
+
+fn parse_name_part(input: ParseStream) -> syn::Result<String> {
+    if input.peek(LitInt) {
+        Ok(input.parse::<LitInt>()?.to_string())
+    } else {
+        Ok(Ident::parse_any(input)?.to_string())
+    }
+}
+
+/// A class or id
+fn parse_value(input: ParseStream) -> syn::Result<Expr> {
+    if input.peek(LitStr) {
+        Ok(Expr::Lit(input.parse()?))
+    } else if input.peek(token::Paren) {
+        parse_paren(input)
+    } else {
+        let name = parse_name(input)?;
+        Ok(syn::parse_quote!(#name))
+    }
+}
+
+/// Names used by the generated code, which are invisible to the caller's
+/// code
+struct Names {
+    parent: Ident,
+    label: syn::Lifetime,
+}
+
+impl Names {
+    fn new() -> Names {
+        Names {
+            parent: Ident::new("parent", Span::mixed_site()),
+            label: syn::Lifetime::new("'html", Span::mixed_site()),
+        }
+    }
+}
+
+impl ToTokens for Input {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Names { parent, label } = Names::new();
+        let target = &self.target;
+        let mut body = TokenStream::new();
+        self.markup.write(&mut body);
+        tokens.extend(quote! {
+            #label: {
+                let #parent: &mut ::html_builder::Node = &mut *#target;
+                #body
+                ::std::result::Result::<(), ::std::fmt::Error>::Ok(())
+            }
+        });
+    }
+}
+
+impl Markup {
+    fn write(&self, tokens: &mut TokenStream) {
+        for item in &self.0 {
+            item.write(tokens);
+        }
+    }
+}
+
+impl Item {
+    fn write(&self, tokens: &mut TokenStream) {
+        let Names { parent, label } = Names::new();
+        match self {
+            Item::Text(expr) => tokens.extend(quote! {
+                if let ::std::result::Result::Err(e) = ::std::fmt::Write::write_fmt(
+                    &mut *#parent,
+                    ::std::format_args!("{}\n", #expr),
+                ) {
+                    break #label ::std::result::Result::Err(e);
+                }
+            }),
+            Item::Element(el) => el.write(tokens),
+            Item::If(x) => x.write(tokens),
+            Item::For(For { pat, iter, body }) => {
+                let mut inner = TokenStream::new();
+                body.write(&mut inner);
+                tokens.extend(quote! {
+                    for #pat in #iter {
+                        #inner
+                    }
+                });
+            }
+        }
+    }
+}
+
+impl Element {
+    fn write(&self, tokens: &mut TokenStream) {
+        let Names { parent, .. } = Names::new();
+        let tag = &self.tag;
+        let mut el = match self.children {
+            Some(_) => quote!(#parent.child(::std::borrow::Cow::Borrowed(#tag))),
+            None => quote!(#parent.void_child(::std::borrow::Cow::Borrowed(#tag))),
+        };
+        for attr in &self.attrs {
+            el = match attr {
+                Attr::Value(name, value) => quote! {
+                    ::html_builder::GlobalAttributes::attr_value(#el, #name, &(#value))
+                },
+                Attr::Flag(name, on) => quote! {
+                    ::html_builder::GlobalAttributes::attr_flag(#el, #name, #on)
+                },
+            };
+        }
+        match &self.children {
+            Some(children) if !children.0.is_empty() => {
+                let mut inner = TokenStream::new();
+                children.write(&mut inner);
+                tokens.extend(quote! {
+                    {
+                        let mut #parent = #el;
+                        let #parent = &mut #parent;
+                        #inner
+                    }
+                });
+            }
+            _ => tokens.extend(quote!(#el;)),
+        }
+    }
+}
+
+impl If {
+    fn write(&self, tokens: &mut TokenStream) {
+        let cond = &self.cond;
+        let mut then = TokenStream::new();
+        self.then.write(&mut then);
+        let mut else_ = TokenStream::new();
+        if let Some(x) = &self.else_ {
+            x.write(&mut else_);
+        }
+        tokens.extend(quote! {
+            if #cond {
+                #then
+            } else {
+                #else_
+            }
+        });
+    }
+}
@@ -0,0 +1,66 @@
+use syn::punctuated::Punctuated;
+use syn::{Data, DeriveInput, Field, Fields, LitStr, Token};
+
+/// The fields of a struct with named fields
+pub fn named_fields<'a>(
+    input: &'a DeriveInput,
+    trait_name: &str,
+) -> syn::Result<&'a Punctuated<Field, Token![,]>> {
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => Ok(&fields.named),
+            _ => Err(syn::Error::new_spanned(
+                &input.ident,
+                format!(
+                    "{} can only be derived for structs with named fields",
+                    trait_name
+                ),
+            )),
+        },
+        _ => Err(syn::Error::new_spanned(
+            &input.ident,
+            format!("{} can only be derived for structs", trait_name),
+        )),
+    }
+}
+
+/// The contents of a field's `#[html(...)]` attributes
+#[derive(Default)]
+pub struct FieldOpts {
+    pub rename: Option<String>,
+    pub skip: bool,
+    pub type_: Option<String>,
+    pub textarea: bool,
+    pub select: Option<Vec<String>>,
+}
+
+impl FieldOpts {
+    pub fn parse(attrs: &[syn::Attribute]) -> syn::Result<FieldOpts> {
+        let mut opts = FieldOpts::default();
+        for attr in attrs.iter().filter(|x| x.path().is_ident("html")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    opts.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("skip") {
+                    opts.skip = true;
+                } else if meta.path.is_ident("type") {
+                    opts.type_ = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("textarea") {
+                    opts.textarea = true;
+                } else if meta.path.is_ident("select") {
+                    let value = meta.value()?;
+                    let content;
+                    syn::bracketed!(content in value);
+                    let options = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+                    opts.select = Some(options.iter().map(LitStr::value).collect());
+                } else {
+                    return Err(
+                        meta.error("expected `rename`, `skip`, `type`, `textarea`, or `select`")
+                    );
+                }
+                Ok(())
+            })?;
+        }
+        Ok(opts)
+    }
+}
@@ -0,0 +1,30 @@
+use crate::opts::{named_fields, FieldOpts};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::DeriveInput;
+
+pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let fields = named_fields(&input, "ToHtmlTable")?;
+    let mut headings = vec![];
+    let mut idents = vec![];
+    for field in fields {
+        let ident = field.ident.as_ref().unwrap();
+        let opts = FieldOpts::parse(&field.attrs)?;
+        if opts.skip {
+            continue;
+        }
+        headings.push(opts.rename.unwrap_or_else(|| ident.to_string()));
+        idents.push(ident);
+    }
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::html_builder::ToHtmlTable for #name #ty_generics #where_clause {
+            const HEADINGS: &'static [&'static str] = &[#(#headings),*];
+
+            fn write_cells(&self, tr: &mut ::html_builder::Node) {
+                #( ::html_builder::Html5::td_text(tr, &self.#idents); )*
+            }
+        }
+    })
+}
@@ -0,0 +1,52 @@
+//! Returning buffers from actix-web handlers
+
+use crate::stream::TryChunks;
+use crate::{Buffer, HtmlStream};
+use actix_web::body::BoxBody;
+use actix_web::{HttpRequest, HttpResponse, Responder};
+
+const TEXT_HTML: &str = "text/html; charset=utf-8";
+
+/// Responds with the buffer's contents, as `text/html`.
+///
+/// If [`try_finish()`][Buffer::try_finish] fails, eg. because the buffer
+/// was created with a [custom sink][Buffer::with_sink], the response is a
+/// 500 Internal Server Error instead.
+///
+/// This is only available with the `actix` feature.
+///
+/// ```
+/// use actix_web::Responder;
+/// use html_builder::*;
+/// use std::fmt::Write;
+///
+/// async fn handler() -> impl Responder {
+///     let mut buf = Buffer::new();
+///     writeln!(buf.p(), "Hello!").unwrap();
+///     buf
+/// }
+/// ```
+impl Responder for Buffer {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse {
+        match self.try_finish() {
+            Ok(html) => HttpResponse::Ok().content_type(TEXT_HTML).body(html),
+            Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+        }
+    }
+}
+
+/// Responds with the output of a [streaming buffer][Buffer::streaming], as
+/// `text/html`, sending it to the client as it's written.
+///
+/// This is only available with the `actix` feature.
+impl Responder for HtmlStream {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse {
+        HttpResponse::Ok()
+            .content_type(TEXT_HTML)
+            .streaming(TryChunks(self))
+    }
+}
@@ -0,0 +1,304 @@
+//! Writing AMP pages
+
+use crate::sink::AnySink;
+use crate::tag::Tag;
+use crate::{
+    Attribute, Buffer, Ctx, Error, Frame, GlobalAttributes, Html5, SlotId, ValidationError,
+};
+use std::fmt::Display;
+
+/// Where AMP's scripts are served from
+const CDN: &str = "https://cdn.ampproject.org/";
+
+/// Hides the page until the AMP runtime has loaded
+const BOILERPLATE: &str = "body{-webkit-animation:-amp-start 8s steps(1,end) 0s 1 normal both;\
+-moz-animation:-amp-start 8s steps(1,end) 0s 1 normal both;\
+-ms-animation:-amp-start 8s steps(1,end) 0s 1 normal both;\
+animation:-amp-start 8s steps(1,end) 0s 1 normal both}\
+@-webkit-keyframes -amp-start{from{visibility:hidden}to{visibility:visible}}\
+@-moz-keyframes -amp-start{from{visibility:hidden}to{visibility:visible}}\
+@-ms-keyframes -amp-start{from{visibility:hidden}to{visibility:visible}}\
+@-o-keyframes -amp-start{from{visibility:hidden}to{visibility:visible}}\
+@keyframes -amp-start{from{visibility:hidden}to{visibility:visible}}";
+
+/// Shows the page straight away when scripts are disabled
+const NOSCRIPT_BOILERPLATE: &str =
+    "body{-webkit-animation:none;-moz-animation:none;-ms-animation:none;animation:none}";
+
+/// Elements which aren't allowed in AMP pages
+const FORBIDDEN: &[&str] = &["applet", "embed", "frame", "frameset", "object", "param"];
+
+/// The stylesheets which can be linked to, for web fonts
+const FONT_PROVIDERS: &[&str] = &[
+    "https://fast.fonts.net/",
+    "https://fonts.googleapis.com/",
+    "https://maxcdn.bootstrapcdn.com/",
+    "https://use.fontawesome.com/",
+    "https://use.typekit.net/",
+];
+
+/// The settings of a buffer in AMP mode
+#[derive(Clone)]
+pub(crate) struct Amp {
+    canonical: String,
+    /// The AMP components which need their extension scripts loaded
+    extensions: Vec<&'static str>,
+}
+
+impl Buffer {
+    /// Writes an [AMP] page.
+    ///
+    /// `canonical` is the URL of the regular version of the page.  In AMP
+    /// mode:
+    ///
+    /// * The `<html>` element gets the `amp` attribute, and the required
+    ///   boilerplate is written at the start of the `<head>`: the charset,
+    ///   the viewport, the AMP runtime, the canonical link, and the
+    ///   boilerplate styles.
+    /// * `<img>`, `<video>`, `<audio>`, and `<iframe>` elements are written
+    ///   as `<amp-img>`, `<amp-video>`, and so on.  The scripts for the
+    ///   components which are used are added to the [head
+    ///   slot][crate::Node::head_slot].
+    /// * Some of AMP's rules are checked, and the problems are recorded as
+    ///   [validation errors][Buffer::validation_errors]: forbidden elements
+    ///   such as `<embed>`, scripts, external stylesheets, `<style>`
+    ///   elements other than `<style amp-custom>`, event handler attributes,
+    ///   and media without a size.  Attributes added with
+    ///   [`attr()`][crate::Node::attr] aren't checked.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new().amp("https://example.com/news");
+    /// buf.doctype();
+    /// let mut html = buf.html();
+    /// let mut head = html.head();
+    /// head.head_slot();
+    /// head.title().text("News");
+    /// let mut body = html.body();
+    /// body.img().src("/cat.jpg").alt("A cat").width(640).height(480);
+    /// body.video().src("/cat.mp4").width(640).height(360);
+    /// let html = buf.try_finish().unwrap();
+    /// assert!(html.starts_with("<!DOCTYPE html>\n<html amp>\n <head>\n  <meta charset=\"utf-8\">\n"));
+    /// assert!(html.contains("<script async custom-element=\"amp-video\" src=\"https://cdn.ampproject.org/v0/amp-video-0.1.js\">"));
+    /// assert!(html.contains("<amp-img src=\"/cat.jpg\" alt=\"A cat\" width=\"640\" height=\"480\">\n  </amp-img>"));
+    /// ```
+    ///
+    /// [AMP]: https://amp.dev/
+    pub fn amp(mut self, canonical: impl Display) -> Buffer {
+        self.node.ctx.get().amp = Some(Amp {
+            canonical: canonical.to_string(),
+            extensions: vec![],
+        });
+        self
+    }
+}
+
+impl Amp {
+    /// The AMP component which is written instead of `tag`, if any
+    pub(crate) fn rename(tag: &str) -> Option<&'static str> {
+        match tag {
+            "img" => Some("amp-img"),
+            "video" => Some("amp-video"),
+            "audio" => Some("amp-audio"),
+            "iframe" => Some("amp-iframe"),
+            _ => None,
+        }
+    }
+
+    /// Records that an element was opened, in case it needs an extension
+    /// script
+    pub(crate) fn opened(&mut self, tag: &str) {
+        let extension = ["amp-video", "amp-audio", "amp-iframe"]
+            .iter()
+            .find(|x| **x == tag);
+        if let Some(extension) = extension {
+            if !self.extensions.contains(extension) {
+                self.extensions.push(extension);
+            }
+        }
+    }
+
+    /// Records the components opened in a slot's contents
+    pub(crate) fn merge(&mut self, other: &Amp) {
+        for extension in &other.extensions {
+            self.opened(extension);
+        }
+    }
+
+    /// Forgets the components which were used
+    pub(crate) fn reset(&mut self) {
+        self.extensions.clear();
+    }
+
+    /// Adjusts and checks the attributes of an element before its start
+    /// tag is written
+    pub(crate) fn start_tag(
+        &self,
+        tag: &str,
+        attrs: &mut Vec<Attribute>,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        if tag == "html" && !attrs.iter().any(|attr| is(attr, "amp")) {
+            attrs.push(Attribute::Flag("amp".into()));
+        }
+        if let Err(e) = check(tag, attrs) {
+            errors.push(ValidationError::Amp(e));
+        }
+    }
+
+    /// Writes the boilerplate at the start of the `<head>`
+    pub(crate) fn write_boilerplate(&self, wtr: &mut dyn AnySink, depth: usize) {
+        let value = |name: &str, value: &str| Attribute::Value(name.into(), value.into());
+        wtr.void_tag(depth, "meta", &[value("charset", "utf-8")], false);
+        let runtime = [
+            Attribute::Flag("async".into()),
+            value("src", &runtime_src()),
+        ];
+        wtr.start_tag(depth, "script", &runtime);
+        wtr.end_tag(depth, "script");
+        let viewport = [
+            value("name", "viewport"),
+            value("content", "width=device-width"),
+        ];
+        wtr.void_tag(depth, "meta", &viewport, false);
+        let canonical = [value("rel", "canonical"), value("href", &self.canonical)];
+        wtr.void_tag(depth, "link", &canonical, false);
+        let boilerplate = [Attribute::Flag("amp-boilerplate".into())];
+        wtr.start_tag(depth, "style", &boilerplate);
+        wtr.raw(BOILERPLATE);
+        wtr.raw("\n");
+        wtr.end_tag(depth, "style");
+        wtr.start_tag(depth, "noscript", &[]);
+        wtr.start_tag(depth + 1, "style", &boilerplate);
+        wtr.raw(NOSCRIPT_BOILERPLATE);
+        wtr.raw("\n");
+        wtr.end_tag(depth + 1, "style");
+        wtr.end_tag(depth, "noscript");
+    }
+}
+
+impl Ctx {
+    /// Opens an element, replacing it with its AMP component in AMP mode
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    pub(crate) fn open_element(
+        &mut self,
+        tag: Tag,
+        depth: usize,
+        fallible: bool,
+    ) -> Result<(), Error> {
+        let tag = match self.amp.as_ref().and_then(|_| Amp::rename(&tag)) {
+            Some(component) => Tag::Static(component),
+            None => tag,
+        };
+        self.open(&tag, depth, fallible)?;
+        if let Some(amp) = &mut self.amp {
+            amp.opened(&tag);
+        }
+        self.stack.push(Frame::Element(tag));
+        Ok(())
+    }
+
+    /// Adds the scripts for the AMP components which were used to the head
+    /// slot
+    pub(crate) fn flush_amp_extensions(&mut self, slot: Option<SlotId>) {
+        let extensions = match &mut self.amp {
+            Some(amp) if !amp.extensions.is_empty() => std::mem::take(&mut amp.extensions),
+            _ => return,
+        };
+        let slot = match slot {
+            Some(slot) => slot,
+            None => {
+                for component in extensions {
+                    let msg = format!("<{}> needs a script, but there's no head slot", component);
+                    self.errors.push(ValidationError::Amp(msg));
+                }
+                return;
+            }
+        };
+        self.fill(slot, |head| {
+            for component in extensions {
+                head.script()
+                    .attr_flag("async", true)
+                    .attr_value("custom-element", component)
+                    .src(format!("{}v0/{}-0.1.js", CDN, component));
+            }
+        });
+    }
+}
+
+fn runtime_src() -> String {
+    format!("{}v0.js", CDN)
+}
+
+/// Checks one of AMP's rules about elements
+fn check(tag: &str, attrs: &[Attribute]) -> Result<(), String> {
+    if FORBIDDEN.contains(&tag) {
+        return Err(format!("<{}> isn't allowed", tag));
+    }
+    for attr in attrs {
+        if let Attribute::Value(name, _) | Attribute::Flag(name) = attr {
+            if name.len() > 2 && name.starts_with("on") {
+                let msg = format!("event handler attributes, such as {}, aren't allowed", name);
+                return Err(msg);
+            }
+        }
+    }
+    let value = |name: &str| {
+        attrs.iter().find_map(|attr| match attr {
+            Attribute::Value(n, value) if n == name => Some(value.as_str()),
+            _ => None,
+        })
+    };
+    match tag {
+        "script" => {
+            let json = matches!(
+                value("type"),
+                Some("application/ld+json") | Some("application/json")
+            );
+            let amp = value("src").is_some_and(|src| src.starts_with(CDN));
+            if !json && !amp {
+                return Err("<script> isn't allowed, except for JSON and AMP's scripts".into());
+            }
+        }
+        "style" => {
+            let allowed = ["amp-custom", "amp-boilerplate", "amp-keyframes"];
+            if !attrs.iter().any(|attr| allowed.iter().any(|x| is(attr, x))) {
+                return Err("<style> needs the amp-custom attribute".into());
+            }
+        }
+        "link" => {
+            let stylesheet = value("rel").is_some_and(|rel| {
+                rel.split_ascii_whitespace()
+                    .any(|x| x.eq_ignore_ascii_case("stylesheet"))
+            });
+            let font = value("href")
+                .is_some_and(|href| FONT_PROVIDERS.iter().any(|x| href.starts_with(x)));
+            if stylesheet && !font {
+                return Err(
+                    "stylesheets must be in <style amp-custom>, except for web fonts".into(),
+                );
+            }
+        }
+        "amp-img" | "amp-video" | "amp-iframe" => {
+            let sized = match value("layout") {
+                Some("fill" | "flex-item" | "nodisplay" | "container") => true,
+                Some("fixed-height") => value("height").is_some(),
+                _ => value("width").is_some() && value("height").is_some(),
+            };
+            if !sized {
+                return Err(format!("<{}> needs a width and height, or a layout", tag));
+            }
+        }
+        _ => (),
+    }
+    Ok(())
+}
+
+/// Whether an attribute has the given name
+fn is(attr: &Attribute, name: &str) -> bool {
+    match attr {
+        Attribute::Value(n, _) | Attribute::Flag(n) => n == name,
+        Attribute::Raw(_) => false,
+    }
+}
@@ -0,0 +1,217 @@
+//! Helpers for loading scripts, stylesheets, and other resources
+
+use crate::{EventHandlers, Html5, LinkElement, Node, ScriptElement};
+use std::fmt::Display;
+
+impl<'a> Node<'a> {
+    /// Writes a `<script>` which loads an external script
+    ///
+    /// Loading behaviour is set on the returned element with
+    /// [`defer()`][ScriptElement::defer], [`async_()`][ScriptElement::async_],
+    /// [`module()`][ScriptElement::module], and
+    /// [`nomodule()`][ScriptElement::nomodule].
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new();
+    /// buf.script_src("/app.js").module();
+    /// buf.script_src("/legacy.js").nomodule(true).defer(true);
+    /// buf.script_src("/analytics.js").async_(true);
+    /// assert_eq!(buf.finish(), "\
+    /// <script src=\"/app.js\" type=\"module\">
+    /// </script>
+    /// <script src=\"/legacy.js\" nomodule defer>
+    /// </script>
+    /// <script src=\"/analytics.js\" async>
+    /// </script>
+    /// ");
+    /// ```
+    pub fn script_src(&mut self, url: impl Display) -> ScriptElement<'_> {
+        self.script().src(url)
+    }
+}
+
+impl<'a> ScriptElement<'a> {
+    /// Loads the script as an ES module
+    ///
+    /// Module scripts are deferred by default.
+    pub fn module(self) -> Self {
+        self.type_("module")
+    }
+}
+
+/// A stylesheet to load with [`Node::stylesheet()`].
+///
+/// A plain URL can be used for a stylesheet with no options.
+///
+/// ```
+/// use html_builder::*;
+///
+/// let mut buf = Buffer::new();
+/// let mut head = buf.head();
+/// head.stylesheet("/main.css");
+/// head.stylesheet(Stylesheet::new("/print.css").media("print"));
+/// head.stylesheet(Stylesheet::new("/dark.css").disabled());
+/// head.stylesheet(Stylesheet::new("/below-the-fold.css").preload());
+/// assert_eq!(buf.finish(), "\
+/// <head>
+///  <link rel=\"stylesheet\" href=\"/main.css\">
+///  <link rel=\"stylesheet\" href=\"/print.css\" media=\"print\">
+///  <link rel=\"stylesheet\" href=\"/dark.css\" disabled>
+///  <link rel=\"preload\" href=\"/below-the-fold.css\" as=\"style\" onload=\"this.onload=null;this.rel='stylesheet'\">
+///  <noscript>
+///   <link rel=\"stylesheet\" href=\"/below-the-fold.css\">
+///  </noscript>
+/// </head>
+/// ");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stylesheet {
+    href: String,
+    media: Option<String>,
+    preload: bool,
+    disabled: bool,
+}
+
+impl Stylesheet {
+    /// A stylesheet at the given URL
+    pub fn new(href: impl Display) -> Stylesheet {
+        Stylesheet {
+            href: href.to_string(),
+            media: None,
+            preload: false,
+            disabled: false,
+        }
+    }
+
+    /// Only applies the stylesheet to the given media, eg. `print`
+    pub fn media(mut self, media: impl Display) -> Stylesheet {
+        self.media = Some(media.to_string());
+        self
+    }
+
+    /// Loads the stylesheet without blocking rendering
+    ///
+    /// The stylesheet is preloaded, and applied once it has loaded.  A
+    /// `<noscript>` fallback loads it normally for browsers with scripts
+    /// disabled.
+    pub fn preload(mut self) -> Stylesheet {
+        self.preload = true;
+        self
+    }
+
+    /// Loads the stylesheet without applying it, so that it can be enabled
+    /// with a script
+    pub fn disabled(mut self) -> Stylesheet {
+        self.disabled = true;
+        self
+    }
+}
+
+impl From<&str> for Stylesheet {
+    fn from(href: &str) -> Stylesheet {
+        Stylesheet::new(href)
+    }
+}
+
+impl From<String> for Stylesheet {
+    fn from(href: String) -> Stylesheet {
+        Stylesheet::new(href)
+    }
+}
+
+impl<'a> Node<'a> {
+    /// Writes the `<link>` tags which load a stylesheet
+    ///
+    /// See [`Stylesheet`].
+    pub fn stylesheet(&mut self, sheet: impl Into<Stylesheet>) -> &mut Self {
+        let sheet = sheet.into();
+        if sheet.preload {
+            let mut link = self
+                .link()
+                .rel("preload")
+                .href(&sheet.href)
+                .as_("style")
+                .onload("this.onload=null;this.rel='stylesheet'");
+            if let Some(media) = &sheet.media {
+                link = link.media(media);
+            }
+            link.disabled(sheet.disabled);
+            self.noscript().stylesheet(Stylesheet {
+                preload: false,
+                ..sheet
+            });
+        } else {
+            let mut link = self.link().rel("stylesheet").href(&sheet.href);
+            if let Some(media) = &sheet.media {
+                link = link.media(media);
+            }
+            link.disabled(sheet.disabled);
+        }
+        self
+    }
+}
+
+impl<'a> Node<'a> {
+    /// Writes a `<link rel="preconnect">`, which opens a connection to an
+    /// origin which will be needed soon
+    ///
+    /// Connections for CORS requests, such as fonts, are separate: for
+    /// those, add `crossorigin` to the returned element.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new();
+    /// let mut head = buf.head();
+    /// head.preconnect("https://fonts.gstatic.com").crossorigin("anonymous");
+    /// head.dns_prefetch("https://fonts.gstatic.com");
+    /// head.preload("/fonts/inter.woff2", "font").type_("font/woff2");
+    /// head.preload("/hero.jpg", "image");
+    /// head.prefetch("/next-page.html");
+    /// assert_eq!(buf.finish(), "\
+    /// <head>
+    ///  <link rel=\"preconnect\" href=\"https://fonts.gstatic.com\" crossorigin=\"anonymous\">
+    ///  <link rel=\"dns-prefetch\" href=\"https://fonts.gstatic.com\">
+    ///  <link rel=\"preload\" href=\"/fonts/inter.woff2\" as=\"font\" crossorigin=\"anonymous\" type=\"font/woff2\">
+    ///  <link rel=\"preload\" href=\"/hero.jpg\" as=\"image\">
+    ///  <link rel=\"prefetch\" href=\"/next-page.html\">
+    /// </head>
+    /// ");
+    /// ```
+    pub fn preconnect(&mut self, origin: impl Display) -> LinkElement<'_> {
+        self.link().rel("preconnect").href(origin)
+    }
+
+    /// Writes a `<link rel="dns-prefetch">`, which resolves an origin's
+    /// domain name ahead of time
+    ///
+    /// This is a fallback for browsers which don't support
+    /// [`preconnect()`][Node::preconnect].
+    pub fn dns_prefetch(&mut self, origin: impl Display) -> LinkElement<'_> {
+        self.link().rel("dns-prefetch").href(origin)
+    }
+
+    /// Writes a `<link rel="preload">`, which fetches a resource which the
+    /// current page will need
+    ///
+    /// `as_` is the type of the resource, eg. `font`, `image`, `script`, or
+    /// `style`.  Fonts and `fetch` requests always use CORS, so for those
+    /// `crossorigin="anonymous"` is added; otherwise the preloaded response
+    /// wouldn't be used.
+    pub fn preload(&mut self, href: impl Display, as_: &str) -> LinkElement<'_> {
+        let link = self.link().rel("preload").href(href).as_(as_);
+        if as_ == "font" || as_ == "fetch" {
+            link.crossorigin("anonymous")
+        } else {
+            link
+        }
+    }
+
+    /// Writes a `<link rel="prefetch">`, which fetches a resource which a
+    /// future page will probably need
+    pub fn prefetch(&mut self, href: impl Display) -> LinkElement<'_> {
+        self.link().rel("prefetch").href(href)
+    }
+}
@@ -0,0 +1,355 @@
+use crate::{Node, Void};
+use std::fmt::Display;
+
+/// Setters for the attributes which can be used on any HTML element.
+///
+/// This trait is implemented by [`Node`], [`Void`], and the typed element
+/// wrappers.  Like [`attr()`][Node::attr], the setters consume the element
+/// and return it, so they can be chained.  Values are escaped for use in a
+/// double-quoted attribute.
+///
+/// The `title` and `style` attributes are set with
+/// [`title_attr()`][GlobalAttributes::title_attr] and
+/// [`style_attr()`][GlobalAttributes::style_attr], since `title()` and
+/// `style()` create child elements.
+pub trait GlobalAttributes: Sized {
+    /// Sets an attribute to the given value, escaping it
+    ///
+    /// Setting an attribute again replaces its value, except for `class`,
+    /// where the new classes are appended to the existing ones.
+    fn attr_value(self, name: &str, value: impl Display) -> Self;
+
+    /// Sets a boolean attribute, if `on` is true
+    fn attr_flag(self, name: &str, on: bool) -> Self;
+
+    /// Sets a unique identifier for the element
+    fn id(self, id: impl Display) -> Self {
+        self.attr_value("id", id)
+    }
+
+    /// Sets the space-separated classes of the element
+    fn class(self, class: impl Display) -> Self {
+        self.attr_value("class", class)
+    }
+
+    /// Sets advisory information about the element, usually shown as a tooltip
+    fn title_attr(self, title: impl Display) -> Self {
+        self.attr_value("title", title)
+    }
+
+    /// Sets inline CSS declarations for the element
+    fn style_attr(self, style: impl Display) -> Self {
+        self.attr_value("style", style)
+    }
+
+    /// Sets the language of the element's contents
+    fn lang(self, lang: impl Display) -> Self {
+        self.attr_value("lang", lang)
+    }
+
+    /// Sets the text direction of the element's contents (`ltr`, `rtl`, or
+    /// `auto`; see [`Dir`][crate::Dir])
+    fn dir(self, dir: impl Display) -> Self {
+        self.attr_value("dir", dir)
+    }
+
+    /// Sets the position of the element in the sequential focus order
+    fn tabindex(self, index: i32) -> Self {
+        self.attr_value("tabindex", index)
+    }
+
+    /// Sets a keyboard shortcut for focusing the element
+    fn accesskey(self, key: impl Display) -> Self {
+        self.attr_value("accesskey", key)
+    }
+
+    /// Hides the element
+    fn hidden(self, on: bool) -> Self {
+        self.attr_flag("hidden", on)
+    }
+
+    /// Makes the element and its contents non-interactive
+    fn inert(self, on: bool) -> Self {
+        self.attr_flag("inert", on)
+    }
+
+    /// Focuses the element when the page is loaded
+    fn autofocus(self, on: bool) -> Self {
+        self.attr_flag("autofocus", on)
+    }
+
+    /// Sets whether the element's contents are editable
+    fn contenteditable(self, editable: bool) -> Self {
+        self.attr_value("contenteditable", editable)
+    }
+
+    /// Sets whether the element can be dragged
+    fn draggable(self, draggable: bool) -> Self {
+        self.attr_value("draggable", draggable)
+    }
+
+    /// Sets whether the element's contents should be spell-checked
+    fn spellcheck(self, spellcheck: bool) -> Self {
+        self.attr_value("spellcheck", spellcheck)
+    }
+
+    /// Sets whether the element's contents should be translated
+    fn translate(self, translate: bool) -> Self {
+        self.attr_value("translate", if translate { "yes" } else { "no" })
+    }
+
+    /// Sets the ARIA role of the element
+    fn role(self, role: impl Display) -> Self {
+        self.attr_value("role", role)
+    }
+
+    /// Sets a custom `data-*` attribute
+    ///
+    /// If `name` contains characters which aren't allowed in an attribute
+    /// name, such as whitespace, quotes, `=`, `/`, or `>`, the attribute is
+    /// dropped (see [strict mode][crate::Buffer::strict]).
+    fn data_attr(self, name: &str, value: impl Display) -> Self {
+        self.attr_value(&format!("data-{}", name), value)
+    }
+
+    /// Sets an `aria-*` attribute
+    ///
+    /// Like [`data_attr()`][GlobalAttributes::data_attr], the attribute is
+    /// dropped if `name` isn't a valid attribute name.  The typed setters below cover the common states and properties.
+    /// Their boolean states are written as `"true"` or `"false"`, as ARIA
+    /// requires, rather than as HTML boolean attributes.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new();
+    /// buf.button()
+    ///     .aria_expanded(false)
+    ///     .aria_controls("menu")
+    ///     .aria("haspopup", "menu")
+    ///     .text("Menu");
+    /// buf.ul().id("menu").aria_hidden(true);
+    /// buf.a().href("/").aria_current(AriaCurrent::Page).text("Home");
+    /// assert_eq!(buf.finish(), "\
+    /// <button aria-expanded=\"false\" aria-controls=\"menu\" aria-haspopup=\"menu\">
+    /// Menu
+    /// </button>
+    /// <ul id=\"menu\" aria-hidden=\"true\">
+    /// </ul>
+    /// <a href=\"/\" aria-current=\"page\">
+    /// Home
+    /// </a>
+    /// ");
+    /// ```
+    fn aria(self, name: &str, value: impl Display) -> Self {
+        self.attr_value(&format!("aria-{}", name), value)
+    }
+
+    /// Sets the element's accessible name, when there's no visible label
+    fn aria_label(self, label: impl Display) -> Self {
+        self.attr_value("aria-label", label)
+    }
+
+    /// Sets the ids of the elements which label this one
+    fn aria_labelledby(self, ids: impl Display) -> Self {
+        self.attr_value("aria-labelledby", ids)
+    }
+
+    /// Sets the ids of the elements which describe this one
+    fn aria_describedby(self, ids: impl Display) -> Self {
+        self.attr_value("aria-describedby", ids)
+    }
+
+    /// Sets the ids of the elements whose contents or presence this one
+    /// controls
+    fn aria_controls(self, ids: impl Display) -> Self {
+        self.attr_value("aria-controls", ids)
+    }
+
+    /// Sets whether the element is hidden from assistive technologies
+    fn aria_hidden(self, hidden: bool) -> Self {
+        self.attr_value("aria-hidden", hidden)
+    }
+
+    /// Sets whether the element, or the element it controls, is expanded
+    fn aria_expanded(self, expanded: bool) -> Self {
+        self.attr_value("aria-expanded", expanded)
+    }
+
+    /// Sets whether the element is selected, eg. a tab or an option
+    fn aria_selected(self, selected: bool) -> Self {
+        self.attr_value("aria-selected", selected)
+    }
+
+    /// Sets whether the element is perceivable but disabled
+    fn aria_disabled(self, disabled: bool) -> Self {
+        self.attr_value("aria-disabled", disabled)
+    }
+
+    /// Sets whether the element is being updated, so assistive technologies
+    /// should wait before announcing it
+    fn aria_busy(self, busy: bool) -> Self {
+        self.attr_value("aria-busy", busy)
+    }
+
+    /// Marks the element as the current item within a set (see
+    /// [`AriaCurrent`][crate::AriaCurrent])
+    fn aria_current(self, current: impl Display) -> Self {
+        self.attr_value("aria-current", current)
+    }
+
+    /// Sets how updates to the element are announced (see
+    /// [`AriaLive`][crate::AriaLive])
+    fn aria_live(self, live: impl Display) -> Self {
+        self.attr_value("aria-live", live)
+    }
+}
+
+impl<'a> GlobalAttributes for Node<'a> {
+    fn attr_value(mut self, name: &str, value: impl Display) -> Self {
+        self.ctx
+            .get()
+            .attr_value(self.depth, name, &value.to_string());
+        self
+    }
+
+    fn attr_flag(mut self, name: &str, on: bool) -> Self {
+        if on {
+            self.ctx.get().attr_flag(self.depth, name);
+        }
+        self
+    }
+}
+
+impl<'a> GlobalAttributes for Void<'a> {
+    fn attr_value(self, name: &str, value: impl Display) -> Self {
+        self.ctx.attr_value(self.depth, name, &value.to_string());
+        self
+    }
+
+    fn attr_flag(self, name: &str, on: bool) -> Self {
+        if on {
+            self.ctx.attr_flag(self.depth, name);
+        }
+        self
+    }
+}
+
+macro_rules! handlers {
+    ($($(#[$m:meta])* $method:ident,)*) => {$(
+        $(#[$m])*
+        fn $method(self, script: impl Display) -> Self {
+            self.attr_value(stringify!($method), script)
+        }
+    )*};
+}
+
+/// Setters for the standard event handler attributes.
+///
+/// The script is escaped for use in a double-quoted attribute, so it's fine
+/// for it to contain quotes:
+///
+/// ```
+/// # use html_builder::*;
+/// let mut buf = Buffer::new();
+/// buf.button().onclick("alert(\"Hello!\")");
+/// assert_eq!(
+///     buf.finish(),
+///     "<button onclick=\"alert(&quot;Hello!&quot;)\">\n</button>\n",
+/// );
+/// ```
+///
+/// This trait is implemented for everything which implements
+/// [`GlobalAttributes`].
+pub trait EventHandlers: GlobalAttributes {
+    handlers! {
+        /// Sets the handler for the `abort` event
+        onabort,
+        /// Sets the handler for the `auxclick` event
+        onauxclick,
+        /// Sets the handler for the `beforeinput` event
+        onbeforeinput,
+        /// Sets the handler for the `blur` event
+        onblur,
+        /// Sets the handler for the `cancel` event
+        oncancel,
+        /// Sets the handler for the `change` event
+        onchange,
+        /// Sets the handler for the `click` event
+        onclick,
+        /// Sets the handler for the `close` event
+        onclose,
+        /// Sets the handler for the `contextmenu` event
+        oncontextmenu,
+        /// Sets the handler for the `copy` event
+        oncopy,
+        /// Sets the handler for the `cut` event
+        oncut,
+        /// Sets the handler for the `dblclick` event
+        ondblclick,
+        /// Sets the handler for the `drag` event
+        ondrag,
+        /// Sets the handler for the `dragend` event
+        ondragend,
+        /// Sets the handler for the `dragenter` event
+        ondragenter,
+        /// Sets the handler for the `dragleave` event
+        ondragleave,
+        /// Sets the handler for the `dragover` event
+        ondragover,
+        /// Sets the handler for the `dragstart` event
+        ondragstart,
+        /// Sets the handler for the `drop` event
+        ondrop,
+        /// Sets the handler for the `error` event
+        onerror,
+        /// Sets the handler for the `focus` event
+        onfocus,
+        /// Sets the handler for the `input` event
+        oninput,
+        /// Sets the handler for the `invalid` event
+        oninvalid,
+        /// Sets the handler for the `keydown` event
+        onkeydown,
+        /// Sets the handler for the `keyup` event
+        onkeyup,
+        /// Sets the handler for the `load` event
+        onload,
+        /// Sets the handler for the `mousedown` event
+        onmousedown,
+        /// Sets the handler for the `mouseenter` event
+        onmouseenter,
+        /// Sets the handler for the `mouseleave` event
+        onmouseleave,
+        /// Sets the handler for the `mousemove` event
+        onmousemove,
+        /// Sets the handler for the `mouseout` event
+        onmouseout,
+        /// Sets the handler for the `mouseover` event
+        onmouseover,
+        /// Sets the handler for the `mouseup` event
+        onmouseup,
+        /// Sets the handler for the `paste` event
+        onpaste,
+        /// Sets the handler for the `pointerdown` event
+        onpointerdown,
+        /// Sets the handler for the `pointerup` event
+        onpointerup,
+        /// Sets the handler for the `reset` event
+        onreset,
+        /// Sets the handler for the `resize` event
+        onresize,
+        /// Sets the handler for the `scroll` event
+        onscroll,
+        /// Sets the handler for the `select` event
+        onselect,
+        /// Sets the handler for the `submit` event
+        onsubmit,
+        /// Sets the handler for the `toggle` event
+        ontoggle,
+        /// Sets the handler for the `wheel` event
+        onwheel,
+    }
+}
+
+impl<T: GlobalAttributes> EventHandlers for T {}
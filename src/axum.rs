@@ -0,0 +1,78 @@
+//! Returning buffers from axum handlers
+
+use crate::stream::TryChunks;
+use crate::{Buffer, HtmlStream};
+use axum_core::body::Body;
+use axum_core::response::{IntoResponse, Response};
+use http::header::{HeaderValue, CONTENT_TYPE};
+use http::StatusCode;
+
+const TEXT_HTML: &str = "text/html; charset=utf-8";
+
+/// Responds with the buffer's contents, as `text/html`.
+///
+/// If [`try_finish()`][Buffer::try_finish] fails, eg. because the buffer
+/// was created with a [custom sink][Buffer::with_sink], the response is a
+/// 500 Internal Server Error instead.
+///
+/// This is only available with the `axum` feature.
+///
+/// ```
+/// use axum_core::response::IntoResponse;
+/// use html_builder::*;
+/// use std::fmt::Write;
+///
+/// async fn handler() -> impl IntoResponse {
+///     let mut buf = Buffer::new();
+///     writeln!(buf.p(), "Hello!").unwrap();
+///     buf
+/// }
+/// ```
+impl IntoResponse for Buffer {
+    fn into_response(self) -> Response {
+        match self.try_finish() {
+            Ok(html) => html_response(Body::from(html)),
+            Err(e) => {
+                let mut resp = Response::new(Body::from(e.to_string()));
+                *resp.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+                resp
+            }
+        }
+    }
+}
+
+/// Responds with the output of a [streaming buffer][Buffer::streaming], as
+/// `text/html`, sending it to the client as it's written.
+///
+/// This is only available with the `axum` feature.
+///
+/// ```
+/// use axum_core::response::IntoResponse;
+/// use html_builder::*;
+/// use std::fmt::Write;
+///
+/// async fn handler() -> impl IntoResponse {
+///     let (mut buf, stream) = Buffer::streaming();
+///     tokio::spawn(async move {
+///         let mut html = buf.html();
+///         writeln!(html.head().title(), "Report").unwrap();
+///         html.flush();
+///         // ...slow queries here...
+///         writeln!(html.body(), "Done").unwrap();
+///         buf.finish_stream();
+///     });
+///     stream
+/// }
+/// ```
+impl IntoResponse for HtmlStream {
+    fn into_response(self) -> Response {
+        html_response(Body::from_stream(TryChunks(self)))
+    }
+}
+
+fn html_response(body: Body) -> Response {
+    let mut resp = Response::new(body);
+    resp.headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static(TEXT_HTML));
+    resp
+}
@@ -0,0 +1,70 @@
+use crate::{Html5, Node};
+use std::fmt;
+
+/// The direction of an element's text, for
+/// [`dir()`][crate::GlobalAttributes::dir]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir {
+    /// Left to right, eg. English
+    Ltr,
+    /// Right to left, eg. Arabic or Hebrew
+    Rtl,
+    /// Determined by the browser from the first strongly-directional
+    /// character of the contents
+    Auto,
+}
+
+impl fmt::Display for Dir {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Dir::Ltr => "ltr",
+            Dir::Rtl => "rtl",
+            Dir::Auto => "auto",
+        })
+    }
+}
+
+impl<'a> Node<'a> {
+    /// Writes text whose direction isn't known, such as a username, in a
+    /// `<bdi>` element
+    ///
+    /// Without the isolation, right-to-left user content can reorder the
+    /// surrounding text and punctuation.  Where elements aren't allowed,
+    /// such as in a `<title>` or an attribute, use [`isolate()`] instead.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new();
+    /// let mut p = buf.p().dir(Dir::Auto);
+    /// p.bidi_isolate("إيان");
+    /// p.text(": 3 posts");
+    /// assert_eq!(buf.finish(), "\
+    /// <p dir=\"auto\">
+    ///  <bdi>
+    /// إيان
+    ///  </bdi>
+    /// : 3 posts
+    /// </p>
+    /// ");
+    /// ```
+    pub fn bidi_isolate(&mut self, text: impl fmt::Display) -> &mut Self {
+        self.bdi().text(text);
+        self
+    }
+}
+
+/// Wraps text whose direction isn't known in Unicode isolate characters
+///
+/// The text is surrounded by FIRST STRONG ISOLATE (U+2068) and POP
+/// DIRECTIONAL ISOLATE (U+2069), which have the same effect as a `<bdi>`
+/// element (see [`Node::bidi_isolate()`]), but can be used anywhere.
+///
+/// ```
+/// use html_builder::*;
+///
+/// assert_eq!(isolate("إيان"), "\u{2068}إيان\u{2069}");
+/// ```
+pub fn isolate(text: impl fmt::Display) -> String {
+    format!("\u{2068}{}\u{2069}", text)
+}
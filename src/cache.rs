@@ -0,0 +1,354 @@
+use crate::amp::Amp;
+use crate::escape::Escaper;
+use crate::head::HeadItems;
+use crate::policy::Policies;
+use crate::{Attribute, Ctx, CtxRef, Encoding, Escaping, Node, Sink, Stats, ValidationError};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Remembers rendered fragments so they can be written again without
+/// re-running the code which built them.
+///
+/// Navigation bars, footers, and other blocks which are the same on every
+/// page can be rendered once with [`Node::cached()`] and then replayed.
+/// The cache is keyed by a hash which you supply; it's up to you to make
+/// sure that it covers everything the fragment depends on (eg. the current
+/// locale).
+///
+/// A fragment is replayed with the indentation of the node it's written
+/// into, so the same fragment can be used at different depths.  The
+/// [policies][crate::Buffer::csp_nonce] and
+/// [encoding][crate::Buffer::encoding] of the buffer it's written into are
+/// applied each time, so eg. every response gets its own nonce.  So is
+/// [AMP mode][crate::Buffer::amp], so the same fragment can be written into
+/// AMP and regular pages.  Items added with the
+/// [`head_manager()`][Node::head_manager] while it's rendered are recorded
+/// along with it, and added again whenever it's replayed.
+///
+/// ```
+/// use html_builder::*;
+/// use std::fmt::Write;
+///
+/// fn page(cache: &mut FragmentCache, lang: &str) -> String {
+///     let mut buf = Buffer::new();
+///     let mut body = buf.body();
+///     let (key, home) = if lang == "fr" { (1, "Accueil") } else { (0, "Home") };
+///     body.cached(cache, key, |node| {
+///         writeln!(node.nav().class("menu"), "{}", home).unwrap();
+///     });
+///     writeln!(body.p(), "Page content").unwrap();
+///     buf.finish()
+/// }
+///
+/// let mut cache = FragmentCache::new();
+/// let en = page(&mut cache, "en");
+/// let fr = page(&mut cache, "fr");
+/// assert_eq!(page(&mut cache, "en"), en);
+/// assert_eq!(page(&mut cache, "fr"), fr);
+/// assert_eq!(cache.len(), 2);
+/// ```
+#[derive(Default)]
+pub struct FragmentCache {
+    fragments: HashMap<u64, Fragment>,
+}
+
+/// A fragment which is rendered once, eg. at startup, and then written into
+/// any number of buffers.
+///
+/// Headers, footers, and other blocks which never change can be rendered
+/// into a `Static` and kept in a `static`; writing one with
+/// [`Node::splice()`] costs about as much as copying the output, without
+/// re-running the code which built it.  Like a [`FragmentCache`] entry, it's
+/// written with the indentation of the node it's spliced into.
+///
+/// ```
+/// use html_builder::*;
+/// use std::sync::OnceLock;
+///
+/// fn footer() -> &'static Static {
+///     static FOOTER: OnceLock<Static> = OnceLock::new();
+///     FOOTER.get_or_init(|| {
+///         Static::new(|node| {
+///             let mut footer = node.footer();
+///             footer.a().href("/about").text("About");
+///             footer.script().raw().text("track();");
+///         })
+///     })
+/// }
+///
+/// let mut buf = Buffer::new().csp_nonce("r4nd0m");
+/// let mut body = buf.body();
+/// body.p().text("Content");
+/// body.splice(footer());
+/// assert_eq!(buf.finish(), "\
+/// <body>
+///  <p>
+/// Content
+///  </p>
+///  <footer>
+///   <a href=\"/about\">
+/// About
+///   </a>
+///   <script nonce=\"r4nd0m\">
+/// track();
+///   </script>
+///  </footer>
+/// </body>
+/// ");
+/// ```
+///
+/// The fragment is rendered without any of a buffer's settings, but the
+/// [policies][crate::Buffer::csp_nonce], [encoding][crate::Buffer::encoding],
+/// and [AMP mode][crate::Buffer::amp] of the buffer it's spliced into are
+/// applied to it, as above.  Anything else which depends on the buffer, such
+/// as [translations][crate::Buffer::translator], isn't available.
+pub struct Static(Fragment);
+
+impl Static {
+    /// Renders a fragment with `f`, which should add children to the node
+    /// it's given
+    pub fn new(f: impl FnOnce(&mut Node)) -> Static {
+        Static(Fragment::render(&Ctx::default(), 0, f))
+    }
+}
+
+struct Fragment {
+    events: Vec<Event>,
+    errors: Vec<ValidationError>,
+    /// Items added with the [`HeadManager`][crate::HeadManager]
+    head: HeadItems,
+    /// With depths relative to the fragment
+    stats: Stats,
+}
+
+/// A call to a [`Sink`] method, with the depth relative to the fragment
+pub(crate) enum Event {
+    StartTag(usize, String, Vec<Attribute>),
+    VoidTag(usize, String, Vec<Attribute>, bool),
+    EndTag(usize, String),
+    Text(String),
+    Raw(String),
+    StartComment(usize),
+    EndComment(usize),
+    StartConditional(usize, String),
+    EndConditional(usize),
+}
+
+/// Records the events of a fragment being rendered
+#[derive(Default)]
+pub(crate) struct Recorder {
+    pub(crate) base: usize,
+    pub(crate) events: Vec<Event>,
+}
+
+impl Sink for Recorder {
+    fn start_tag(&mut self, depth: usize, tag: &str, attrs: &[Attribute]) {
+        let depth = depth - self.base;
+        self.events
+            .push(Event::StartTag(depth, tag.into(), attrs.to_vec()));
+    }
+
+    fn void_tag(&mut self, depth: usize, tag: &str, attrs: &[Attribute], self_closing: bool) {
+        let depth = depth - self.base;
+        self.events.push(Event::VoidTag(
+            depth,
+            tag.into(),
+            attrs.to_vec(),
+            self_closing,
+        ));
+    }
+
+    fn end_tag(&mut self, depth: usize, tag: &str) {
+        let depth = depth - self.base;
+        self.events.push(Event::EndTag(depth, tag.into()));
+    }
+
+    fn text(&mut self, text: &str) {
+        self.events.push(Event::Text(text.into()));
+    }
+
+    fn raw(&mut self, html: &str) {
+        self.events.push(Event::Raw(html.into()));
+    }
+
+    fn start_comment(&mut self, depth: usize) {
+        let depth = depth - self.base;
+        self.events.push(Event::StartComment(depth));
+    }
+
+    fn end_comment(&mut self, depth: usize) {
+        let depth = depth - self.base;
+        self.events.push(Event::EndComment(depth));
+    }
+
+    fn start_conditional(&mut self, depth: usize, cond: &str) {
+        let depth = depth - self.base;
+        self.events
+            .push(Event::StartConditional(depth, cond.into()));
+    }
+
+    fn end_conditional(&mut self, depth: usize) {
+        let depth = depth - self.base;
+        self.events.push(Event::EndConditional(depth));
+    }
+}
+
+impl FragmentCache {
+    /// Creates an empty cache
+    pub fn new() -> FragmentCache {
+        FragmentCache::default()
+    }
+
+    /// The number of fragments in the cache
+    pub fn len(&self) -> usize {
+        self.fragments.len()
+    }
+
+    /// Returns `true` if the cache contains no fragments
+    pub fn is_empty(&self) -> bool {
+        self.fragments.is_empty()
+    }
+
+    /// Forgets all the fragments
+    pub fn clear(&mut self) {
+        self.fragments.clear();
+    }
+}
+
+impl Fragment {
+    fn render(ctx: &Ctx, depth: usize, f: impl FnOnce(&mut Node)) -> Fragment {
+        let mut sub = Ctx {
+            wtr: Box::new(Recorder {
+                base: depth,
+                events: vec![],
+            }),
+            stack: ctx.stack.clone(),
+            xml: ctx.xml,
+            strict: ctx.strict,
+            policies: ctx.policies.for_recording(),
+            translator: ctx.translator.clone(),
+            ..Ctx::default()
+        };
+        f(&mut Node {
+            depth,
+            ctx: CtxRef::Borrowed(&mut sub),
+            escaping: Escaping::Normal,
+        });
+        sub.close_deeper_than(depth);
+        let recorder: Box<Recorder> = sub.wtr.into_any().downcast().unwrap();
+        let mut stats = sub.stats;
+        stats.max_depth = stats.max_depth.saturating_sub(depth);
+        Fragment {
+            events: recorder.events,
+            errors: sub.errors,
+            head: sub.head,
+            stats,
+        }
+    }
+
+    /// Writes the fragment into `ctx` at depth `base`, applying `ctx`'s
+    /// policies and AMP mode, which the fragment was rendered without
+    fn replay(&self, ctx: &mut Ctx, base: usize) {
+        ctx.errors.extend_from_slice(&self.errors);
+        ctx.head.merge(&self.head);
+        ctx.stats.merge(&self.stats, base);
+        ctx.mid_line = false;
+        ctx.trailing_space = false;
+        let Ctx {
+            wtr: sink,
+            policies,
+            amp,
+            errors,
+            ..
+        } = ctx;
+        for event in &self.events {
+            match event {
+                Event::StartTag(depth, tag, attrs) => {
+                    let (tag, attrs) = start_tag(tag, attrs, policies, amp, errors);
+                    sink.start_tag(base + depth, tag, &attrs);
+                }
+                Event::VoidTag(depth, tag, attrs, self_closing) => {
+                    let (tag, attrs) = start_tag(tag, attrs, policies, amp, errors);
+                    if tag == "amp-img" {
+                        // `<amp-img>` isn't a void element
+                        sink.start_tag(base + depth, tag, &attrs);
+                        sink.end_tag(base + depth, tag);
+                    } else {
+                        sink.void_tag(base + depth, tag, &attrs, *self_closing);
+                    }
+                }
+                Event::EndTag(depth, tag) => {
+                    let tag = match amp {
+                        Some(_) => Amp::rename(tag).unwrap_or(tag),
+                        None => tag,
+                    };
+                    sink.end_tag(base + depth, tag);
+                }
+                Event::Text(text) if policies.encoding != Encoding::Minimal => {
+                    let mut escaper = Escaper {
+                        wtr: &mut **sink,
+                        safe: false,
+                        encoding: policies.encoding,
+                    };
+                    // Writing to a sink never fails
+                    escaper.write_str(text).unwrap();
+                }
+                Event::Text(text) => sink.text(text),
+                Event::Raw(html) => sink.raw(html),
+                Event::StartComment(depth) => sink.start_comment(base + depth),
+                Event::EndComment(depth) => sink.end_comment(base + depth),
+                Event::StartConditional(depth, cond) => sink.start_conditional(base + depth, cond),
+                Event::EndConditional(depth) => sink.end_conditional(base + depth),
+            }
+        }
+    }
+}
+
+/// The name which an element from a fragment is written with, and its
+/// attributes after `policies` and AMP mode have been applied
+fn start_tag<'t>(
+    tag: &'t str,
+    attrs: &[Attribute],
+    policies: &Policies,
+    amp: &mut Option<Amp>,
+    errors: &mut Vec<ValidationError>,
+) -> (&'t str, Vec<Attribute>) {
+    let mut attrs = attrs.to_vec();
+    let tag = match amp {
+        Some(amp) => {
+            let tag = Amp::rename(tag).unwrap_or(tag);
+            amp.start_tag(tag, &mut attrs, errors);
+            amp.opened(tag);
+            tag
+        }
+        None => tag,
+    };
+    policies.apply(tag, &mut attrs);
+    (tag, attrs)
+}
+
+impl<'a> Node<'a> {
+    /// Writes a fragment from the cache, rendering it with `f` if it isn't
+    /// there yet
+    ///
+    /// The fragment is written into this node: `f` should add children to
+    /// the node it's given.  See [`FragmentCache`].
+    pub fn cached(&mut self, cache: &mut FragmentCache, key: u64, f: impl FnOnce(&mut Node)) {
+        let depth = self.depth;
+        let ctx = self.ctx.get();
+        ctx.close_deeper_than(depth);
+        let fragment = cache
+            .fragments
+            .entry(key)
+            .or_insert_with(|| Fragment::render(ctx, depth, f));
+        fragment.replay(ctx, depth);
+    }
+
+    /// Writes a [`Static`] fragment into this node
+    pub fn splice(&mut self, fragment: &Static) {
+        let depth = self.depth;
+        let ctx = self.ctx.get();
+        ctx.close_deeper_than(depth);
+        fragment.0.replay(ctx, depth);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::Buffer;
+
+/// An iterator over the contents of a finished [`Buffer`], in chunks.
+///
+/// This is created by [`Buffer::into_chunks()`].
+pub struct Chunks {
+    text: String,
+    pos: usize,
+    size: usize,
+}
+
+impl Buffer {
+    /// Closes all open tags and returns the buffer's contents in chunks of
+    /// roughly `size` bytes.
+    ///
+    /// The chunks end at line breaks where possible.  If there's no line
+    /// break within `size` bytes, as in [minified][crate::Format::minify]
+    /// output, a chunk ends after a `>` instead.  A chunk is only bigger
+    /// than `size` if it has neither within `size` bytes, eg. a long line of
+    /// text.  Chunk boundaries usually fall between tags, but not always: a
+    /// chunk can end inside a start tag which is
+    /// [wrapped][crate::Format::wrap_attributes], or inside a `<script>`
+    /// which contains a `>`.  That doesn't matter for chunked transfer
+    /// encoding, which this is meant for.
+    ///
+    /// The whole document is rendered before the first chunk is returned, so
+    /// this doesn't send anything early.  To stream the output as it's
+    /// written, use [`Buffer::to_writer()`].
+    ///
+    /// ```
+    /// use html_builder::*;
+    /// use std::fmt::Write;
+    ///
+    /// let mut buf = Buffer::new();
+    /// let mut list = buf.ul();
+    /// for i in 0..3 {
+    ///     writeln!(list.li(), "{}", i)?;
+    /// }
+    /// let chunks = buf.into_chunks(20).collect::<Vec<_>>();
+    /// assert_eq!(chunks, [
+    ///     "<ul>\n <li>\n0\n </li>\n",
+    ///     " <li>\n1\n </li>\n",
+    ///     " <li>\n2\n </li>\n",
+    ///     "</ul>\n",
+    /// ]);
+    /// # Ok::<(), std::fmt::Error>(())
+    /// ```
+    pub fn into_chunks(self, size: usize) -> Chunks {
+        Chunks {
+            text: self.finish(),
+            pos: 0,
+            size,
+        }
+    }
+}
+
+impl Iterator for Chunks {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let rest = &self.text[self.pos..];
+        if rest.is_empty() {
+            return None;
+        }
+        let end = if rest.len() <= self.size {
+            rest.len()
+        } else {
+            let mut limit = self.size;
+            while !rest.is_char_boundary(limit) {
+                limit -= 1;
+            }
+            let head = &rest[..limit];
+            match head.rfind('\n').or_else(|| head.rfind('>')) {
+                Some(i) => i + 1,
+                None => rest.find(['\n', '>']).map_or(rest.len(), |i| i + 1),
+            }
+        };
+        self.pos += end;
+        Some(rest[..end].to_string())
+    }
+}
@@ -0,0 +1,106 @@
+use crate::Node;
+
+impl<'a> Node<'a> {
+    /// Calls `f` for each item, to write something into this node
+    ///
+    /// This saves binding the node to a variable just to loop over it.
+    ///
+    /// ```
+    /// use html_builder::*;
+    /// use std::fmt::Write;
+    ///
+    /// let mut buf = Buffer::new();
+    /// buf.ul().class("fruit").render_each(["apples", "pears"], |ul, fruit| {
+    ///     writeln!(ul.li(), "{}", fruit)
+    /// })?;
+    /// assert_eq!(buf.finish(), "\
+    /// <ul class=\"fruit\">
+    ///  <li>
+    /// apples
+    ///  </li>
+    ///  <li>
+    /// pears
+    ///  </li>
+    /// </ul>
+    /// ");
+    /// # Ok::<(), std::fmt::Error>(())
+    /// ```
+    pub fn render_each<I: IntoIterator>(
+        &mut self,
+        iter: I,
+        mut f: impl FnMut(&mut Node, I::Item) -> std::fmt::Result,
+    ) -> std::fmt::Result {
+        for item in iter {
+            f(self, item)?;
+        }
+        Ok(())
+    }
+
+    /// Calls `f` to write something into this node, if `cond` is true
+    ///
+    /// ```
+    /// use html_builder::*;
+    /// use std::fmt::Write;
+    ///
+    /// let logged_in = false;
+    /// let mut buf = Buffer::new();
+    /// buf.nav()
+    ///     .when(logged_in, |nav| writeln!(nav.a().href("/logout"), "Log out"))?
+    ///     .when(!logged_in, |nav| writeln!(nav.a().href("/login"), "Log in"))?;
+    /// assert_eq!(buf.finish(), "\
+    /// <nav>
+    ///  <a href=\"/login\">
+    /// Log in
+    ///  </a>
+    /// </nav>
+    /// ");
+    /// # Ok::<(), std::fmt::Error>(())
+    /// ```
+    pub fn when(
+        &mut self,
+        cond: bool,
+        f: impl FnOnce(&mut Node) -> std::fmt::Result,
+    ) -> Result<&mut Self, std::fmt::Error> {
+        if cond {
+            f(self)?;
+        }
+        Ok(self)
+    }
+
+    /// Calls `then` or `else_` to write something into this node, depending
+    /// on `cond`
+    ///
+    /// ```
+    /// use html_builder::*;
+    /// use std::fmt::Write;
+    ///
+    /// let items: Vec<&str> = vec![];
+    /// let mut buf = Buffer::new();
+    /// buf.div().if_else(
+    ///     items.is_empty(),
+    ///     |div| writeln!(div.p(), "Nothing here"),
+    ///     |div| div.ul().render_each(&items, |ul, x| writeln!(ul.li(), "{}", x)),
+    /// )?;
+    /// assert_eq!(buf.finish(), "\
+    /// <div>
+    ///  <p>
+    /// Nothing here
+    ///  </p>
+    /// </div>
+    /// ");
+    /// # Ok::<(), std::fmt::Error>(())
+    /// ```
+    pub fn if_else(
+        &mut self,
+        cond: bool,
+        then: impl FnOnce(&mut Node) -> std::fmt::Result,
+        else_: impl FnOnce(&mut Node) -> std::fmt::Result,
+    ) -> Result<&mut Self, std::fmt::Error> {
+        if cond {
+            then(self)?;
+        } else {
+            else_(self)?;
+        }
+        Ok(self)
+    }
+}
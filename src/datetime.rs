@@ -0,0 +1,76 @@
+//! Integration with the `chrono` and `time` crates
+
+use crate::{Html5, Node};
+
+/// A point in time which can be written into a `<time>` element.
+///
+/// This trait is only available with the `chrono` or `time` feature.  It's
+/// implemented for `chrono::DateTime` and `time::OffsetDateTime`.
+pub trait Timestamp {
+    /// The machine-readable form, in RFC 3339 format
+    fn rfc3339(&self) -> String;
+
+    /// The human-readable form, eg. "12 April 2021 14:30"
+    fn human(&self) -> String;
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> Timestamp for chrono::DateTime<Tz>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    fn rfc3339(&self) -> String {
+        self.to_rfc3339()
+    }
+
+    fn human(&self) -> String {
+        self.format("%-d %B %Y %H:%M").to_string()
+    }
+}
+
+#[cfg(feature = "time")]
+impl Timestamp for time::OffsetDateTime {
+    fn rfc3339(&self) -> String {
+        self.format(&time::format_description::well_known::Rfc3339)
+            .expect("the year is out of range for RFC 3339")
+    }
+
+    fn human(&self) -> String {
+        format!(
+            "{} {} {} {:02}:{:02}",
+            self.day(),
+            self.month(),
+            self.year(),
+            self.hour(),
+            self.minute(),
+        )
+    }
+}
+
+impl<'a> Node<'a> {
+    /// Write a `<time>` element for the given point in time
+    ///
+    /// This method is only available with the `chrono` or `time` feature.
+    /// The `datetime` attribute is set to the RFC 3339 form, and the
+    /// contents are a human-readable form.
+    ///
+    /// ```
+    /// # #[cfg(feature = "chrono")] {
+    /// use html_builder::*;
+    /// use chrono::TimeZone;
+    ///
+    /// let when = chrono::Utc.with_ymd_and_hms(2021, 4, 12, 14, 30, 0).unwrap();
+    /// let mut buf = Buffer::new();
+    /// buf.time_datetime(when);
+    /// assert_eq!(buf.finish(), "\
+    /// <time datetime=\"2021-04-12T14:30:00+00:00\">
+    /// 12 April 2021 14:30
+    /// </time>
+    /// ");
+    /// # }
+    /// ```
+    pub fn time_datetime(&mut self, when: impl Timestamp) -> &mut Self {
+        self.time().datetime(when.rfc3339()).text(when.human());
+        self
+    }
+}
@@ -0,0 +1,138 @@
+//! Hashing the output as it's written
+
+use crate::sink::StringSink;
+use crate::{Attribute, Buffer, Format, Sink};
+use sha2::{Digest, Sha256};
+
+/// Formats the output as HTML, like a `String`, and hashes it as it goes
+#[derive(Clone)]
+pub(crate) struct DigestSink {
+    buf: StringSink,
+    hasher: Sha256,
+    /// How much of `buf` has been hashed
+    hashed: usize,
+}
+
+impl DigestSink {
+    fn new() -> DigestSink {
+        DigestSink {
+            buf: StringSink::default(),
+            hasher: Sha256::new(),
+            hashed: 0,
+        }
+    }
+
+    fn update(&mut self) {
+        // Hold back a final newline, in case it's the end of the document
+        let mut end = self.buf.out.len();
+        if self.buf.out.ends_with('\n') {
+            end -= 1;
+        }
+        if end > self.hashed {
+            self.hasher
+                .update(&self.buf.out.as_bytes()[self.hashed..end]);
+            self.hashed = end;
+        }
+    }
+
+    fn finish(mut self) -> (String, [u8; 32]) {
+        self.hasher.update(&self.buf.out.as_bytes()[self.hashed..]);
+        (self.buf.out, self.hasher.finalize().into())
+    }
+}
+
+impl Sink for DigestSink {
+    fn start_tag(&mut self, depth: usize, tag: &str, attrs: &[Attribute]) {
+        self.buf.start_tag(depth, tag, attrs);
+        self.update();
+    }
+
+    fn void_tag(&mut self, depth: usize, tag: &str, attrs: &[Attribute], self_closing: bool) {
+        self.buf.void_tag(depth, tag, attrs, self_closing);
+        self.update();
+    }
+
+    fn end_tag(&mut self, depth: usize, tag: &str) {
+        self.buf.end_tag(depth, tag);
+        self.update();
+    }
+
+    fn text(&mut self, text: &str) {
+        self.buf.text(text);
+        self.update();
+    }
+
+    fn raw(&mut self, html: &str) {
+        self.buf.raw(html);
+        self.update();
+    }
+
+    fn start_comment(&mut self, depth: usize) {
+        self.buf.start_comment(depth);
+    }
+
+    fn end_comment(&mut self, depth: usize) {
+        self.buf.end_comment(depth);
+        self.update();
+    }
+
+    fn start_conditional(&mut self, depth: usize, cond: &str) {
+        self.buf.start_conditional(depth, cond);
+        self.update();
+    }
+
+    fn end_conditional(&mut self, depth: usize) {
+        self.buf.end_conditional(depth);
+        self.update();
+    }
+
+    fn set_format(&mut self, format: &Format) {
+        self.buf.set_format(format);
+    }
+
+    fn end(&mut self) {
+        self.buf.end();
+    }
+}
+
+impl Buffer {
+    /// Creates a new empty buffer which computes a SHA-256 digest of its
+    /// output as it's written.
+    ///
+    /// This method is only available with the `digest` feature.  Use
+    /// [`finish_with_digest()`][Buffer::finish_with_digest] to get the
+    /// output and its digest, eg. for a strong `ETag` header, without
+    /// hashing the whole output again afterwards.
+    /// [Placeholders][crate::Node::placeholder] can't be used with this
+    /// kind of buffer.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::with_digest();
+    /// buf.p().text("Hello");
+    /// let (html, digest) = buf.finish_with_digest();
+    /// assert_eq!(html, "<p>\nHello\n</p>\n");
+    ///
+    /// let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+    /// let etag = format!("\"{}\"", hex);
+    /// assert_eq!(
+    ///     etag,
+    ///     "\"2e661653e44150ce8ec4f595f6f25b6ddc823455a41ed90feedbdc70edb3a289\"",
+    /// );
+    /// ```
+    pub fn with_digest() -> Buffer {
+        Buffer::with_sink(DigestSink::new())
+    }
+
+    /// Closes all open tags, and returns the buffer's contents along with
+    /// their SHA-256 digest.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the buffer wasn't created by
+    /// [`with_digest()`][Buffer::with_digest].
+    pub fn finish_with_digest(self) -> (String, [u8; 32]) {
+        self.into_sink::<DigestSink>().finish()
+    }
+}
@@ -0,0 +1,260 @@
+//! Rendering into a live DOM
+
+use crate::{Attribute, Buffer, Sink};
+use send_wrapper::SendWrapper;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Document, Element, HtmlTemplateElement};
+
+const HTML_NAMESPACE: &str = "http://www.w3.org/1999/xhtml";
+const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+const MATHML_NAMESPACE: &str = "http://www.w3.org/1998/Math/MathML";
+
+/// A [`Sink`] which creates DOM nodes, rather than writing text.
+///
+/// This is only available with the `wasm` feature.  It lets the same code
+/// render HTML on the server and DOM nodes in the browser: create a buffer
+/// with [`Buffer::dom()`], and the elements, text, and comments written to
+/// it are appended to the given parent node as they're completed.
+///
+/// `<svg>` and `<math>` elements, and their descendants, are created in the
+/// right namespace.  Raw markup is parsed by the browser, and so are
+/// attributes added with [`attr()`][crate::Node::attr].
+///
+/// The sink can only be used on the thread which created it, and it panics
+/// if it's dropped or accessed on another thread.
+pub struct DomSink(SendWrapper<State>);
+
+struct State {
+    document: Document,
+    /// The nodes which new nodes are appended to; the first is the parent
+    /// given to [`DomSink::new()`]
+    stack: Vec<web_sys::Node>,
+    /// The text of a comment which is being written
+    comment: Option<String>,
+    /// The first error returned by the DOM
+    error: Option<JsValue>,
+}
+
+impl DomSink {
+    /// Creates a sink which appends nodes to `parent`
+    ///
+    /// `parent` can be an element, a document fragment, or a document.
+    pub fn new(parent: &web_sys::Node) -> DomSink {
+        let document = match parent.owner_document() {
+            Some(document) => document,
+            None => parent
+                .dyn_ref::<Document>()
+                .expect("the parent isn't in a document")
+                .clone(),
+        };
+        DomSink(SendWrapper::new(State {
+            document,
+            stack: vec![parent.clone()],
+            comment: None,
+            error: None,
+        }))
+    }
+
+    /// Returns the first error which the DOM returned, if any
+    ///
+    /// After an error, the sink carries on, but the document may be
+    /// missing some nodes or attributes.
+    pub fn finish(self) -> Result<(), JsValue> {
+        let state = self.0.take();
+        match state.error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+impl State {
+    fn parent(&self) -> &web_sys::Node {
+        self.stack.last().unwrap()
+    }
+
+    /// Records the error, if it's the first one
+    fn check<T>(&mut self, result: Result<T, JsValue>) -> Option<T> {
+        match result {
+            Ok(x) => Some(x),
+            Err(e) => {
+                self.error.get_or_insert(e);
+                None
+            }
+        }
+    }
+
+    fn append(&mut self, node: &web_sys::Node) {
+        let result = self.parent().append_child(node);
+        self.check(result);
+    }
+
+    /// Creates an element, in the namespace it would get from the parser
+    fn create_element(&self, tag: &str) -> Result<Element, JsValue> {
+        let namespace = match tag {
+            "svg" => SVG_NAMESPACE.to_string(),
+            "math" => MATHML_NAMESPACE.to_string(),
+            _ => match self.parent().dyn_ref::<Element>() {
+                Some(parent) if parent.local_name() != "foreignObject" => parent
+                    .namespace_uri()
+                    .unwrap_or_else(|| HTML_NAMESPACE.to_string()),
+                _ => HTML_NAMESPACE.to_string(),
+            },
+        };
+        if namespace == HTML_NAMESPACE {
+            self.document.create_element(tag)
+        } else {
+            self.document.create_element_ns(Some(&namespace), tag)
+        }
+    }
+
+    fn set_attrs(&self, element: &Element, attrs: &[Attribute]) -> Result<(), JsValue> {
+        for attr in attrs {
+            match attr {
+                Attribute::Value(name, value) => element.set_attribute(name, value)?,
+                Attribute::Flag(name) => element.set_attribute(name, "")?,
+                Attribute::Raw(raw) => {
+                    // Let the browser parse the attributes, then copy them
+                    let template = self.parse(&format!("<b {}></b>", raw))?;
+                    if let Some(parsed) = template.content().first_element_child() {
+                        let parsed = parsed.attributes();
+                        for i in 0..parsed.length() {
+                            if let Some(attr) = parsed.item(i) {
+                                element.set_attribute(&attr.name(), &attr.value())?;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn element(&mut self, tag: &str, attrs: &[Attribute]) -> Option<Element> {
+        let result = self
+            .create_element(tag)
+            .and_then(|element| self.set_attrs(&element, attrs).map(|()| element));
+        self.check(result)
+    }
+
+    /// Parses some HTML into a `<template>`
+    fn parse(&self, html: &str) -> Result<HtmlTemplateElement, JsValue> {
+        let template: HtmlTemplateElement =
+            self.document.create_element("template")?.unchecked_into();
+        template.set_inner_html(html);
+        Ok(template)
+    }
+
+    fn append_comment(&mut self, text: &str) {
+        let comment = self.document.create_comment(text);
+        self.append(&comment);
+    }
+}
+
+impl Sink for DomSink {
+    fn start_tag(&mut self, _depth: usize, tag: &str, attrs: &[Attribute]) {
+        let state = &mut *self.0;
+        match state.element(tag, attrs) {
+            Some(element) => {
+                state.append(&element);
+                state.stack.push(element.into());
+            }
+            // Put the children in the parent instead
+            None => state.stack.push(state.parent().clone()),
+        }
+    }
+
+    fn void_tag(&mut self, _depth: usize, tag: &str, attrs: &[Attribute], _self_closing: bool) {
+        // The doctype is already part of the document
+        if tag.starts_with('!') {
+            return;
+        }
+        let state = &mut *self.0;
+        if let Some(element) = state.element(tag, attrs) {
+            state.append(&element);
+        }
+    }
+
+    fn end_tag(&mut self, _depth: usize, _tag: &str) {
+        self.0.stack.pop();
+    }
+
+    fn text(&mut self, text: &str) {
+        let state = &mut *self.0;
+        let node = state.document.create_text_node(text);
+        state.append(&node);
+    }
+
+    fn raw(&mut self, html: &str) {
+        let state = &mut *self.0;
+        if let Some(comment) = &mut state.comment {
+            comment.push_str(html);
+            return;
+        }
+        let result = state.parse(html);
+        if let Some(template) = state.check(result) {
+            state.append(&template.content());
+        }
+    }
+
+    fn start_comment(&mut self, _depth: usize) {
+        self.0.comment = Some(String::new());
+    }
+
+    fn end_comment(&mut self, _depth: usize) {
+        let state = &mut *self.0;
+        if let Some(text) = state.comment.take() {
+            state.append_comment(&text);
+        }
+    }
+
+    fn start_conditional(&mut self, _depth: usize, cond: &str) {
+        self.0.append_comment(&format!("[{}]>", cond));
+    }
+
+    fn end_conditional(&mut self, _depth: usize) {
+        self.0.append_comment("<![endif]");
+    }
+}
+
+impl Buffer {
+    /// Creates a new empty buffer which appends DOM nodes to `parent`,
+    /// rather than writing text.
+    ///
+    /// This method is only available with the `wasm` feature; see
+    /// [`DomSink`].  Use [`finish_dom()`][Buffer::finish_dom] to close the
+    /// open elements and check for errors.
+    ///
+    /// ```no_run
+    /// use html_builder::*;
+    ///
+    /// fn greeting(node: &mut Node, name: &str) {
+    ///     node.p().class("greeting").text(format!("Hello, {}!", name));
+    /// }
+    ///
+    /// // On the server
+    /// let mut buf = Buffer::new();
+    /// greeting(&mut buf, "Alice");
+    /// let html = buf.finish();
+    ///
+    /// // In the browser
+    /// fn show_greeting(parent: &web_sys::Node) {
+    ///     let mut buf = Buffer::dom(parent);
+    ///     greeting(&mut buf, "Alice");
+    ///     buf.finish_dom().unwrap();
+    /// }
+    /// ```
+    pub fn dom(parent: &web_sys::Node) -> Buffer {
+        Buffer::with_sink(DomSink::new(parent))
+    }
+
+    /// Closes all open elements, and returns the first error which the DOM
+    /// returned, if any.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the buffer wasn't created by [`dom()`][Buffer::dom].
+    pub fn finish_dom(self) -> Result<(), JsValue> {
+        self.into_sink::<DomSink>().finish()
+    }
+}
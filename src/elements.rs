@@ -0,0 +1,530 @@
+//! Typed wrappers for elements which have element-specific attributes.
+//!
+//! The [`Html5`][crate::Html5] helpers for these elements return a wrapper
+//! instead of a plain [`Node`] or [`Void`].  Each wrapper has setters for the
+//! attributes defined on that element, so a misspelled attribute name is a
+//! compile error rather than silently-wrong markup.  Values are escaped for
+//! use in a double-quoted attribute.
+//!
+//! The wrappers deref to the underlying `Node`/`Void`, so everything you can
+//! do with a plain node (adding children, writing text, calling
+//! [`attr()`][Node::attr]) still works.  They also implement
+//! [`GlobalAttributes`].
+
+use crate::{GlobalAttributes, Node, Void};
+use std::fmt::{Display, Write};
+
+macro_rules! setter {
+    ($(#[$m:meta])* $method:ident: value $name:literal) => {
+        $(#[$m])*
+        pub fn $method(self, value: impl Display) -> Self {
+            self.attr_value($name, value)
+        }
+    };
+    ($(#[$m:meta])* $method:ident: url $name:literal) => {
+        $(#[$m])*
+        ///
+        /// Unsafe URLs, such as `javascript:` URLs, are replaced; see
+        /// [`is_safe_url()`][crate::is_safe_url].
+        pub fn $method(self, url: impl Display) -> Self {
+            self.attr_value($name, crate::url::guard(url.to_string()))
+        }
+    };
+    ($(#[$m:meta])* $method:ident: flag $name:literal) => {
+        $(#[$m])*
+        pub fn $method(self, on: bool) -> Self {
+            self.attr_flag($name, on)
+        }
+    };
+}
+
+macro_rules! elements {
+    ($(
+        $(#[$m:meta])*
+        $ty:ident($inner:ident) {
+            $( $(#[$am:meta])* $method:ident: $kind:ident $name:literal, )*
+        }
+    )*) => {$(
+        $(#[$m])*
+        pub struct $ty<'a>($inner<'a>);
+
+        impl<'a> $ty<'a> {
+            $( setter!($(#[$am])* $method: $kind $name); )*
+
+            /// Adds a raw attribute; see [`Node::attr`]
+            pub fn attr(self, attr: &str) -> Self {
+                $ty(self.0.attr(attr))
+            }
+        }
+
+        impl<'a> GlobalAttributes for $ty<'a> {
+            fn attr_value(self, name: &str, value: impl Display) -> Self {
+                $ty(self.0.attr_value(name, value))
+            }
+
+            fn attr_flag(self, name: &str, on: bool) -> Self {
+                $ty(self.0.attr_flag(name, on))
+            }
+        }
+
+        impl<'a> From<$inner<'a>> for $ty<'a> {
+            fn from(x: $inner<'a>) -> Self {
+                $ty(x)
+            }
+        }
+
+        impl<'a> std::ops::Deref for $ty<'a> {
+            type Target = $inner<'a>;
+            fn deref(&self) -> &$inner<'a> {
+                &self.0
+            }
+        }
+
+        impl<'a> std::ops::DerefMut for $ty<'a> {
+            fn deref_mut(&mut self) -> &mut $inner<'a> {
+                &mut self.0
+            }
+        }
+
+        elements!(@$inner $ty);
+    )*};
+    (@Node $ty:ident) => {
+        impl<'a> $ty<'a> {
+            /// Disable escaping; see [`Node::raw`]
+            pub fn raw(self) -> Self {
+                $ty(self.0.raw())
+            }
+
+            /// Escape more special characters; see [`Node::safe`]
+            pub fn safe(self) -> Self {
+                $ty(self.0.safe())
+            }
+        }
+
+        impl<'a> Write for $ty<'a> {
+            fn write_str(&mut self, s: &str) -> std::fmt::Result {
+                self.0.write_str(s)
+            }
+        }
+    };
+    (@Void $ty:ident) => {};
+}
+
+elements! {
+    /// An `<a>` element
+    AElement(Node) {
+        /// The URL that the hyperlink points to
+        href: url "href",
+        /// Where to display the linked URL (eg. `_blank`; see
+        /// [`Target`][crate::Target])
+        target: value "target",
+        /// The relationship of the linked URL (see [`LinkRel`][crate::LinkRel])
+        rel: value "rel",
+        /// Prompts the user to download the linked URL, with the given filename
+        download: value "download",
+        /// The language of the linked URL
+        hreflang: value "hreflang",
+        /// The MIME type of the linked URL
+        type_: value "type",
+        /// How much of the referrer to send when following the link (see
+        /// [`ReferrerPolicy`][crate::ReferrerPolicy])
+        referrerpolicy: value "referrerpolicy",
+    }
+
+    /// An `<area>` element
+    AreaElement(Void) {
+        /// The URL that the area points to
+        href: url "href",
+        /// Alternative text for the area
+        alt: value "alt",
+        /// The shape of the area
+        shape: value "shape",
+        /// The coordinates of the area
+        coords: value "coords",
+        /// Where to display the linked URL (see [`Target`][crate::Target])
+        target: value "target",
+    }
+
+    /// An `<audio>` element
+    AudioElement(Node) {
+        /// The URL of the audio
+        src: url "src",
+        /// Show playback controls
+        controls: flag "controls",
+        /// Start playing as soon as possible
+        autoplay: flag "autoplay",
+        /// Restart from the beginning when finished
+        loop_: flag "loop",
+        /// Mute the audio by default
+        muted: flag "muted",
+        /// How much of the audio to load ahead of time
+        preload: value "preload",
+    }
+
+    /// A `<base>` element
+    BaseElement(Void) {
+        /// The base URL for relative URLs in the document
+        href: url "href",
+        /// The default browsing context for links (see
+        /// [`Target`][crate::Target])
+        target: value "target",
+    }
+
+    /// A `<button>` element
+    ButtonElement(Node) {
+        /// The behaviour of the button (`submit`, `reset`, or `button`; see
+        /// [`ButtonType`][crate::ButtonType])
+        type_: value "type",
+        /// The name of the button, submitted with the form
+        name: value "name",
+        /// The value of the button, submitted with the form
+        value: value "value",
+        /// Prevents the user from interacting with the button
+        disabled: flag "disabled",
+        /// The id of the form which the button belongs to
+        form: value "form",
+    }
+
+    /// A `<col>` element
+    ColElement(Void) {
+        /// The number of columns spanned
+        span: value "span",
+    }
+
+    /// An `<embed>` element
+    EmbedElement(Void) {
+        /// The URL of the embedded resource
+        src: url "src",
+        /// The MIME type of the embedded resource
+        type_: value "type",
+        /// The width of the embedded resource
+        width: value "width",
+        /// The height of the embedded resource
+        height: value "height",
+    }
+
+    /// A `<form>` element
+    FormElement(Node) {
+        /// The URL which processes the form submission
+        action: url "action",
+        /// The HTTP method used to submit the form (see
+        /// [`FormMethod`][crate::FormMethod])
+        method: value "method",
+        /// The MIME type of the form submission
+        enctype: value "enctype",
+        /// Where to display the response (see [`Target`][crate::Target])
+        target: value "target",
+        /// Skip validation when the form is submitted
+        novalidate: flag "novalidate",
+    }
+
+    /// An `<iframe>` element
+    IframeElement(Node) {
+        /// The URL of the embedded page
+        src: url "src",
+        /// Inline HTML to embed, overriding `src`
+        srcdoc: value "srcdoc",
+        /// The name of the embedded browsing context
+        name: value "name",
+        /// The width of the frame
+        width: value "width",
+        /// The height of the frame
+        height: value "height",
+        /// Restrictions applied to the embedded content (see
+        /// [`Sandbox`][crate::Sandbox])
+        sandbox: value "sandbox",
+        /// The permissions policy of the frame
+        allow: value "allow",
+        /// When to load the frame (`eager` or `lazy`)
+        loading: value "loading",
+        /// How much of the referrer to send when fetching the frame (see
+        /// [`ReferrerPolicy`][crate::ReferrerPolicy])
+        referrerpolicy: value "referrerpolicy",
+    }
+
+    /// An `<img>` element
+    ImgElement(Void) {
+        /// The URL of the image
+        src: url "src",
+        /// Alternative text for the image
+        alt: value "alt",
+        /// The intrinsic width of the image
+        width: value "width",
+        /// The intrinsic height of the image
+        height: value "height",
+        /// Candidate images for different resolutions
+        srcset: value "srcset",
+        /// The display sizes for the `srcset` candidates
+        sizes: value "sizes",
+        /// When to load the image (`eager` or `lazy`)
+        loading: value "loading",
+        /// How to decode the image (`sync`, `async`, or `auto`)
+        decoding: value "decoding",
+        /// The CORS mode used to fetch the image
+        crossorigin: value "crossorigin",
+        /// How much of the referrer to send when fetching the image (see
+        /// [`ReferrerPolicy`][crate::ReferrerPolicy])
+        referrerpolicy: value "referrerpolicy",
+        /// The image map to use
+        usemap: value "usemap",
+    }
+
+    /// An `<input>` element
+    InputElement(Void) {
+        /// The type of control (see [`InputType`][crate::InputType])
+        type_: value "type",
+        /// The name of the control, submitted with the form
+        name: value "name",
+        /// The value of the control
+        value: value "value",
+        /// Hint text shown when the control is empty
+        placeholder: value "placeholder",
+        /// Whether the checkbox or radio button is checked
+        checked: flag "checked",
+        /// Prevents the user from interacting with the control
+        disabled: flag "disabled",
+        /// Prevents the user from editing the value
+        readonly: flag "readonly",
+        /// The value must be filled in before submitting
+        required: flag "required",
+        /// Allow multiple values
+        multiple: flag "multiple",
+        /// The minimum value
+        min: value "min",
+        /// The maximum value
+        max: value "max",
+        /// The granularity of the value
+        step: value "step",
+        /// The maximum length of the value
+        maxlength: value "maxlength",
+        /// A regular expression the value must match
+        pattern: value "pattern",
+        /// Hint for the browser's autofill feature
+        autocomplete: value "autocomplete",
+        /// The file types accepted by a file input
+        accept: value "accept",
+        /// The id of a `<datalist>` with suggested values
+        list: value "list",
+        /// The id of the form which the control belongs to
+        form: value "form",
+    }
+
+    /// A `<label>` element
+    LabelElement(Node) {
+        /// The id of the control being labelled
+        for_: value "for",
+    }
+
+    /// A `<link>` element
+    LinkElement(Void) {
+        /// The relationship of the linked resource (see
+        /// [`LinkRel`][crate::LinkRel])
+        rel: value "rel",
+        /// The URL of the linked resource
+        href: url "href",
+        /// The MIME type of the linked resource
+        type_: value "type",
+        /// The media which the linked resource applies to
+        media: value "media",
+        /// The sizes of the icons, for `rel="icon"`
+        sizes: value "sizes",
+        /// The language of the linked resource
+        hreflang: value "hreflang",
+        /// The type of content being loaded, for `rel="preload"`
+        as_: value "as",
+        /// The CORS mode used to fetch the linked resource
+        crossorigin: value "crossorigin",
+        /// The expected digest of the linked resource
+        integrity: value "integrity",
+        /// How much of the referrer to send when fetching the linked resource (see
+        /// [`ReferrerPolicy`][crate::ReferrerPolicy])
+        referrerpolicy: value "referrerpolicy",
+        /// Don't apply the stylesheet
+        disabled: flag "disabled",
+    }
+
+    /// A `<meta>` element
+    MetaElement(Void) {
+        /// The name of the metadata
+        name: value "name",
+        /// The value of the metadata
+        content: value "content",
+        /// The character encoding of the document
+        charset: value "charset",
+        /// A pragma directive
+        http_equiv: value "http-equiv",
+        /// The RDFa property of the metadata, as used by Open Graph
+        property: value "property",
+        /// The media which the metadata applies to
+        media: value "media",
+    }
+
+    /// An `<ol>` element
+    OlElement(Node) {
+        /// The number of the first item
+        start: value "start",
+        /// Number the items in descending order
+        reversed: flag "reversed",
+        /// The kind of marker to use
+        type_: value "type",
+    }
+
+    /// An `<option>` element
+    OptionElement(Node) {
+        /// The value submitted with the form
+        value: value "value",
+        /// Whether the option is initially selected
+        selected: flag "selected",
+        /// Prevents the user from selecting the option
+        disabled: flag "disabled",
+        /// The text shown for the option
+        label: value "label",
+    }
+
+    /// A `<script>` element
+    ScriptElement(Node) {
+        /// The URL of an external script
+        src: url "src",
+        /// The type of script
+        type_: value "type",
+        /// Execute the script as soon as it's available
+        async_: flag "async",
+        /// Execute the script after the document has been parsed
+        defer: flag "defer",
+        /// Don't execute the script in browsers which support modules
+        nomodule: flag "nomodule",
+        /// The CORS mode used to fetch the script
+        crossorigin: value "crossorigin",
+        /// The expected digest of the script
+        integrity: value "integrity",
+        /// How much of the referrer to send when fetching the script (see
+        /// [`ReferrerPolicy`][crate::ReferrerPolicy])
+        referrerpolicy: value "referrerpolicy",
+    }
+
+    /// A `<select>` element
+    SelectElement(Node) {
+        /// The name of the control, submitted with the form
+        name: value "name",
+        /// Allow multiple options to be selected
+        multiple: flag "multiple",
+        /// Prevents the user from interacting with the control
+        disabled: flag "disabled",
+        /// An option must be selected before submitting
+        required: flag "required",
+        /// The number of visible options
+        size: value "size",
+        /// The id of the form which the control belongs to
+        form: value "form",
+    }
+
+    /// A `<source>` element
+    SourceElement(Void) {
+        /// The URL of the media resource
+        src: url "src",
+        /// Candidate images for different resolutions
+        srcset: value "srcset",
+        /// The display sizes for the `srcset` candidates
+        sizes: value "sizes",
+        /// The MIME type of the media resource
+        type_: value "type",
+        /// The media which the resource applies to
+        media: value "media",
+    }
+
+    /// A `<style>` element
+    StyleElement(Node) {
+        /// The media which the styles apply to
+        media: value "media",
+    }
+
+    /// A `<td>` element
+    TdElement(Node) {
+        /// The number of columns spanned
+        colspan: value "colspan",
+        /// The number of rows spanned
+        rowspan: value "rowspan",
+        /// The ids of the header cells which apply to this cell
+        headers: value "headers",
+    }
+
+    /// A `<textarea>` element
+    TextareaElement(Node) {
+        /// The name of the control, submitted with the form
+        name: value "name",
+        /// The number of visible text lines
+        rows: value "rows",
+        /// The visible width, in characters
+        cols: value "cols",
+        /// Hint text shown when the control is empty
+        placeholder: value "placeholder",
+        /// Prevents the user from interacting with the control
+        disabled: flag "disabled",
+        /// Prevents the user from editing the value
+        readonly: flag "readonly",
+        /// The value must be filled in before submitting
+        required: flag "required",
+        /// The maximum length of the value
+        maxlength: value "maxlength",
+        /// The id of the form which the control belongs to
+        form: value "form",
+    }
+
+    /// A `<th>` element
+    ThElement(Node) {
+        /// The number of columns spanned
+        colspan: value "colspan",
+        /// The number of rows spanned
+        rowspan: value "rowspan",
+        /// The ids of the header cells which apply to this cell
+        headers: value "headers",
+        /// The cells which this header applies to (eg. `col` or `row`)
+        scope: value "scope",
+        /// An abbreviated description of the cell's contents
+        abbr: value "abbr",
+    }
+
+    /// A `<time>` element
+    TimeElement(Node) {
+        /// The machine-readable date and/or time
+        datetime: value "datetime",
+    }
+
+    /// A `<track>` element
+    TrackElement(Void) {
+        /// The URL of the text track
+        src: url "src",
+        /// How the text track is meant to be used (eg. `subtitles`)
+        kind: value "kind",
+        /// The language of the text track
+        srclang: value "srclang",
+        /// The title of the text track
+        label: value "label",
+        /// Enable the track by default
+        default: flag "default",
+    }
+
+    /// A `<video>` element
+    VideoElement(Node) {
+        /// The URL of the video
+        src: url "src",
+        /// An image shown until the video is played
+        poster: url "poster",
+        /// The display width of the video
+        width: value "width",
+        /// The display height of the video
+        height: value "height",
+        /// Show playback controls
+        controls: flag "controls",
+        /// Start playing as soon as possible
+        autoplay: flag "autoplay",
+        /// Restart from the beginning when finished
+        loop_: flag "loop",
+        /// Mute the audio by default
+        muted: flag "muted",
+        /// Play inline rather than fullscreen on mobile
+        playsinline: flag "playsinline",
+        /// How much of the video to load ahead of time
+        preload: value "preload",
+    }
+}
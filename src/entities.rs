@@ -0,0 +1,176 @@
+//! Encoding non-ASCII characters as character references
+
+use crate::{escape, Attribute, Buffer};
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+
+/// How characters are encoded in text and attribute values.
+///
+/// The special characters `&`, `<`, and `>` (and `"` in attribute values)
+/// are always escaped.  The other modes also control how non-ASCII
+/// characters are written, for systems which can't handle UTF-8.  See
+/// [`Buffer::encoding()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Encoding {
+    /// Non-ASCII characters are written as UTF-8
+    #[default]
+    Minimal,
+    /// Non-ASCII characters which have a named entity, such as `&eacute;`,
+    /// are written with it; others are written as UTF-8
+    Named,
+    /// Non-ASCII characters are written as numeric references, such as
+    /// `&#233;`
+    Numeric,
+    /// Non-ASCII characters are written with a named entity if they have
+    /// one, and as a numeric reference otherwise
+    Ascii,
+}
+
+impl Buffer {
+    /// Sets how characters are encoded in text and attribute values.
+    ///
+    /// This applies to text written in the normal and
+    /// [safe][crate::Node::safe] escaping modes, and to attributes set via
+    /// [`GlobalAttributes`][crate::GlobalAttributes].  Raw text, and
+    /// attributes added with [`attr()`][crate::Node::attr], are written
+    /// verbatim.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new().encoding(Encoding::Ascii);
+    /// buf.p().title_attr("Café").text("Crème brûlée — €5 ✓");
+    /// assert_eq!(buf.finish(), "\
+    /// <p title=\"Caf&eacute;\">
+    /// Cr&egrave;me br&ucirc;l&eacute;e &mdash; &euro;5 &#10003;
+    /// </p>
+    /// ");
+    /// ```
+    pub fn encoding(mut self, encoding: Encoding) -> Buffer {
+        self.node.ctx.get().policies.encoding = encoding;
+        self
+    }
+}
+
+impl Encoding {
+    /// Converts an attribute value into a raw attribute, encoding its
+    /// non-ASCII characters
+    pub(crate) fn encode_attr(self, attr: &mut Attribute) {
+        if let Attribute::Value(name, value) = attr {
+            let mut escaped = String::with_capacity(value.len());
+            escape::escape_attr(&mut escaped, value);
+            *attr = Attribute::Raw(format!("{}=\"{}\"", name, self.encode(&escaped)));
+        }
+    }
+
+    /// Encodes the non-ASCII characters of already-escaped text
+    pub(crate) fn encode(self, text: &str) -> Cow<'_, str> {
+        if self == Encoding::Minimal || text.is_ascii() {
+            return Cow::Borrowed(text);
+        }
+        let mut out = String::with_capacity(text.len() + 16);
+        for c in text.chars() {
+            match self.reference(c) {
+                Some(reference) => write!(out, "{}", reference).unwrap(),
+                None => out.push(c),
+            }
+        }
+        Cow::Owned(out)
+    }
+
+    /// The character reference which `c` is written as, if it isn't
+    /// written as it is
+    pub(crate) fn reference(self, c: char) -> Option<CharRef> {
+        if c.is_ascii() || self == Encoding::Minimal {
+            return None;
+        }
+        let named = match self {
+            Encoding::Named | Encoding::Ascii => entity_name(c),
+            _ => None,
+        };
+        match named {
+            Some(name) => Some(CharRef::Named(name)),
+            None if self == Encoding::Named => None,
+            None => Some(CharRef::Numeric(c as u32)),
+        }
+    }
+}
+
+/// A character reference, such as `&eacute;` or `&#233;`
+pub(crate) enum CharRef {
+    Named(&'static str),
+    Numeric(u32),
+}
+
+impl fmt::Display for CharRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CharRef::Named(name) => write!(f, "&{};", name),
+            CharRef::Numeric(code) => write!(f, "&#{};", code),
+        }
+    }
+}
+
+/// The names of the characters from U+00A0 to U+00FF
+const LATIN_1: [&str; 96] = [
+    "nbsp", "iexcl", "cent", "pound", "curren", "yen", "brvbar", "sect", "uml", "copy", "ordf",
+    "laquo", "not", "shy", "reg", "macr", "deg", "plusmn", "sup2", "sup3", "acute", "micro",
+    "para", "middot", "cedil", "sup1", "ordm", "raquo", "frac14", "frac12", "frac34", "iquest",
+    "Agrave", "Aacute", "Acirc", "Atilde", "Auml", "Aring", "AElig", "Ccedil", "Egrave", "Eacute",
+    "Ecirc", "Euml", "Igrave", "Iacute", "Icirc", "Iuml", "ETH", "Ntilde", "Ograve", "Oacute",
+    "Ocirc", "Otilde", "Ouml", "times", "Oslash", "Ugrave", "Uacute", "Ucirc", "Uuml", "Yacute",
+    "THORN", "szlig", "agrave", "aacute", "acirc", "atilde", "auml", "aring", "aelig", "ccedil",
+    "egrave", "eacute", "ecirc", "euml", "igrave", "iacute", "icirc", "iuml", "eth", "ntilde",
+    "ograve", "oacute", "ocirc", "otilde", "ouml", "divide", "oslash", "ugrave", "uacute", "ucirc",
+    "uuml", "yacute", "thorn", "yuml",
+];
+
+/// Returns the name of the character's entity, if it's a commonly-supported
+/// one
+fn entity_name(c: char) -> Option<&'static str> {
+    if ('\u{a0}'..='\u{ff}').contains(&c) {
+        return Some(LATIN_1[c as usize - 0xa0]);
+    }
+    Some(match c {
+        '\u{152}' => "OElig",
+        '\u{153}' => "oelig",
+        '\u{160}' => "Scaron",
+        '\u{161}' => "scaron",
+        '\u{178}' => "Yuml",
+        '\u{192}' => "fnof",
+        '\u{2c6}' => "circ",
+        '\u{2dc}' => "tilde",
+        '\u{2002}' => "ensp",
+        '\u{2003}' => "emsp",
+        '\u{2009}' => "thinsp",
+        '\u{200c}' => "zwnj",
+        '\u{200d}' => "zwj",
+        '\u{200e}' => "lrm",
+        '\u{200f}' => "rlm",
+        '\u{2013}' => "ndash",
+        '\u{2014}' => "mdash",
+        '\u{2018}' => "lsquo",
+        '\u{2019}' => "rsquo",
+        '\u{201a}' => "sbquo",
+        '\u{201c}' => "ldquo",
+        '\u{201d}' => "rdquo",
+        '\u{201e}' => "bdquo",
+        '\u{2020}' => "dagger",
+        '\u{2021}' => "Dagger",
+        '\u{2022}' => "bull",
+        '\u{2026}' => "hellip",
+        '\u{2030}' => "permil",
+        '\u{2032}' => "prime",
+        '\u{2033}' => "Prime",
+        '\u{2039}' => "lsaquo",
+        '\u{203a}' => "rsaquo",
+        '\u{20ac}' => "euro",
+        '\u{2122}' => "trade",
+        '\u{2190}' => "larr",
+        '\u{2191}' => "uarr",
+        '\u{2192}' => "rarr",
+        '\u{2193}' => "darr",
+        '\u{2194}' => "harr",
+        _ => return None,
+    })
+}
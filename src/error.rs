@@ -0,0 +1,38 @@
+use crate::ValidationError;
+use std::fmt;
+
+/// An error returned by the fallible (`try_`) methods, and by
+/// serialization.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The document is invalid; see [strict mode][crate::Buffer::strict]
+    Invalid(Vec<ValidationError>),
+    /// A value couldn't be serialized; see `Node::serialize()`, which is
+    /// available with the `serde` feature
+    Serialize(String),
+    /// An element would have been nested deeper than the limit; see
+    /// [`Buffer::max_depth()`][crate::Buffer::max_depth]
+    TooDeep(usize),
+    /// The buffer doesn't write text, so there's nothing to return; see
+    /// [`Buffer::with_sink()`][crate::Buffer::with_sink]
+    NotText,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Invalid(errors) => {
+                f.write_str("invalid document")?;
+                for (i, e) in errors.iter().enumerate() {
+                    write!(f, "{}{}", if i == 0 { ": " } else { "; " }, e)?;
+                }
+                Ok(())
+            }
+            Error::Serialize(msg) => write!(f, "couldn't serialize value: {}", msg),
+            Error::TooDeep(limit) => write!(f, "elements nested more than {} deep", limit),
+            Error::NotText => f.write_str("the buffer isn't writing to a String"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,108 @@
+//! Escaping text for HTML.
+//!
+//! Most text doesn't contain any special characters, so these functions
+//! search for them with `memchr` (which is vectorised) and copy the clean
+//! runs in between straight into the output.
+
+use crate::sink::{AnySink, RawWriter};
+use crate::Encoding;
+use memchr::{memchr, memchr3, memchr3_iter};
+use std::fmt::{self, Write};
+
+/// Escapes `&`, `<`, and `>`
+pub(crate) fn escape_text(out: &mut String, text: &str) {
+    let mut clean = 0;
+    for i in memchr3_iter(b'&', b'<', b'>', text.as_bytes()) {
+        out.push_str(&text[clean..i]);
+        out.push_str(match text.as_bytes()[i] {
+            b'&' => "&amp;",
+            b'<' => "&lt;",
+            _ => "&gt;",
+        });
+        clean = i + 1;
+    }
+    out.push_str(&text[clean..]);
+}
+
+/// Escapes `&`, `<`, `>`, and `"`
+pub(crate) fn escape_attr(out: &mut String, value: &str) {
+    if memchr(b'"', value.as_bytes()).is_none() {
+        escape_text(out, value);
+    } else {
+        html_escape::encode_double_quoted_attribute_to_string(value, out);
+    }
+}
+
+/// Escapes text as it's written, and passes it on to a sink as markup.
+///
+/// Formatted text arrives in pieces, and each piece is escaped as it comes,
+/// so the text is never collected into a `String` first.  The clean runs
+/// between special characters are passed on as they are.
+pub(crate) struct Escaper<'a> {
+    pub(crate) wtr: &'a mut dyn AnySink,
+    /// Whether quotes and `/` are escaped too, as in [safe
+    /// mode][crate::Node::safe]
+    pub(crate) safe: bool,
+    pub(crate) encoding: Encoding,
+}
+
+impl<'a> Escaper<'a> {
+    /// Whether the byte `b` might need to be escaped, other than `&`, `<`,
+    /// and `>`
+    fn is_special(&self, b: u8) -> bool {
+        if b.is_ascii() {
+            self.safe && matches!(b, b'"' | b'\'' | b'/')
+        } else {
+            self.encoding != Encoding::Minimal
+        }
+    }
+}
+
+impl<'a> Write for Escaper<'a> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let bytes = s.as_bytes();
+        let mut clean = 0;
+        let mut pos = 0;
+        // The next `&`, `<`, or `>`, found with `memchr`; the bytes before
+        // it only need checking for other special characters
+        let find_markup =
+            |pos: usize| memchr3(b'&', b'<', b'>', &bytes[pos..]).map_or(s.len(), |i| pos + i);
+        let mut markup = find_markup(0);
+        loop {
+            if markup < pos {
+                markup = find_markup(pos);
+            }
+            let i = match bytes[pos..markup].iter().position(|&b| self.is_special(b)) {
+                Some(i) => pos + i,
+                None => markup,
+            };
+            let c = match s[i..].chars().next() {
+                Some(c) => c,
+                None => break,
+            };
+            pos = i + c.len_utf8();
+            let escaped = match c {
+                '&' => "&amp;",
+                '<' => "&lt;",
+                '>' => "&gt;",
+                '"' => "&quot;",
+                '\'' => "&#x27;",
+                '/' => "&#x2F;",
+                _ => match self.encoding.reference(c) {
+                    Some(reference) => {
+                        self.wtr.raw(&s[clean..i]);
+                        write!(RawWriter(&mut *self.wtr), "{}", reference)?;
+                        clean = pos;
+                        continue;
+                    }
+                    None => continue,
+                },
+            };
+            self.wtr.raw(&s[clean..i]);
+            self.wtr.raw(escaped);
+            clean = pos;
+        }
+        self.wtr.raw(&s[clean..]);
+        Ok(())
+    }
+}
@@ -0,0 +1,193 @@
+use crate::{ButtonType, FormMethod, GlobalAttributes, Html5, InputType, Node};
+use std::fmt::Display;
+
+/// Types which can be edited with an HTML form.
+///
+/// With the `macros` feature this can be derived for structs with named
+/// fields.  Each field gets a `<label>` and a control, pre-filled with the
+/// field's current value; the control's type depends on the field's type
+/// (see [`FormField`]).  The following field attributes are supported:
+///
+/// * `#[html(rename = "...")]` changes the label
+/// * `#[html(skip)]` leaves the field out
+/// * `#[html(type = "...")]` sets the `type` of the `<input>`, eg. `email`
+///   or `password`
+/// * `#[html(textarea)]` uses a `<textarea>` instead of an `<input>`
+/// * `#[html(select = ["a", "b"])]` uses a `<select>` with the given
+///   options
+///
+/// The field's name is used for the control's `name` and `id`.
+pub trait HtmlForm {
+    /// Writes a label and control for each field
+    fn write_fields(&self, form: &mut Node);
+
+    /// Writes a `<form>` which posts to `action`, with a submit button
+    fn to_html_form(&self, parent: &mut Node, action: &str) {
+        let mut form = parent.form().method(FormMethod::Post).action(action);
+        self.write_fields(&mut form);
+        form.button().type_(ButtonType::Submit).text("Submit");
+    }
+}
+
+/// Types which can be edited with an `<input>`.
+///
+/// * `bool` uses a checkbox.
+/// * Numbers use a number input.
+/// * Strings and `char` use a text input.
+/// * `Option<T>` uses the same input as `T`, which is left empty for
+///   `None`.
+pub trait FormField {
+    /// Writes an `<input>` with the given name and id, and value (if any)
+    fn write_input(value: Option<&Self>, parent: &mut Node, name: &str);
+}
+
+impl FormField for bool {
+    fn write_input(value: Option<&bool>, parent: &mut Node, name: &str) {
+        parent
+            .input()
+            .type_(InputType::Checkbox)
+            .name(name)
+            .id(name)
+            .checked(value == Some(&true));
+    }
+}
+
+macro_rules! form_field {
+    ($type:literal, $step:expr; $($ty:ty),*) => {$(
+        impl FormField for $ty {
+            fn write_input(value: Option<&$ty>, parent: &mut Node, name: &str) {
+                let mut input = parent.input().type_($type).name(name).id(name);
+                if let Some(step) = $step {
+                    input = input.step(step);
+                }
+                if let Some(value) = value {
+                    input.value(value);
+                }
+            }
+        }
+    )*};
+}
+
+form_field!("number", None::<&str>; i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+form_field!("number", Some("any"); f32, f64);
+form_field!("text", None::<&str>; String, str, char);
+
+impl<T: FormField + ?Sized> FormField for &T {
+    fn write_input(value: Option<&&T>, parent: &mut Node, name: &str) {
+        T::write_input(value.copied(), parent, name)
+    }
+}
+
+impl<T: FormField> FormField for Option<T> {
+    fn write_input(value: Option<&Option<T>>, parent: &mut Node, name: &str) {
+        T::write_input(value.and_then(Option::as_ref), parent, name)
+    }
+}
+
+impl<'a> Node<'a> {
+    /// Writes a `<select>` with an option for each `(value, label)` pair
+    ///
+    /// The `name` is used for the control's `name` and `id`.  The option
+    /// whose value is `selected`, if any, is marked as selected.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let sizes = [("s", "Small"), ("m", "Medium"), ("l", "Large & tall")];
+    /// let mut buf = Buffer::new();
+    /// buf.select_from("size", sizes.iter().copied(), Some("m"));
+    /// assert_eq!(buf.finish(), "\
+    /// <select name=\"size\" id=\"size\">
+    ///  <option value=\"s\">
+    /// Small
+    ///  </option>
+    ///  <option value=\"m\" selected>
+    /// Medium
+    ///  </option>
+    ///  <option value=\"l\">
+    /// Large &amp; tall
+    ///  </option>
+    /// </select>
+    /// ");
+    /// ```
+    pub fn select_from<V, L>(
+        &mut self,
+        name: &str,
+        options: impl IntoIterator<Item = (V, L)>,
+        selected: Option<&str>,
+    ) -> &mut Self
+    where
+        V: Display,
+        L: Display,
+    {
+        let mut select = self.select().name(name).id(name);
+        write_options(&mut select, options, selected);
+        self
+    }
+
+    /// Writes a `<select>` with its options in groups
+    ///
+    /// Each group is a label and a list of `(value, label)` pairs, which
+    /// are written in an `<optgroup>`.  Otherwise this is the same as
+    /// [`select_from()`][Node::select_from].
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let groups = vec![
+    ///     ("Fruit", vec![("apple", "Apple")]),
+    ///     ("Vegetables", vec![("leek", "Leek")]),
+    /// ];
+    /// let mut buf = Buffer::new();
+    /// buf.select_from_groups("food", groups, Some("leek"));
+    /// assert_eq!(buf.finish(), "\
+    /// <select name=\"food\" id=\"food\">
+    ///  <optgroup label=\"Fruit\">
+    ///   <option value=\"apple\">
+    /// Apple
+    ///   </option>
+    ///  </optgroup>
+    ///  <optgroup label=\"Vegetables\">
+    ///   <option value=\"leek\" selected>
+    /// Leek
+    ///   </option>
+    ///  </optgroup>
+    /// </select>
+    /// ");
+    /// ```
+    pub fn select_from_groups<G, O, V, L>(
+        &mut self,
+        name: &str,
+        groups: impl IntoIterator<Item = (G, O)>,
+        selected: Option<&str>,
+    ) -> &mut Self
+    where
+        G: Display,
+        O: IntoIterator<Item = (V, L)>,
+        V: Display,
+        L: Display,
+    {
+        let mut select = self.select().name(name).id(name);
+        for (label, options) in groups {
+            let mut group = select.optgroup().attr_value("label", label);
+            write_options(&mut group, options, selected);
+        }
+        self
+    }
+}
+
+fn write_options<V: Display, L: Display>(
+    parent: &mut Node,
+    options: impl IntoIterator<Item = (V, L)>,
+    selected: Option<&str>,
+) {
+    for (value, label) in options {
+        let value = value.to_string();
+        let is_selected = selected == Some(value.as_str());
+        parent
+            .option()
+            .value(value)
+            .selected(is_selected)
+            .text(label);
+    }
+}
@@ -0,0 +1,461 @@
+//! Options for how the output is laid out
+
+use crate::tree::VOID_ELEMENTS;
+use crate::{Buffer, Ctx, Frame};
+use std::borrow::Cow;
+
+/// Elements whose text is written exactly as given, even with
+/// [`indent_text()`][Format::indent_text]
+const PREFORMATTED: &[&str] = &["pre", "textarea"];
+
+/// Elements whose text is written exactly as given, even when
+/// [minified][Format::minify]
+const VERBATIM: &[&str] = &["pre", "textarea", "script", "style"];
+
+/// Elements whose contents aren't parsed as markup
+const RAW_TEXT: &[&str] = &["script", "style", "textarea", "title"];
+
+/// Options for how a buffer lays out its output.
+///
+/// The default is to write each tag on its own line, indented by its depth,
+/// with a newline at the end of the document.  Use
+/// [`Buffer::format()`] to change this.  Custom [sinks][crate::Sink] are
+/// given the options too, but they're free to ignore them.
+///
+/// ```
+/// use html_builder::*;
+///
+/// let mut buf = Buffer::new().format(Format::new().trailing_newline(false));
+/// buf.p().text("Hello");
+/// assert_eq!(buf.finish(), "<p>\nHello\n</p>");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Format {
+    pub(crate) trailing_newline: bool,
+    pub(crate) blank_line_at_end: bool,
+    pub(crate) indent_text: bool,
+    pub(crate) indent_raw: bool,
+    pub(crate) wrap_attributes: Option<usize>,
+    pub(crate) minify: bool,
+}
+
+impl Default for Format {
+    fn default() -> Format {
+        Format {
+            trailing_newline: true,
+            blank_line_at_end: false,
+            indent_text: false,
+            indent_raw: false,
+            wrap_attributes: None,
+            minify: false,
+        }
+    }
+}
+
+impl Format {
+    /// The default options
+    pub fn new() -> Format {
+        Format::default()
+    }
+
+    /// Whether the document ends with a newline (the default), or with the
+    /// last end tag
+    pub fn trailing_newline(mut self, yes: bool) -> Format {
+        self.trailing_newline = yes;
+        self
+    }
+
+    /// Whether the document ends with a blank line, after the end tags
+    /// written by [`finish()`][Buffer::finish]
+    ///
+    /// This is handy when documents are concatenated, to keep them
+    /// visually separate.  It implies a trailing newline.
+    pub fn blank_line_at_end(mut self, yes: bool) -> Format {
+        self.blank_line_at_end = yes;
+        self
+    }
+
+    /// Whether text is indented to the depth of the element it's in
+    ///
+    /// By default text is written exactly as given, so the lines of a
+    /// multi-line string start at the beginning of the line.  With this
+    /// option, each line of text is indented like the element's children
+    /// would be; blank lines are left empty.  Text inside `<pre>` and
+    /// `<textarea>`, where whitespace matters, and text written in
+    /// [raw mode][crate::Node::raw] are left alone.
+    ///
+    /// ```
+    /// use html_builder::*;
+    /// use std::fmt::Write;
+    ///
+    /// let mut buf = Buffer::new().format(Format::new().indent_text(true));
+    /// let mut div = buf.div();
+    /// writeln!(div.p(), "Roses are red,\nViolets are blue")?;
+    /// writeln!(div.pre(), "fn main() {{\n    println!();\n}}")?;
+    /// assert_eq!(buf.finish(), "\
+    /// <div>
+    ///  <p>
+    ///   Roses are red,
+    ///   Violets are blue
+    ///  </p>
+    ///  <pre>
+    /// fn main() {
+    ///     println!();
+    /// }
+    ///  </pre>
+    /// </div>
+    /// ");
+    /// # Ok::<(), std::fmt::Error>(())
+    /// ```
+    pub fn indent_text(mut self, yes: bool) -> Format {
+        self.indent_text = yes;
+        self
+    }
+
+    /// Whether markup written in [raw mode][crate::Node::raw] is
+    /// re-indented to the depth of the element it's in
+    ///
+    /// By default raw markup is written exactly as given, so pasting in a
+    /// pre-rendered partial can leave it at the wrong indentation.  With
+    /// this option, the indentation which the partial's lines have in
+    /// common is removed, and they're indented like the element's children
+    /// would be; blank lines are left empty.  Each piece of markup written
+    /// is re-indented separately.
+    ///
+    /// Markup which contains a `<pre>` or `<textarea>`, or which is written
+    /// inside one, is left alone, since changing its whitespace would change
+    /// what's displayed.
+    ///
+    /// ```
+    /// use html_builder::*;
+    /// use std::fmt::Write;
+    ///
+    /// let partial = "    <nav>\n      <a href=\"/\">Home</a>\n    </nav>\n";
+    /// let mut buf = Buffer::new().format(Format::new().indent_raw(true));
+    /// let mut body = buf.body();
+    /// let mut header = body.header();
+    /// header.raw().write_str(partial)?;
+    /// assert_eq!(buf.finish(), "\
+    /// <body>
+    ///  <header>
+    ///   <nav>
+    ///     <a href=\"/\">Home</a>
+    ///   </nav>
+    ///  </header>
+    /// </body>
+    /// ");
+    /// # Ok::<(), std::fmt::Error>(())
+    /// ```
+    pub fn indent_raw(mut self, yes: bool) -> Format {
+        self.indent_raw = yes;
+        self
+    }
+
+    /// Puts each attribute of a start tag on its own line, if the tag would
+    /// otherwise be more than `width` characters wide (counting the
+    /// indentation)
+    ///
+    /// The attributes are indented two levels deeper than the tag, so that
+    /// they stand out from its children.  Tags with a single attribute are
+    /// never wrapped.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new().format(Format::new().wrap_attributes(40));
+    /// let mut body = buf.body();
+    /// body.div()
+    ///     .id("app")
+    ///     .data_attr("controller", "gallery")
+    ///     .data_attr("gallery-index-value", "0")
+    ///     .text("Loading...");
+    /// body.div().id("footer");
+    /// assert_eq!(buf.finish(), "\
+    /// <body>
+    ///  <div
+    ///    id=\"app\"
+    ///    data-controller=\"gallery\"
+    ///    data-gallery-index-value=\"0\">
+    /// Loading...
+    ///  </div>
+    ///  <div id=\"footer\">
+    ///  </div>
+    /// </body>
+    /// ");
+    /// ```
+    pub fn wrap_attributes(mut self, width: usize) -> Format {
+        self.wrap_attributes = Some(width);
+        self
+    }
+
+    /// Writes the output as compactly as possible
+    ///
+    /// Tags aren't indented or put on their own lines, runs of whitespace
+    /// in text are collapsed to a single space, comments are left out
+    /// (except for [conditional comments][crate::Node::conditional_comment]),
+    /// and attribute values which don't need quotes are written without
+    /// them.  Text inside `<pre>`, `<textarea>`, `<script>`, and `<style>`,
+    /// and text written in [raw mode][crate::Node::raw], are left alone.
+    /// The other options are ignored, except for
+    /// [`blank_line_at_end()`][Format::blank_line_at_end].
+    ///
+    /// This makes it easy to have readable output during development and
+    /// small output in production:
+    ///
+    /// ```
+    /// use html_builder::*;
+    /// use std::fmt::Write;
+    ///
+    /// fn page(format: Format) -> String {
+    ///     let mut buf = Buffer::new().format(format);
+    ///     let mut ul = buf.ul().class("menu");
+    ///     write!(ul.comment(), "Navigation").unwrap();
+    ///     ul.li().a().href("/about").title_attr("About us").text("About");
+    ///     ul.li().text("Contact:\n    alice@example.com");
+    ///     buf.finish()
+    /// }
+    ///
+    /// let dev = page(Format::new());
+    /// assert_eq!(dev, "\
+    /// <ul class=\"menu\">
+    ///  <!-- Navigation -->
+    ///  <li>
+    ///   <a href=\"/about\" title=\"About us\">
+    /// About
+    ///   </a>
+    ///  </li>
+    ///  <li>
+    /// Contact:
+    ///     alice@example.com
+    ///  </li>
+    /// </ul>
+    /// ");
+    ///
+    /// let prod = page(Format::new().minify(true));
+    /// assert_eq!(
+    ///     prod,
+    ///     "<ul class=menu><li><a href=/about title=\"About us\">About </a></li><li>Contact: alice@example.com </li></ul>",
+    /// );
+    /// ```
+    pub fn minify(mut self, yes: bool) -> Format {
+        self.minify = yes;
+        self
+    }
+}
+
+/// Indents the lines of `text` by `depth` spaces, except for the first
+/// one if it doesn't start at the beginning of a line
+pub(crate) fn indent(text: &str, depth: usize, line_start: bool) -> Cow<'_, str> {
+    if depth == 0 || (!line_start && !text.contains('\n')) {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len() + depth);
+    let mut at_start = line_start;
+    for line in text.split_inclusive('\n') {
+        if at_start && line != "\n" {
+            crate::sink::spaces(&mut out, depth);
+        }
+        out.push_str(line);
+        at_start = true;
+    }
+    Cow::Owned(out)
+}
+
+/// Removes the indentation which the lines of `text` have in common, and
+/// then indents them by `depth` spaces, as [`indent()`] does.  The first
+/// line doesn't count if it doesn't start at the beginning of a line.
+pub(crate) fn reindent(text: &str, depth: usize, line_start: bool) -> Cow<'_, str> {
+    let lines = text
+        .split_inclusive('\n')
+        .skip(if line_start { 0 } else { 1 });
+    let common = lines
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start_matches([' ', '\t']).len())
+        .min()
+        .unwrap_or(0);
+    if common == 0 {
+        return indent(text, depth, line_start);
+    }
+    let mut out = String::with_capacity(text.len());
+    for (i, line) in text.split_inclusive('\n').enumerate() {
+        if i == 0 && !line_start {
+            out.push_str(line);
+        } else if line.trim().is_empty() {
+            out.push_str(line.trim_start_matches([' ', '\t']));
+        } else {
+            out.push_str(&line[common..]);
+        }
+    }
+    Cow::Owned(indent(&out, depth, line_start).into_owned())
+}
+
+/// Indents the tags in some output by `depth` more spaces.  Text, which is
+/// written at the start of the line, is left alone, and so is everything
+/// between the tags of a `<pre>` or similar.
+pub(crate) fn shift(html: &str, depth: usize) -> Cow<'_, str> {
+    if depth == 0 {
+        return Cow::Borrowed(html);
+    }
+    let mut out = String::with_capacity(html.len() + html.len() / 8);
+    let mut preformatted: Option<&str> = None;
+    for line in html.split_inclusive('\n') {
+        let trimmed = line.trim_start_matches(' ');
+        let is_tag = match preformatted {
+            Some(tag) => {
+                let is_end = trimmed
+                    .strip_prefix("</")
+                    .and_then(|x| x.strip_prefix(tag))
+                    .is_some_and(|x| x.starts_with('>'));
+                if is_end {
+                    preformatted = None;
+                }
+                is_end
+            }
+            None => {
+                preformatted = PREFORMATTED.iter().copied().find(|tag| {
+                    trimmed
+                        .strip_prefix('<')
+                        .and_then(|x| x.strip_prefix(tag))
+                        .is_some_and(|x| x.starts_with(['>', ' ', '\n']))
+                });
+                trimmed.starts_with('<')
+            }
+        };
+        if is_tag {
+            crate::sink::spaces(&mut out, depth);
+        }
+        out.push_str(line);
+    }
+    Cow::Owned(out)
+}
+
+/// Whether some markup contains an element whose whitespace matters
+pub(crate) fn has_preformatted(html: &str) -> bool {
+    let html = html.to_ascii_lowercase();
+    PREFORMATTED
+        .iter()
+        .any(|tag| html.contains(&format!("<{}", tag)))
+}
+
+/// The elements which are left open at the end of some markup, outermost
+/// first.
+///
+/// This is lenient: an end tag closes the innermost element with the same
+/// name, and one which doesn't match anything is ignored.
+pub(crate) fn open_elements(html: &str) -> Vec<String> {
+    let mut stack: Vec<String> = vec![];
+    let mut pos = 0;
+    while let Some(offset) = html[pos..].find('<') {
+        let rest = &html[pos + offset..];
+        pos += offset + 1;
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            match comment.find("-->") {
+                Some(end) => pos += 3 + end + 3,
+                None => break,
+            }
+        } else if let Some(tag) = rest.strip_prefix("</") {
+            let name = tag_name(tag);
+            if let Some(i) = stack.iter().rposition(|open| *open == name) {
+                stack.truncate(i);
+            }
+        } else if rest[1..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+            let name = tag_name(&rest[1..]);
+            let Some(end) = tag_end(&rest[1..]) else {
+                break;
+            };
+            pos += end + 1;
+            if rest[..end + 1].ends_with('/') || VOID_ELEMENTS.contains(&name.as_str()) {
+                continue;
+            }
+            if RAW_TEXT.contains(&name.as_str()) {
+                // Skip to the end tag
+                let close = format!("</{}", name);
+                match html[pos..].to_ascii_lowercase().find(&close) {
+                    Some(end) => pos += end,
+                    None => break,
+                }
+            }
+            stack.push(name);
+        }
+    }
+    stack
+}
+
+/// The name at the start of a tag, in lower case
+fn tag_name(tag: &str) -> String {
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == ':';
+    let len = tag.find(|c| !is_name_char(c)).unwrap_or(tag.len());
+    tag[..len].to_ascii_lowercase()
+}
+
+/// The position of the `>` which ends a tag, skipping over quoted
+/// attribute values
+fn tag_end(tag: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in tag.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return Some(i),
+            (Some(q), c) if q == c => quote = None,
+            _ => (),
+        }
+    }
+    None
+}
+
+/// Replaces each run of whitespace in `text` with a single space.  A
+/// leading space is left out if `after_space` is true.
+pub(crate) fn collapse(text: &str, after_space: bool) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut space = after_space;
+    for c in text.chars() {
+        if c.is_ascii_whitespace() {
+            if !space {
+                out.push(' ');
+            }
+            space = true;
+        } else {
+            out.push(c);
+            space = false;
+        }
+    }
+    out
+}
+
+impl Ctx {
+    /// Whether text is being written inside a `<pre>` or similar
+    pub(crate) fn preformatted(&self) -> bool {
+        self.inside(PREFORMATTED)
+    }
+
+    /// Whether text is being written inside a `<pre>`, `<script>`, or
+    /// similar, where it shouldn't be minified
+    pub(crate) fn verbatim(&self) -> bool {
+        self.inside(VERBATIM)
+    }
+
+    fn inside(&self, tags: &[&str]) -> bool {
+        self.stack.iter().any(|frame| match frame {
+            Frame::Element(tag) => tags.contains(&&**tag),
+            Frame::Conditional => false,
+        })
+    }
+}
+
+impl Buffer {
+    /// Sets the options for how the output is laid out; see [`Format`].
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new().format(Format::new().blank_line_at_end(true));
+    /// buf.html().body();
+    /// assert_eq!(buf.finish(), "<html>\n <body>\n </body>\n</html>\n\n");
+    /// ```
+    pub fn format(mut self, format: Format) -> Buffer {
+        let ctx = self.node.ctx.get();
+        ctx.wtr.set_format(&format);
+        ctx.policies.format = format;
+        self
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{Ctx, GlobalAttributes, Html5, Node, SlotId};
+use std::fmt::{Display, Write};
+
+/// Collects the contents of the document's `<head>` from anywhere in the
+/// document.
+///
+/// Components deep inside the `<body>` often need something in the
+/// `<head>`: a stylesheet, a `<meta>` tag, or the page title.  By the time
+/// they run, the `<head>` has already been written.  Instead, mark a
+/// position in the `<head>` with [`head_slot()`][Node::head_slot], and push
+/// the requirements onto the manager; they're written into the slot when
+/// the buffer is finished.
+///
+/// Duplicates are dropped: there's only one title, only one `<meta>` for
+/// each `name` or `property` (the last value wins), and only one `<link>`
+/// for each `rel` and `href`.
+///
+/// ```
+/// use html_builder::*;
+/// use std::fmt::Write;
+///
+/// fn widget(parent: &mut Node) {
+///     parent.head_manager().link("stylesheet", "/widget.css");
+///     writeln!(parent.div().class("widget"), "Hi!").unwrap();
+/// }
+///
+/// let mut buf = Buffer::new();
+/// let mut html = buf.html();
+/// html.head().head_slot();
+/// let mut body = html.body();
+/// body.head_manager().title("Widgets");
+/// widget(&mut body);
+/// widget(&mut body);
+/// assert_eq!(buf.finish(), "\
+/// <html>
+///  <head>
+///   <title>
+/// Widgets
+///   </title>
+///   <link rel=\"stylesheet\" href=\"/widget.css\">
+///  </head>
+///  <body>
+///   <div class=\"widget\">
+/// Hi!
+///   </div>
+///   <div class=\"widget\">
+/// Hi!
+///   </div>
+///  </body>
+/// </html>
+/// ");
+/// ```
+pub struct HeadManager<'a> {
+    ctx: &'a mut Ctx,
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct HeadItems {
+    slot: Option<SlotId>,
+    title: Option<String>,
+    /// `(attribute, key, content)`, where `attribute` is `name` or `property`
+    metas: Vec<(&'static str, String, String)>,
+    /// `(rel, href)`
+    links: Vec<(String, String)>,
+}
+
+impl<'a> HeadManager<'a> {
+    fn with(&mut self, f: impl FnOnce(&mut HeadItems)) -> &mut Self {
+        f(&mut self.ctx.head);
+        self
+    }
+
+    /// Sets the document's title, replacing any previous one
+    pub fn title(&mut self, title: impl Display) -> &mut Self {
+        let title = title.to_string();
+        self.with(|head| head.title = Some(title))
+    }
+
+    /// Adds a `<meta name="..." content="...">` tag
+    pub fn meta(&mut self, name: &str, content: impl Display) -> &mut Self {
+        let content = content.to_string();
+        self.with(|head| head.meta("name", name, content))
+    }
+
+    /// Adds a `<meta property="..." content="...">` tag, as used by Open
+    /// Graph
+    pub fn meta_property(&mut self, property: &str, content: impl Display) -> &mut Self {
+        let content = content.to_string();
+        self.with(|head| head.meta("property", property, content))
+    }
+
+    /// Adds a `<link rel="..." href="...">` tag
+    ///
+    /// Unsafe URLs, such as `javascript:` URLs, are replaced; see
+    /// [`is_safe_url()`][crate::is_safe_url].
+    pub fn link(&mut self, rel: &str, href: &str) -> &mut Self {
+        self.with(|head| head.link(rel.to_string(), href.to_string()))
+    }
+}
+
+impl HeadItems {
+    fn meta(&mut self, attr: &'static str, key: &str, content: String) {
+        let existing = self
+            .metas
+            .iter_mut()
+            .find(|(a, k, _)| *a == attr && k == key);
+        match existing {
+            Some(meta) => meta.2 = content,
+            None => self.metas.push((attr, key.to_string(), content)),
+        }
+    }
+
+    fn link(&mut self, rel: String, href: String) {
+        let link = (rel, href);
+        if !self.links.contains(&link) {
+            self.links.push(link);
+        }
+    }
+
+    /// Adds the items collected somewhere else, eg. while a slot was filled
+    /// or a cached fragment was rendered.  The slot isn't copied.
+    pub(crate) fn merge(&mut self, other: &HeadItems) {
+        if let Some(title) = &other.title {
+            self.title = Some(title.clone());
+        }
+        for (attr, key, content) in &other.metas {
+            self.meta(attr, key, content.clone());
+        }
+        for (rel, href) in &other.links {
+            self.link(rel.clone(), href.clone());
+        }
+    }
+
+    fn write_to(&self, head: &mut Node) {
+        if let Some(title) = &self.title {
+            writeln!(head.title(), "{}", title).unwrap();
+        }
+        for (attr, key, content) in &self.metas {
+            head.meta()
+                .attr_value(attr, key)
+                .attr_value("content", content);
+        }
+        for (rel, href) in &self.links {
+            head.link().rel(rel).href(href);
+        }
+    }
+}
+
+impl Ctx {
+    /// Writes the items collected by the [`HeadManager`] into the head slot
+    pub(crate) fn flush_head(&mut self) {
+        let head = std::mem::take(&mut self.head);
+        self.flush_amp_extensions(head.slot);
+        if let Some(slot) = head.slot {
+            self.fill(slot, |node| head.write_to(node));
+        }
+    }
+}
+
+impl<'a> Node<'a> {
+    /// Get a handle for adding things to the document's `<head>`
+    ///
+    /// See [`HeadManager`].
+    pub fn head_manager(&mut self) -> HeadManager<'_> {
+        HeadManager {
+            ctx: self.ctx.get(),
+        }
+    }
+
+    /// Reserve the position where the [`HeadManager`]'s items are written
+    ///
+    /// This should be called on the `<head>` element.  If it isn't called,
+    /// the items are discarded.
+    pub fn head_slot(&mut self) {
+        let slot = self.placeholder();
+        self.ctx.get().head.slot = Some(slot);
+    }
+}
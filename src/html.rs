@@ -1,895 +1,1232 @@
-use crate::{Node, Void};
+use crate::*;
 use std::borrow::Cow;
+use std::fmt::Display;
+
+/// Provided methods which write an element containing some text
+macro_rules! text_methods {
+    ($($tag:ident => $method:ident,)*) => {$(
+        #[doc = concat!("Defines a `<", stringify!($tag), ">` element containing `text`")]
+        #[cfg_attr(feature = "source-locations", track_caller)]
+        fn $method(&mut self, text: impl Display) -> &mut Self {
+            self.$tag().text(text);
+            self
+        }
+    )*};
+}
 
 /// Helper methods for generating HTML5 documents.
 pub trait Html5 {
     /// Defines the document type
     fn doctype(&mut self);
 
+    /// Defines the document type as XHTML 1.0 Strict
+    fn doctype_xhtml1_strict(&mut self);
+
+    /// Defines the document type as HTML 4.01 Strict
+    fn doctype_html4(&mut self);
+
+    /// Defines the document type using an arbitrary declaration
+    ///
+    /// `decl` is written verbatim after `<!DOCTYPE`.
+    fn doctype_custom(&mut self, decl: &str);
+
     /// Defines a hyperlink
-    fn a(&mut self) -> Node;
+    fn a(&mut self) -> AElement<'_>;
 
     /// Defines an abbreviation or an acronym
-    fn abbr(&mut self) -> Node;
+    fn abbr(&mut self) -> Node<'_>;
 
     /// Defines contact information for the author/owner of a document
-    fn address(&mut self) -> Node;
+    fn address(&mut self) -> Node<'_>;
 
     /// Defines an area inside an image map
-    fn area(&mut self) -> Void;
+    fn area(&mut self) -> AreaElement<'_>;
 
     /// Defines an article
-    fn article(&mut self) -> Node;
+    fn article(&mut self) -> Node<'_>;
 
     /// Defines content aside from the page content
-    fn aside(&mut self) -> Node;
+    fn aside(&mut self) -> Node<'_>;
 
     /// Defines embedded sound content
-    fn audio(&mut self) -> Node;
+    fn audio(&mut self) -> AudioElement<'_>;
 
     /// Defines bold text
-    fn b(&mut self) -> Node;
+    fn b(&mut self) -> Node<'_>;
 
     /// Specifies the base URL/target for all relative URLs in a document
-    fn base(&mut self) -> Void;
+    fn base(&mut self) -> BaseElement<'_>;
 
     /// Isolates a part of text that might be formatted in a different direction from other text outside it
-    fn bdi(&mut self) -> Node;
+    fn bdi(&mut self) -> Node<'_>;
 
     /// Overrides the current text direction
-    fn bdo(&mut self) -> Node;
+    fn bdo(&mut self) -> Node<'_>;
 
     /// Defines a section that is quoted from another source
-    fn blockquote(&mut self) -> Node;
+    fn blockquote(&mut self) -> Node<'_>;
 
     /// Defines the document's body
-    fn body(&mut self) -> Node;
+    fn body(&mut self) -> Node<'_>;
 
     /// Defines a single line break
-    fn br(&mut self) -> Void;
+    fn br(&mut self) -> Void<'_>;
 
     /// Defines a clickable button
-    fn button(&mut self) -> Node;
+    fn button(&mut self) -> ButtonElement<'_>;
 
     /// Used to draw graphics, on the fly, via scripting (usually JavaScript)
-    fn canvas(&mut self) -> Node;
+    fn canvas(&mut self) -> Node<'_>;
 
     /// Defines a table caption
-    fn caption(&mut self) -> Node;
+    fn caption(&mut self) -> Node<'_>;
 
     /// Defines the title of a work
-    fn cite(&mut self) -> Node;
+    fn cite(&mut self) -> Node<'_>;
 
     /// Defines a piece of computer code
-    fn code(&mut self) -> Node;
+    fn code(&mut self) -> Node<'_>;
 
     /// Specifies column properties for each column within a `<colgroup>` element
-    fn col(&mut self) -> Void;
+    fn col(&mut self) -> ColElement<'_>;
 
     /// Specifies a group of one or more columns in a table for formatting
-    fn colgroup(&mut self) -> Node;
+    fn colgroup(&mut self) -> Node<'_>;
 
     /// Adds a machine-readable translation of a given content
-    fn data(&mut self) -> Node;
+    fn data(&mut self) -> Node<'_>;
 
     /// Specifies a list of pre-defined options for input controls
-    fn datalist(&mut self) -> Node;
+    fn datalist(&mut self) -> Node<'_>;
 
     /// Defines a description/value of a term in a description list
-    fn dd(&mut self) -> Node;
+    fn dd(&mut self) -> Node<'_>;
 
     /// Defines text that has been deleted from a document
-    fn del(&mut self) -> Node;
+    fn del(&mut self) -> Node<'_>;
 
     /// Defines additional details that the user can view or hide
-    fn details(&mut self) -> Node;
+    fn details(&mut self) -> Node<'_>;
 
     /// Specifies a term that is going to be defined within the content
-    fn dfn(&mut self) -> Node;
+    fn dfn(&mut self) -> Node<'_>;
 
     /// Defines a dialog box or window
-    fn dialog(&mut self) -> Node;
+    fn dialog(&mut self) -> Node<'_>;
 
     /// Defines a section in a document
-    fn div(&mut self) -> Node;
+    fn div(&mut self) -> Node<'_>;
 
     /// Defines a description list
-    fn dl(&mut self) -> Node;
+    fn dl(&mut self) -> Node<'_>;
 
     /// Defines a term/name in a description list
-    fn dt(&mut self) -> Node;
+    fn dt(&mut self) -> Node<'_>;
 
     /// Defines emphasized text
-    fn em(&mut self) -> Node;
+    fn em(&mut self) -> Node<'_>;
 
     /// Defines a container for an external application
-    fn embed(&mut self) -> Void;
+    fn embed(&mut self) -> EmbedElement<'_>;
 
     /// Groups related elements in a form
-    fn fieldset(&mut self) -> Node;
+    fn fieldset(&mut self) -> Node<'_>;
 
     /// Defines a caption for a `<figure>` element
-    fn figcaption(&mut self) -> Node;
+    fn figcaption(&mut self) -> Node<'_>;
 
     /// Specifies self-contained content
-    fn figure(&mut self) -> Node;
+    fn figure(&mut self) -> Node<'_>;
 
     /// Defines a footer for a document or section
-    fn footer(&mut self) -> Node;
+    fn footer(&mut self) -> Node<'_>;
 
     /// Defines an HTML form for user input
-    fn form(&mut self) -> Node;
+    fn form(&mut self) -> FormElement<'_>;
 
     /// Defines HTML headings
-    fn h1(&mut self) -> Node;
+    fn h1(&mut self) -> Node<'_>;
 
     /// Defines HTML headings
-    fn h2(&mut self) -> Node;
+    fn h2(&mut self) -> Node<'_>;
 
     /// Defines HTML headings
-    fn h3(&mut self) -> Node;
+    fn h3(&mut self) -> Node<'_>;
 
     /// Defines HTML headings
-    fn h4(&mut self) -> Node;
+    fn h4(&mut self) -> Node<'_>;
 
     /// Defines HTML headings
-    fn h5(&mut self) -> Node;
+    fn h5(&mut self) -> Node<'_>;
 
     /// Defines HTML headings
-    fn h6(&mut self) -> Node;
+    fn h6(&mut self) -> Node<'_>;
 
     /// Contains metadata/information for the document
-    fn head(&mut self) -> Node;
+    fn head(&mut self) -> Node<'_>;
 
     /// Defines a header for a document or section
-    fn header(&mut self) -> Node;
+    fn header(&mut self) -> Node<'_>;
+
+    /// Groups a heading with related content, such as subheadings
+    fn hgroup(&mut self) -> Node<'_>;
 
     /// Defines a thematic change in the content
-    fn hr(&mut self) -> Void;
+    fn hr(&mut self) -> Void<'_>;
 
     /// Defines the root of an HTML document
-    fn html(&mut self) -> Node;
+    fn html(&mut self) -> Node<'_>;
 
     /// Defines a part of text in an alternate voice or mood
-    fn i(&mut self) -> Node;
+    fn i(&mut self) -> Node<'_>;
 
     /// Defines an inline frame
-    fn iframe(&mut self) -> Node;
+    fn iframe(&mut self) -> IframeElement<'_>;
 
     /// Defines an image
-    fn img(&mut self) -> Void;
+    fn img(&mut self) -> ImgElement<'_>;
 
     /// Defines an input control
-    fn input(&mut self) -> Void;
+    fn input(&mut self) -> InputElement<'_>;
 
     /// Defines a text that has been inserted into a document
-    fn ins(&mut self) -> Node;
+    fn ins(&mut self) -> Node<'_>;
 
     /// Defines keyboard input
-    fn kbd(&mut self) -> Node;
+    fn kbd(&mut self) -> Node<'_>;
 
     /// Defines a label for an `<input>` element
-    fn label(&mut self) -> Node;
+    fn label(&mut self) -> LabelElement<'_>;
 
     /// Defines a caption for a `<fieldset>` element
-    fn legend(&mut self) -> Node;
+    fn legend(&mut self) -> Node<'_>;
 
     /// Defines a list item
-    fn li(&mut self) -> Node;
+    fn li(&mut self) -> Node<'_>;
 
     /// Defines the relationship between a document and an external resource (most used to link to style sheets)
-    fn link(&mut self) -> Void;
+    fn link(&mut self) -> LinkElement<'_>;
 
     /// Specifies the main content of a document
-    fn main(&mut self) -> Node;
+    fn main(&mut self) -> Node<'_>;
 
     /// Defines an image map
-    fn map(&mut self) -> Node;
+    fn map(&mut self) -> Node<'_>;
 
     /// Defines marked/highlighted text
-    fn mark(&mut self) -> Node;
+    fn mark(&mut self) -> Node<'_>;
+
+    /// Defines a MathML formula
+    fn math(&mut self) -> Node<'_>;
+
+    /// Defines an unordered list of commands
+    fn menu(&mut self) -> Node<'_>;
 
     /// Defines metadata about an HTML document
-    fn meta(&mut self) -> Void;
+    fn meta(&mut self) -> MetaElement<'_>;
 
     /// Defines a scalar measurement within a known range (a gauge)
-    fn meter(&mut self) -> Node;
+    fn meter(&mut self) -> Node<'_>;
 
     /// Defines navigation links
-    fn nav(&mut self) -> Node;
+    fn nav(&mut self) -> Node<'_>;
 
     /// Defines an alternate content for users that do not support client-side scripts
-    fn noscript(&mut self) -> Node;
+    fn noscript(&mut self) -> Node<'_>;
 
     /// Defines a container for an external application
-    fn object(&mut self) -> Node;
+    fn object(&mut self) -> Node<'_>;
 
     /// Defines an ordered list
-    fn ol(&mut self) -> Node;
+    fn ol(&mut self) -> OlElement<'_>;
 
     /// Defines a group of related options in a drop-down list
-    fn optgroup(&mut self) -> Node;
+    fn optgroup(&mut self) -> Node<'_>;
 
     /// Defines an option in a drop-down list
-    fn option(&mut self) -> Node;
+    fn option(&mut self) -> OptionElement<'_>;
 
     /// Defines the result of a calculation
-    fn output(&mut self) -> Node;
+    fn output(&mut self) -> Node<'_>;
 
     /// Defines a paragraph
-    fn p(&mut self) -> Node;
+    fn p(&mut self) -> Node<'_>;
 
     /// Defines a parameter for an object
-    fn param(&mut self) -> Void;
+    #[deprecated(note = "`param` is obsolete and no longer part of the HTML standard")]
+    fn param(&mut self) -> Void<'_>;
 
     /// Defines a container for multiple image resources
-    fn picture(&mut self) -> Node;
+    fn picture(&mut self) -> Node<'_>;
 
     /// Defines preformatted text
-    fn pre(&mut self) -> Node;
+    fn pre(&mut self) -> Node<'_>;
 
     /// Represents the progress of a task
-    fn progress(&mut self) -> Node;
+    fn progress(&mut self) -> Node<'_>;
 
     /// Defines a short quotation
-    fn q(&mut self) -> Node;
+    fn q(&mut self) -> Node<'_>;
 
     /// Defines what to show in browsers that do not support ruby annotations
-    fn rp(&mut self) -> Node;
+    fn rp(&mut self) -> Node<'_>;
 
     /// Defines an explanation/pronunciation of characters (for East Asian typography)
-    fn rt(&mut self) -> Node;
+    fn rt(&mut self) -> Node<'_>;
 
     /// Defines a ruby annotation (for East Asian typography)
-    fn ruby(&mut self) -> Node;
+    fn ruby(&mut self) -> Node<'_>;
 
     /// Defines text that is no longer correct
-    fn s(&mut self) -> Node;
+    fn s(&mut self) -> Node<'_>;
 
     /// Defines sample output from a computer program
-    fn samp(&mut self) -> Node;
+    fn samp(&mut self) -> Node<'_>;
 
     /// Defines a client-side script
-    fn script(&mut self) -> Node;
+    fn script(&mut self) -> ScriptElement<'_>;
+
+    /// Defines a section containing search or filtering controls
+    fn search(&mut self) -> Node<'_>;
 
     /// Defines a section in a document
-    fn section(&mut self) -> Node;
+    fn section(&mut self) -> Node<'_>;
 
     /// Defines a drop-down list
-    fn select(&mut self) -> Node;
+    fn select(&mut self) -> SelectElement<'_>;
+
+    /// Defines a placeholder inside a web component that you can fill with your own markup
+    fn slot(&mut self) -> Node<'_>;
 
     /// Defines smaller text
-    fn small(&mut self) -> Node;
+    fn small(&mut self) -> Node<'_>;
 
     /// Defines multiple media resources for media elements (`<video>` and `<audio>`)
-    fn source(&mut self) -> Void;
+    fn source(&mut self) -> SourceElement<'_>;
 
     /// Defines a section in a document
-    fn span(&mut self) -> Node;
+    fn span(&mut self) -> Node<'_>;
 
     /// Defines important text
-    fn strong(&mut self) -> Node;
+    fn strong(&mut self) -> Node<'_>;
 
     /// Defines style information for a document
-    fn style(&mut self) -> Node;
+    fn style(&mut self) -> StyleElement<'_>;
 
     /// Defines subscripted text
-    fn sub(&mut self) -> Node;
+    fn sub(&mut self) -> Node<'_>;
 
     /// Defines a visible heading for a `<details>` element
-    fn summary(&mut self) -> Node;
+    fn summary(&mut self) -> Node<'_>;
 
     /// Defines superscripted text
-    fn sup(&mut self) -> Node;
+    fn sup(&mut self) -> Node<'_>;
 
     /// Defines a container for SVG graphics
-    fn svg(&mut self) -> Node;
+    fn svg(&mut self) -> Node<'_>;
 
     /// Defines a table
-    fn table(&mut self) -> Node;
+    fn table(&mut self) -> Node<'_>;
 
     /// Groups the body content in a table
-    fn tbody(&mut self) -> Node;
+    fn tbody(&mut self) -> Node<'_>;
 
     /// Defines a cell in a table
-    fn td(&mut self) -> Node;
+    fn td(&mut self) -> TdElement<'_>;
 
     /// Defines a container for content that should be hidden when the page loads
-    fn template(&mut self) -> Node;
+    fn template(&mut self) -> Node<'_>;
 
     /// Defines a multiline input control (text area)
-    fn textarea(&mut self) -> Node;
+    fn textarea(&mut self) -> TextareaElement<'_>;
 
     /// Groups the footer content in a table
-    fn tfoot(&mut self) -> Node;
+    fn tfoot(&mut self) -> Node<'_>;
 
     /// Defines a header cell in a table
-    fn th(&mut self) -> Node;
+    fn th(&mut self) -> ThElement<'_>;
 
     /// Groups the header content in a table
-    fn thead(&mut self) -> Node;
+    fn thead(&mut self) -> Node<'_>;
 
     /// Defines a specific time (or datetime)
-    fn time(&mut self) -> Node;
+    fn time(&mut self) -> TimeElement<'_>;
 
     /// Defines a title for the document
-    fn title(&mut self) -> Node;
+    fn title(&mut self) -> Node<'_>;
 
     /// Defines a row in a table
-    fn tr(&mut self) -> Node;
+    fn tr(&mut self) -> Node<'_>;
 
     /// Defines text tracks for media elements (`<video>` and `<audio>`)
-    fn track(&mut self) -> Void;
+    fn track(&mut self) -> TrackElement<'_>;
 
     /// Defines some text that is unarticulated and styled differently from normal text
-    fn u(&mut self) -> Node;
+    fn u(&mut self) -> Node<'_>;
 
     /// Defines an unordered list
-    fn ul(&mut self) -> Node;
+    fn ul(&mut self) -> Node<'_>;
 
     /// Defines a variable
-    fn var(&mut self) -> Node;
+    fn var(&mut self) -> Node<'_>;
 
     /// Defines embedded video content
-    fn video(&mut self) -> Node;
+    fn video(&mut self) -> VideoElement<'_>;
 
     /// Defines a possible line-break
-    fn wbr(&mut self) -> Void;
+    fn wbr(&mut self) -> Void<'_>;
+
+    /// Defines a hyperlink containing `text`
+    ///
+    /// The `*_text` methods create an element, write some text into it, and
+    /// close it again.  The text is escaped as usual.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new();
+    /// let mut nav = buf.nav();
+    /// nav.h2_text("Contents").a_text("/page_1.html", "Page 1");
+    /// drop(nav);
+    /// assert_eq!(buf.finish(), "\
+    /// <nav>
+    ///  <h2>
+    /// Contents
+    ///  </h2>
+    ///  <a href=\"/page_1.html\">
+    /// Page 1
+    ///  </a>
+    /// </nav>
+    /// ");
+    /// ```
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn a_text(&mut self, href: impl Display, text: impl Display) -> &mut Self {
+        self.a().href(href).text(text);
+        self
+    }
+
+    text_methods! {
+        abbr => abbr_text,
+        address => address_text,
+        article => article_text,
+        aside => aside_text,
+        audio => audio_text,
+        b => b_text,
+        bdi => bdi_text,
+        bdo => bdo_text,
+        blockquote => blockquote_text,
+        body => body_text,
+        button => button_text,
+        canvas => canvas_text,
+        caption => caption_text,
+        cite => cite_text,
+        code => code_text,
+        colgroup => colgroup_text,
+        data => data_text,
+        datalist => datalist_text,
+        dd => dd_text,
+        del => del_text,
+        details => details_text,
+        dfn => dfn_text,
+        dialog => dialog_text,
+        div => div_text,
+        dl => dl_text,
+        dt => dt_text,
+        em => em_text,
+        fieldset => fieldset_text,
+        figcaption => figcaption_text,
+        figure => figure_text,
+        footer => footer_text,
+        form => form_text,
+        h1 => h1_text,
+        h2 => h2_text,
+        h3 => h3_text,
+        h4 => h4_text,
+        h5 => h5_text,
+        h6 => h6_text,
+        head => head_text,
+        header => header_text,
+        hgroup => hgroup_text,
+        html => html_text,
+        i => i_text,
+        iframe => iframe_text,
+        ins => ins_text,
+        kbd => kbd_text,
+        label => label_text,
+        legend => legend_text,
+        li => li_text,
+        main => main_text,
+        map => map_text,
+        mark => mark_text,
+        math => math_text,
+        menu => menu_text,
+        meter => meter_text,
+        nav => nav_text,
+        noscript => noscript_text,
+        object => object_text,
+        ol => ol_text,
+        optgroup => optgroup_text,
+        option => option_text,
+        output => output_text,
+        p => p_text,
+        picture => picture_text,
+        pre => pre_text,
+        progress => progress_text,
+        q => q_text,
+        rp => rp_text,
+        rt => rt_text,
+        ruby => ruby_text,
+        s => s_text,
+        samp => samp_text,
+        script => script_text,
+        search => search_text,
+        section => section_text,
+        select => select_text,
+        slot => slot_text,
+        small => small_text,
+        span => span_text,
+        strong => strong_text,
+        style => style_text,
+        sub => sub_text,
+        summary => summary_text,
+        sup => sup_text,
+        svg => svg_text,
+        table => table_text,
+        tbody => tbody_text,
+        td => td_text,
+        template => template_text,
+        textarea => textarea_text,
+        tfoot => tfoot_text,
+        th => th_text,
+        thead => thead_text,
+        time => time_text,
+        title => title_text,
+        tr => tr_text,
+        u => u_text,
+        ul => ul_text,
+        var => var_text,
+        video => video_text,
+    }
 }
 
 impl<'a> Html5 for Node<'a> {
     /// Defines the document type
+    #[cfg_attr(feature = "source-locations", track_caller)]
     fn doctype(&mut self) {
-        self.void_child(Cow::Borrowed("!DOCTYPE")).attr("html");
+        self.doctype_custom("html");
+    }
+
+    /// Defines the document type as XHTML 1.0 Strict
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn doctype_xhtml1_strict(&mut self) {
+        self.doctype_custom(concat!(
+            r#"html PUBLIC "-//W3C//DTD XHTML 1.0 Strict//EN" "#,
+            r#""http://www.w3.org/TR/xhtml1/DTD/xhtml1-strict.dtd""#,
+        ));
+    }
+
+    /// Defines the document type as HTML 4.01 Strict
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn doctype_html4(&mut self) {
+        self.doctype_custom(concat!(
+            r#"HTML PUBLIC "-//W3C//DTD HTML 4.01//EN" "#,
+            r#""http://www.w3.org/TR/html4/strict.dtd""#,
+        ));
+    }
+
+    /// Defines the document type using an arbitrary declaration
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn doctype_custom(&mut self, decl: &str) {
+        self.void_child(Cow::Borrowed("!DOCTYPE")).attr(decl);
     }
 
     /// Defines a hyperlink
-    fn a(&mut self) -> Node {
-        self.child(Cow::Borrowed("a"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn a(&mut self) -> AElement<'_> {
+        self.child(Cow::Borrowed("a")).into()
     }
 
     /// Defines an abbreviation or an acronym
-    fn abbr(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn abbr(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("abbr"))
     }
 
     /// Defines contact information for the author/owner of a document
-    fn address(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn address(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("address"))
     }
 
     /// Defines an area inside an image map
-    fn area(&mut self) -> Void {
-        self.void_child(Cow::Borrowed("area"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn area(&mut self) -> AreaElement<'_> {
+        self.void_child(Cow::Borrowed("area")).into()
     }
 
     /// Defines an article
-    fn article(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn article(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("article"))
     }
 
     /// Defines content aside from the page content
-    fn aside(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn aside(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("aside"))
     }
 
     /// Defines embedded sound content
-    fn audio(&mut self) -> Node {
-        self.child(Cow::Borrowed("audio"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn audio(&mut self) -> AudioElement<'_> {
+        self.child(Cow::Borrowed("audio")).into()
     }
 
     /// Defines bold text
-    fn b(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn b(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("b"))
     }
 
     /// Specifies the base URL/target for all relative URLs in a document
-    fn base(&mut self) -> Void {
-        self.void_child(Cow::Borrowed("base"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn base(&mut self) -> BaseElement<'_> {
+        self.void_child(Cow::Borrowed("base")).into()
     }
 
     /// Isolates a part of text that might be formatted in a different direction from other text outside it
-    fn bdi(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn bdi(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("bdi"))
     }
 
     /// Overrides the current text direction
-    fn bdo(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn bdo(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("bdo"))
     }
 
     /// Defines a section that is quoted from another source
-    fn blockquote(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn blockquote(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("blockquote"))
     }
 
     /// Defines the document's body
-    fn body(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn body(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("body"))
     }
 
     /// Defines a single line break
-    fn br(&mut self) -> Void {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn br(&mut self) -> Void<'_> {
         self.void_child(Cow::Borrowed("br"))
     }
 
     /// Defines a clickable button
-    fn button(&mut self) -> Node {
-        self.child(Cow::Borrowed("button"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn button(&mut self) -> ButtonElement<'_> {
+        self.child(Cow::Borrowed("button")).into()
     }
 
     /// Used to draw graphics, on the fly, via scripting (usually JavaScript)
-    fn canvas(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn canvas(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("canvas"))
     }
 
     /// Defines a table caption
-    fn caption(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn caption(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("caption"))
     }
 
     /// Defines the title of a work
-    fn cite(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn cite(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("cite"))
     }
 
     /// Defines a piece of computer code
-    fn code(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn code(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("code"))
     }
 
     /// Specifies column properties for each column within a `<colgroup>` element
-    fn col(&mut self) -> Void {
-        self.void_child(Cow::Borrowed("col"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn col(&mut self) -> ColElement<'_> {
+        self.void_child(Cow::Borrowed("col")).into()
     }
 
     /// Specifies a group of one or more columns in a table for formatting
-    fn colgroup(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn colgroup(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("colgroup"))
     }
 
     /// Adds a machine-readable translation of a given content
-    fn data(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn data(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("data"))
     }
 
     /// Specifies a list of pre-defined options for input controls
-    fn datalist(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn datalist(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("datalist"))
     }
 
     /// Defines a description/value of a term in a description list
-    fn dd(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn dd(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("dd"))
     }
 
     /// Defines text that has been deleted from a document
-    fn del(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn del(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("del"))
     }
 
     /// Defines additional details that the user can view or hide
-    fn details(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn details(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("details"))
     }
 
     /// Specifies a term that is going to be defined within the content
-    fn dfn(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn dfn(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("dfn"))
     }
 
     /// Defines a dialog box or window
-    fn dialog(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn dialog(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("dialog"))
     }
 
     /// Defines a section in a document
-    fn div(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn div(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("div"))
     }
 
     /// Defines a description list
-    fn dl(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn dl(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("dl"))
     }
 
     /// Defines a term/name in a description list
-    fn dt(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn dt(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("dt"))
     }
 
     /// Defines emphasized text
-    fn em(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn em(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("em"))
     }
 
     /// Defines a container for an external application
-    fn embed(&mut self) -> Void {
-        self.void_child(Cow::Borrowed("embed"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn embed(&mut self) -> EmbedElement<'_> {
+        self.void_child(Cow::Borrowed("embed")).into()
     }
 
     /// Groups related elements in a form
-    fn fieldset(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn fieldset(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("fieldset"))
     }
 
     /// Defines a caption for a `<figure>` element
-    fn figcaption(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn figcaption(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("figcaption"))
     }
 
     /// Specifies self-contained content
-    fn figure(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn figure(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("figure"))
     }
 
     /// Defines a footer for a document or section
-    fn footer(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn footer(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("footer"))
     }
 
     /// Defines an HTML form for user input
-    fn form(&mut self) -> Node {
-        self.child(Cow::Borrowed("form"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn form(&mut self) -> FormElement<'_> {
+        self.child(Cow::Borrowed("form")).into()
     }
 
     /// Defines HTML headings
-    fn h1(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn h1(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("h1"))
     }
 
     /// Defines HTML headings
-    fn h2(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn h2(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("h2"))
     }
 
     /// Defines HTML headings
-    fn h3(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn h3(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("h3"))
     }
 
     /// Defines HTML headings
-    fn h4(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn h4(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("h4"))
     }
 
     /// Defines HTML headings
-    fn h5(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn h5(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("h5"))
     }
 
     /// Defines HTML headings
-    fn h6(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn h6(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("h6"))
     }
 
     /// Contains metadata/information for the document
-    fn head(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn head(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("head"))
     }
 
     /// Defines a header for a document or section
-    fn header(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn header(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("header"))
     }
 
+    /// Groups a heading with related content, such as subheadings
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn hgroup(&mut self) -> Node<'_> {
+        self.child(Cow::Borrowed("hgroup"))
+    }
+
     /// Defines a thematic change in the content
-    fn hr(&mut self) -> Void {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn hr(&mut self) -> Void<'_> {
         self.void_child(Cow::Borrowed("hr"))
     }
 
     /// Defines the root of an HTML document
-    fn html(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn html(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("html"))
     }
 
     /// Defines a part of text in an alternate voice or mood
-    fn i(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn i(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("i"))
     }
 
     /// Defines an inline frame
-    fn iframe(&mut self) -> Node {
-        self.child(Cow::Borrowed("iframe"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn iframe(&mut self) -> IframeElement<'_> {
+        self.child(Cow::Borrowed("iframe")).into()
     }
 
     /// Defines an image
-    fn img(&mut self) -> Void {
-        self.void_child(Cow::Borrowed("img"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn img(&mut self) -> ImgElement<'_> {
+        self.void_child(Cow::Borrowed("img")).into()
     }
 
     /// Defines an input control
-    fn input(&mut self) -> Void {
-        self.void_child(Cow::Borrowed("input"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn input(&mut self) -> InputElement<'_> {
+        self.void_child(Cow::Borrowed("input")).into()
     }
 
     /// Defines a text that has been inserted into a document
-    fn ins(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn ins(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("ins"))
     }
 
     /// Defines keyboard input
-    fn kbd(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn kbd(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("kbd"))
     }
 
     /// Defines a label for an `<input>` element
-    fn label(&mut self) -> Node {
-        self.child(Cow::Borrowed("label"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn label(&mut self) -> LabelElement<'_> {
+        self.child(Cow::Borrowed("label")).into()
     }
 
     /// Defines a caption for a `<fieldset>` element
-    fn legend(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn legend(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("legend"))
     }
 
     /// Defines a list item
-    fn li(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn li(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("li"))
     }
 
     /// Defines the relationship between a document and an external resource (most used to link to style sheets)
-    fn link(&mut self) -> Void {
-        self.void_child(Cow::Borrowed("link"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn link(&mut self) -> LinkElement<'_> {
+        self.void_child(Cow::Borrowed("link")).into()
     }
 
     /// Specifies the main content of a document
-    fn main(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn main(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("main"))
     }
 
     /// Defines an image map
-    fn map(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn map(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("map"))
     }
 
     /// Defines marked/highlighted text
-    fn mark(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn mark(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("mark"))
     }
 
+    /// Defines a MathML formula
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn math(&mut self) -> Node<'_> {
+        self.child(Cow::Borrowed("math"))
+    }
+
+    /// Defines an unordered list of commands
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn menu(&mut self) -> Node<'_> {
+        self.child(Cow::Borrowed("menu"))
+    }
+
     /// Defines metadata about an HTML document
-    fn meta(&mut self) -> Void {
-        self.void_child(Cow::Borrowed("meta"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn meta(&mut self) -> MetaElement<'_> {
+        self.void_child(Cow::Borrowed("meta")).into()
     }
 
     /// Defines a scalar measurement within a known range (a gauge)
-    fn meter(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn meter(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("meter"))
     }
 
     /// Defines navigation links
-    fn nav(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn nav(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("nav"))
     }
 
     /// Defines an alternate content for users that do not support client-side scripts
-    fn noscript(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn noscript(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("noscript"))
     }
 
     /// Defines a container for an external application
-    fn object(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn object(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("object"))
     }
 
     /// Defines an ordered list
-    fn ol(&mut self) -> Node {
-        self.child(Cow::Borrowed("ol"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn ol(&mut self) -> OlElement<'_> {
+        self.child(Cow::Borrowed("ol")).into()
     }
 
     /// Defines a group of related options in a drop-down list
-    fn optgroup(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn optgroup(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("optgroup"))
     }
 
     /// Defines an option in a drop-down list
-    fn option(&mut self) -> Node {
-        self.child(Cow::Borrowed("option"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn option(&mut self) -> OptionElement<'_> {
+        self.child(Cow::Borrowed("option")).into()
     }
 
     /// Defines the result of a calculation
-    fn output(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn output(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("output"))
     }
 
     /// Defines a paragraph
-    fn p(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn p(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("p"))
     }
 
     /// Defines a parameter for an object
-    fn param(&mut self) -> Void {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn param(&mut self) -> Void<'_> {
         self.void_child(Cow::Borrowed("param"))
     }
 
     /// Defines a container for multiple image resources
-    fn picture(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn picture(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("picture"))
     }
 
     /// Defines preformatted text
-    fn pre(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn pre(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("pre"))
     }
 
     /// Represents the progress of a task
-    fn progress(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn progress(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("progress"))
     }
 
     /// Defines a short quotation
-    fn q(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn q(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("q"))
     }
 
     /// Defines what to show in browsers that do not support ruby annotations
-    fn rp(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn rp(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("rp"))
     }
 
     /// Defines an explanation/pronunciation of characters (for East Asian typography)
-    fn rt(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn rt(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("rt"))
     }
 
     /// Defines a ruby annotation (for East Asian typography)
-    fn ruby(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn ruby(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("ruby"))
     }
 
     /// Defines text that is no longer correct
-    fn s(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn s(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("s"))
     }
 
     /// Defines sample output from a computer program
-    fn samp(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn samp(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("samp"))
     }
 
     /// Defines a client-side script
-    fn script(&mut self) -> Node {
-        self.child(Cow::Borrowed("script"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn script(&mut self) -> ScriptElement<'_> {
+        self.child(Cow::Borrowed("script")).into()
+    }
+
+    /// Defines a section containing search or filtering controls
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn search(&mut self) -> Node<'_> {
+        self.child(Cow::Borrowed("search"))
     }
 
     /// Defines a section in a document
-    fn section(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn section(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("section"))
     }
 
     /// Defines a drop-down list
-    fn select(&mut self) -> Node {
-        self.child(Cow::Borrowed("select"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn select(&mut self) -> SelectElement<'_> {
+        self.child(Cow::Borrowed("select")).into()
+    }
+
+    /// Defines a placeholder inside a web component that you can fill with your own markup
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn slot(&mut self) -> Node<'_> {
+        self.child(Cow::Borrowed("slot"))
     }
 
     /// Defines smaller text
-    fn small(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn small(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("small"))
     }
 
     /// Defines multiple media resources for media elements (`<video>` and `<audio>`)
-    fn source(&mut self) -> Void {
-        self.void_child(Cow::Borrowed("source"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn source(&mut self) -> SourceElement<'_> {
+        self.void_child(Cow::Borrowed("source")).into()
     }
 
     /// Defines a section in a document
-    fn span(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn span(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("span"))
     }
 
     /// Defines important text
-    fn strong(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn strong(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("strong"))
     }
 
     /// Defines style information for a document
-    fn style(&mut self) -> Node {
-        self.child(Cow::Borrowed("style"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn style(&mut self) -> StyleElement<'_> {
+        self.child(Cow::Borrowed("style")).into()
     }
 
     /// Defines subscripted text
-    fn sub(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn sub(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("sub"))
     }
 
     /// Defines a visible heading for a `<details>` element
-    fn summary(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn summary(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("summary"))
     }
 
     /// Defines superscripted text
-    fn sup(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn sup(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("sup"))
     }
 
     /// Defines a container for SVG graphics
-    fn svg(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn svg(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("svg"))
     }
 
     /// Defines a table
-    fn table(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn table(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("table"))
     }
 
     /// Groups the body content in a table
-    fn tbody(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn tbody(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("tbody"))
     }
 
     /// Defines a cell in a table
-    fn td(&mut self) -> Node {
-        self.child(Cow::Borrowed("td"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn td(&mut self) -> TdElement<'_> {
+        self.child(Cow::Borrowed("td")).into()
     }
 
     /// Defines a container for content that should be hidden when the page loads
-    fn template(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn template(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("template"))
     }
 
     /// Defines a multiline input control (text area)
-    fn textarea(&mut self) -> Node {
-        self.child(Cow::Borrowed("textarea"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn textarea(&mut self) -> TextareaElement<'_> {
+        self.child(Cow::Borrowed("textarea")).into()
     }
 
     /// Groups the footer content in a table
-    fn tfoot(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn tfoot(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("tfoot"))
     }
 
     /// Defines a header cell in a table
-    fn th(&mut self) -> Node {
-        self.child(Cow::Borrowed("th"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn th(&mut self) -> ThElement<'_> {
+        self.child(Cow::Borrowed("th")).into()
     }
 
     /// Groups the header content in a table
-    fn thead(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn thead(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("thead"))
     }
 
     /// Defines a specific time (or datetime)
-    fn time(&mut self) -> Node {
-        self.child(Cow::Borrowed("time"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn time(&mut self) -> TimeElement<'_> {
+        self.child(Cow::Borrowed("time")).into()
     }
 
     /// Defines a title for the document
-    fn title(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn title(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("title"))
     }
 
     /// Defines a row in a table
-    fn tr(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn tr(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("tr"))
     }
 
     /// Defines text tracks for media elements (`<video>` and `<audio>`)
-    fn track(&mut self) -> Void {
-        self.void_child(Cow::Borrowed("track"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn track(&mut self) -> TrackElement<'_> {
+        self.void_child(Cow::Borrowed("track")).into()
     }
 
     /// Defines some text that is unarticulated and styled differently from normal text
-    fn u(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn u(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("u"))
     }
 
     /// Defines an unordered list
-    fn ul(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn ul(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("ul"))
     }
 
     /// Defines a variable
-    fn var(&mut self) -> Node {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn var(&mut self) -> Node<'_> {
         self.child(Cow::Borrowed("var"))
     }
 
     /// Defines embedded video content
-    fn video(&mut self) -> Node {
-        self.child(Cow::Borrowed("video"))
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn video(&mut self) -> VideoElement<'_> {
+        self.child(Cow::Borrowed("video")).into()
     }
 
     /// Defines a possible line-break
-    fn wbr(&mut self) -> Void {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn wbr(&mut self) -> Void<'_> {
         self.void_child(Cow::Borrowed("wbr"))
     }
 }
@@ -0,0 +1,49 @@
+use crate::{Buffer, Node};
+use std::borrow::Cow;
+use std::sync::Arc;
+
+/// Translates text written with [`Node::t()`]
+pub(crate) type Translator = Arc<dyn Fn(&str) -> Cow<'_, str> + Send + Sync>;
+
+impl Buffer {
+    /// Installs a function which translates text written with
+    /// [`t()`][Node::t].
+    ///
+    /// This is the integration point for a localization library such as
+    /// Fluent or gettext: the function is given the message (or message
+    /// ID), and returns the text in the user's language.  Without a
+    /// translator, messages are written as-is.
+    ///
+    /// ```
+    /// use html_builder::*;
+    /// use std::borrow::Cow;
+    ///
+    /// let mut buf = Buffer::new().translator(|msg| match msg {
+    ///     "Hello" => Cow::Borrowed("Bonjour"),
+    ///     _ => Cow::Borrowed(msg),
+    /// });
+    /// buf.p().t("Hello").t("Rust & HTML");
+    /// assert_eq!(buf.finish(), "<p>\nBonjour\nRust &amp; HTML\n</p>\n");
+    /// ```
+    pub fn translator(
+        mut self,
+        translator: impl Fn(&str) -> Cow<'_, str> + Send + Sync + 'static,
+    ) -> Buffer {
+        self.node.ctx.get().translator = Some(Arc::new(translator));
+        self
+    }
+}
+
+impl<'a> Node<'a> {
+    /// Write a translated message into the node, followed by a newline
+    ///
+    /// The message is passed through the buffer's
+    /// [translator][Buffer::translator], and the result is written like
+    /// [`text()`][Node::text].
+    pub fn t(&mut self, msg: &str) -> &mut Self {
+        match self.ctx.get().translator.clone() {
+            Some(translator) => self.text(translator(msg)),
+            None => self.text(msg),
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Rendering JSON values as HTML
+
+use crate::{Html5, Node};
+use serde_json::{Map, Value};
+
+impl<'a> Node<'a> {
+    /// Write a JSON value into the node, as HTML.
+    ///
+    /// This method is only available with the `serde_json` feature.  It's
+    /// meant for debug views of API payloads and the like:
+    ///
+    /// * Objects become a `<dl>`.
+    /// * Arrays of objects which all have the same keys become a `<table>`,
+    ///   with a column for each key.  Other arrays become a `<ul>`.
+    /// * Strings, numbers, booleans, and `null` are written as text.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let value = serde_json::json!({ "tags": ["a", "<b>"] });
+    /// let mut buf = Buffer::new();
+    /// buf.json(&value);
+    /// assert_eq!(buf.finish(), "\
+    /// <dl>
+    ///  <dt>
+    /// tags
+    ///  </dt>
+    ///  <dd>
+    ///   <ul>
+    ///    <li>
+    /// a
+    ///    </li>
+    ///    <li>
+    /// &lt;b&gt;
+    ///    </li>
+    ///   </ul>
+    ///  </dd>
+    /// </dl>
+    /// ");
+    /// ```
+    pub fn json(&mut self, value: &Value) {
+        match value {
+            Value::Null | Value::Bool(_) | Value::Number(_) => {
+                self.text(value);
+            }
+            Value::String(x) => {
+                self.text(x);
+            }
+            Value::Array(items) => match table_columns(items) {
+                Some(columns) => {
+                    let mut table = self.table();
+                    let mut thead = table.thead();
+                    let mut tr = thead.tr();
+                    for column in columns {
+                        tr.th_text(column);
+                    }
+                    let mut tbody = table.tbody();
+                    for item in items {
+                        let mut tr = tbody.tr();
+                        for value in item.as_object().into_iter().flat_map(Map::values) {
+                            tr.td().json(value);
+                        }
+                    }
+                }
+                None => {
+                    let mut ul = self.ul();
+                    for item in items {
+                        ul.li().json(item);
+                    }
+                }
+            },
+            Value::Object(map) => {
+                let mut dl = self.dl();
+                for (key, value) in map {
+                    dl.dt().text(key);
+                    dl.dd().json(value);
+                }
+            }
+        }
+    }
+}
+
+/// The keys, if `items` is a non-empty list of objects which all have the
+/// same keys in the same order
+fn table_columns(items: &[Value]) -> Option<Vec<&String>> {
+    let first = items.first()?.as_object()?;
+    let same_keys = |item: &Value| match item.as_object() {
+        Some(obj) => obj.keys().eq(first.keys()),
+        None => false,
+    };
+    if items.iter().all(same_keys) {
+        Some(first.keys().collect())
+    } else {
+        None
+    }
+}
+
+impl<'a> Node<'a> {
+    /// Writes a `<script type="importmap">`
+    ///
+    /// This method is only available with the `serde_json` feature.  The
+    /// import map controls how the browser resolves the module specifiers
+    /// in `import` statements.  It must come before any module scripts.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let map = serde_json::json!({
+    ///     "imports": { "lit": "https://cdn.example.com/lit@3/index.js" },
+    /// });
+    /// let mut buf = Buffer::new();
+    /// buf.importmap(&map);
+    /// buf.script_src("/app.js").module();
+    /// assert_eq!(buf.finish(), "\
+    /// <script type=\"importmap\">
+    /// {\"imports\":{\"lit\":\"https://cdn.example.com/lit@3/index.js\"}}
+    /// </script>
+    /// <script src=\"/app.js\" type=\"module\">
+    /// </script>
+    /// ");
+    /// ```
+    pub fn importmap(&mut self, map: &Value) -> &mut Self {
+        // A `</script>` in a string would end the element early
+        let json = map.to_string().replace("</", "<\\/");
+        self.script().type_("importmap").raw().text(json);
+        self
+    }
+}
@@ -0,0 +1,73 @@
+//! Obsolete elements, for very old browsers
+
+use crate::{Node, Void};
+use std::borrow::Cow;
+
+macro_rules! legacy_elements {
+    ($($(#[$m:meta])* $method:ident($ty:ident) => $tag:literal,)*) => {
+        /// Helper methods for obsolete HTML elements.
+        ///
+        /// This trait is only available with the `legacy` feature.  These
+        /// elements were removed from HTML, and modern browsers support
+        /// them only for compatibility, if at all; use them when the output
+        /// is for browsers which don't support CSS, such as those of old
+        /// set-top boxes.  Their presentational attributes, such as `<font
+        /// color>`, can be set with
+        /// [`attr_value()`][crate::GlobalAttributes::attr_value].
+        ///
+        /// ```
+        /// use html_builder::*;
+        ///
+        /// let mut buf = Buffer::new();
+        /// let mut center = buf.center();
+        /// center.font().attr_value("color", "red").text("Breaking news");
+        /// assert_eq!(buf.finish(), "\
+        /// <center>
+        ///  <font color=\"red\">
+        /// Breaking news
+        ///  </font>
+        /// </center>
+        /// ");
+        /// ```
+        pub trait LegacyElements {
+            $(
+                $(#[$m])*
+                fn $method(&mut self) -> $ty<'_>;
+            )*
+        }
+
+        impl<'a> LegacyElements for Node<'a> {
+            $(
+                #[cfg_attr(feature = "source-locations", track_caller)]
+                fn $method(&mut self) -> $ty<'_> {
+                    legacy_elements!(@$ty self, $tag)
+                }
+            )*
+        }
+    };
+    (@Node $self:ident, $tag:literal) => { $self.child(Cow::Borrowed($tag)) };
+    (@Void $self:ident, $tag:literal) => { $self.void_child(Cow::Borrowed($tag)) };
+}
+
+legacy_elements! {
+    /// Defines a font size larger than the surrounding text
+    big(Node) => "big",
+    /// Defines a default font for the document
+    basefont(Void) => "basefont",
+    /// Defines horizontally centred content
+    center(Node) => "center",
+    /// Defines the font, size, and colour of some text
+    font(Node) => "font",
+    /// Defines a frame within a `<frameset>`
+    frame(Void) => "frame",
+    /// Defines a set of frames, in place of a `<body>`
+    frameset(Node) => "frameset",
+    /// Defines scrolling text
+    marquee(Node) => "marquee",
+    /// Defines content for browsers which don't support frames
+    noframes(Node) => "noframes",
+    /// Defines struck-out text
+    strike(Node) => "strike",
+    /// Defines teletype (monospaced) text
+    tt(Node) => "tt",
+}
@@ -50,9 +50,7 @@ writeln!(body.h1(), "It's a website!")?;
 let mut list = body.ul();
 for i in 1..=3 {
     writeln!(
-        list.li().a().attr(
-            &format!("href='/page_{}.html'", i)
-        ),
+        list.li().a().attr(attrs!(href = format!("/page_{}.html", i))),
         "Page {}", i,
     )?
 }
@@ -60,9 +58,8 @@ for i in 1..=3 {
 // You can write functions which add subtrees to a node
 fn figure_with_caption(parent: &mut Node, src: &str, cap: &str) {
     let mut fig = parent.figure();
-    fig.img()
-        .attr(&format!("src='{}'", src))
-        .attr(&format!("alt='{}'", cap));
+    // The attrs! macro escapes the values
+    fig.img().attr(attrs!(src = src, alt = cap));
     writeln!(fig.figcaption(), "{}", cap).unwrap();
 }
 
@@ -90,12 +87,12 @@ buf.finish()
  <body>
   <h1>It's a website!</h1>
   <ul>
-   <li><a href='/page_1.html'>Page 1</a></li>
-   <li><a href='/page_2.html'>Page 2</a></li>
-   <li><a href='/page_3.html'>Page 3</a></li>
+   <li><a href="/page_1.html">Page 1</a></li>
+   <li><a href="/page_2.html">Page 2</a></li>
+   <li><a href="/page_3.html">Page 3</a></li>
   </ul>
   <figure>
-   <img src='img.jpg' alt='Awesome image'>
+   <img src="img.jpg" alt="Awesome image">
    <figcaption>Awesome image</figcaption>
   </figure>
   <footer>
@@ -108,16 +105,129 @@ buf.finish()
 
 */
 
+#[cfg(feature = "actix")]
+mod actix;
+mod amp;
+mod assets;
+mod attributes;
+#[cfg(feature = "axum")]
+mod axum;
+mod bidi;
+mod cache;
+mod chunks;
+mod control;
+#[cfg(any(feature = "chrono", feature = "time"))]
+mod datetime;
+#[cfg(feature = "digest")]
+mod digest;
+#[cfg(feature = "wasm")]
+mod dom;
+mod elements;
+mod entities;
+mod error;
+mod escape;
+mod form;
+mod format;
+mod head;
 mod html;
+mod i18n;
+#[cfg(feature = "serde_json")]
+mod json;
+#[cfg(feature = "legacy")]
+mod legacy;
+mod lists;
+mod macros;
+mod meta;
+pub mod model;
+mod nav;
+mod observe;
+#[cfg(feature = "parse")]
+mod parse;
+mod policy;
+#[cfg(feature = "ammonia")]
+mod sanitize;
+mod selector;
+#[cfg(feature = "serde")]
+mod ser;
+mod sink;
+mod srcdoc;
+mod sri;
+mod stats;
+mod stimulus;
+#[cfg(feature = "tokio")]
+mod stream;
+mod svg;
+mod table;
+mod tag;
+#[cfg(feature = "test-util")]
+mod test_util;
+#[cfg(feature = "html5ever")]
+mod tokens;
+mod tree;
+mod turbo;
+mod url;
+mod validate;
+mod values;
+mod xml;
+pub use assets::Stylesheet;
+pub use attributes::*;
+pub use bidi::{isolate, Dir};
+pub use cache::{FragmentCache, Static};
+pub use chunks::Chunks;
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub use datetime::Timestamp;
+#[cfg(feature = "wasm")]
+pub use dom::DomSink;
+pub use elements::*;
+pub use entities::Encoding;
+pub use error::Error;
+pub use form::{FormField, HtmlForm};
+pub use format::Format;
+pub use head::HeadManager;
 pub use html::*;
+#[cfg(feature = "macros")]
+pub use html_builder_macros::{html, include_html, HtmlForm, ToHtmlTable};
+#[cfg(feature = "legacy")]
+pub use legacy::LegacyElements;
+#[doc(hidden)]
+pub use macros::__private;
+pub use meta::{Icons, OpenGraph};
+pub use observe::Event;
+pub use selector::{Selector, SelectorError};
+pub use sink::{Attribute, Sink};
+pub use sri::Integrity;
+pub use stats::Stats;
+pub use stimulus::Stimulus;
+#[cfg(feature = "tokio")]
+pub use stream::HtmlStream;
+pub use svg::{SvgBuffer, SvgElements};
+pub use table::{TableBuilder, ToHtmlTable};
+#[cfg(feature = "test-util")]
+pub use test_util::{normalize_for_snapshot, Rendered, SnapshotRules};
+pub use tree::*;
+pub use turbo::TurboStream;
+pub use url::{is_safe_url, BLOCKED_URL};
+pub use validate::{NestingError, ValidationError};
+pub use values::{
+    AriaCurrent, AriaLive, ButtonType, FormMethod, InputType, LinkRel, ReferrerPolicy, Sandbox,
+    Target,
+};
+pub use xml::*;
 
+use head::HeadItems;
+use sink::AnySink;
 use std::borrow::Cow;
 use std::fmt::Write;
-use std::sync::{Arc, Mutex, Weak};
+use tag::Tag;
 
 /// A buffer for writing HTML into.
+///
+/// The buffer owns its state, and the nodes created from it borrow it, so
+/// writing doesn't involve any locking or reference counting.  A `Buffer`
+/// is `Send`, so it can be moved to another thread (or async task) while
+/// it's being written.
 pub struct Buffer {
-    ctx: Arc<Mutex<Ctx>>,
+    /// The root node, which owns the buffer's state
     node: Node<'static>,
 }
 
@@ -136,11 +246,20 @@ pub struct Buffer {
 /// are escaped: `&`, `<`, and `>`.  The escaping can be strengthened or
 /// weakened using the [`safe()`][Node::safe] and [`raw()`][Node::raw]
 /// methods respectively.
+///
+/// Text written with `write!()` is escaped as it's formatted, so the
+/// formatted text is never collected into an intermediate `String`.
 pub struct Node<'a> {
     depth: usize,
-    ctx: Weak<Mutex<Ctx>>,
+    ctx: CtxRef<'a>,
     escaping: Escaping,
-    _phantom: std::marker::PhantomData<&'a ()>,
+}
+
+/// The buffer's state.  The root node owns it, and other nodes borrow it
+/// from their parent.
+enum CtxRef<'a> {
+    Owned(Box<Ctx>),
+    Borrowed(&'a mut Ctx),
 }
 
 enum Escaping {
@@ -154,23 +273,97 @@ enum Escaping {
 /// Void elements can't have any contents (since there's no end tag, no
 /// content can be put between the start tag and the end tag).
 pub struct Void<'a> {
-    ctx: Weak<Mutex<Ctx>>,
-    _phantom: std::marker::PhantomData<&'a ()>,
+    depth: usize,
+    ctx: &'a mut Ctx,
 }
 
 /// A comment.
 ///
 /// No escaping is performed on the contents.
 pub struct Comment<'a> {
-    ctx: Weak<Mutex<Ctx>>,
-    _phantom: std::marker::PhantomData<&'a ()>,
+    ctx: &'a mut Ctx,
+}
+
+/// Something which has been opened, but not yet passed to the sink.
+#[derive(Clone)]
+enum Pending {
+    /// The element at the top of the stack
+    Element,
+    Void(Tag),
+    Comment,
+}
+
+/// An element which has been opened but not yet closed.
+#[derive(Clone)]
+enum Frame {
+    Element(Tag),
+    Conditional,
 }
 
-#[derive(Default)]
 struct Ctx {
-    wtr: String,
-    stack: Vec<Cow<'static, str>>,
-    tag_open: Option<&'static str>,
+    wtr: Box<dyn AnySink>,
+    stack: Vec<Frame>,
+    tag_open: Option<Pending>,
+    /// The depth of the handle which owns the open tag
+    open_depth: usize,
+    /// Attributes which haven't been written yet.  They're written when the
+    /// open tag is closed, so until then they can be added to or replaced.
+    attrs: Vec<Attribute>,
+    /// Whether the last text written didn't end with a newline
+    mid_line: bool,
+    /// Whether the last text written ended with whitespace
+    trailing_space: bool,
+    /// The first bytes of a code point written with `io::Write`, whose
+    /// remaining bytes haven't been written yet
+    partial_utf8: Vec<u8>,
+    xml: bool,
+    strict: bool,
+    policies: policy::Policies,
+    translator: Option<i18n::Translator>,
+    stats: Stats,
+    errors: Vec<ValidationError>,
+    slots: Vec<Slot>,
+    head: HeadItems,
+    amp: Option<amp::Amp>,
+}
+
+impl Default for Ctx {
+    fn default() -> Ctx {
+        Ctx {
+            wtr: Box::new(sink::StringSink::default()),
+            stack: vec![],
+            tag_open: None,
+            open_depth: 0,
+            attrs: vec![],
+            mid_line: false,
+            trailing_space: false,
+            partial_utf8: vec![],
+            xml: false,
+            strict: false,
+            policies: policy::Policies::default(),
+            translator: None,
+            stats: Stats::default(),
+            errors: vec![],
+            slots: vec![],
+            head: HeadItems::default(),
+            amp: None,
+        }
+    }
+}
+
+/// A position reserved with [`Node::placeholder()`].
+///
+/// Pass it to [`Buffer::fill()`] to write content there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SlotId(usize);
+
+#[derive(Clone)]
+struct Slot {
+    /// Where in `wtr` the slot's content goes
+    offset: usize,
+    depth: usize,
+    /// The elements which were open when the slot was created
+    stack: Vec<Frame>,
 }
 
 impl Buffer {
@@ -179,25 +372,364 @@ impl Buffer {
         Buffer::default()
     }
 
+    /// Creates a new buffer which starts with some existing HTML, and
+    /// carries on inside the elements it leaves open.
+    ///
+    /// This is for adding to a prelude produced by something else, eg. a
+    /// page header from a template engine.  The prefix is written as it is.
+    /// Elements written to the buffer are indented as if they were inside
+    /// the prefix's open elements, and those elements are closed when the
+    /// buffer is finished.  In [strict mode][Buffer::strict], they count
+    /// as the parents of the new elements.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let prelude = "<!DOCTYPE html>\n<html>\n <body>\n  <main>\n  <h1>News</h1>\n";
+    /// let mut buf = Buffer::from_prefix(prelude);
+    /// buf.p().text("Nothing happened today.");
+    /// assert_eq!(buf.finish(), "\
+    /// <!DOCTYPE html>
+    /// <html>
+    ///  <body>
+    ///   <main>
+    ///   <h1>News</h1>
+    ///    <p>
+    /// Nothing happened today.
+    ///    </p>
+    ///   </main>
+    ///  </body>
+    /// </html>
+    /// ");
+    /// ```
+    ///
+    /// The prefix isn't checked.  An end tag closes the innermost open
+    /// element with the same name, and end tags which don't match any open
+    /// element are ignored.
+    pub fn from_prefix(html: &str) -> Buffer {
+        let mut buf = Buffer::new();
+        // Writing to a node never fails
+        buf.node.write_raw(html).unwrap();
+        if !html.is_empty() && !html.ends_with('\n') {
+            buf.node.write_raw("\n").unwrap();
+        }
+        let ctx = buf.node.ctx.get();
+        for tag in format::open_elements(html) {
+            ctx.stack.push(Frame::Element(tag.into()));
+        }
+        buf.node.depth = ctx.stack.len();
+        buf
+    }
+
     /// Closes all open tags and returns the buffer's contents.
+    ///
+    /// The contents of a [segmented][Buffer::segmented] buffer are joined
+    /// together.  Panics if the buffer was created with a [custom
+    /// sink][Buffer::with_sink].
     pub fn finish(self) -> String {
-        let mutex = Arc::try_unwrap(self.ctx).ok().unwrap();
-        let mut ctx = mutex.into_inner().unwrap();
-        ctx.close_deeper_than(0);
-        ctx.wtr
+        let mut ctx = self.into_ctx();
+        ctx.finish();
+        ctx.into_text()
     }
-}
 
-impl Default for Buffer {
-    fn default() -> Buffer {
-        let ctx = Arc::new(Mutex::new(Ctx::default()));
+    /// Closes all open tags and appends the buffer's contents to `out`.
+    ///
+    /// If `out` is empty, the contents are moved into it without being
+    /// copied.  Otherwise they're copied onto the end of it, which costs the
+    /// same as [`finish()`][Buffer::finish] followed by `push_str()`.
+    /// Panics if the buffer was created with a [custom
+    /// sink][Buffer::with_sink].
+    ///
+    /// ```
+    /// use html_builder::*;
+    /// use std::fmt::Write;
+    ///
+    /// let mut out = String::from("<!-- generated -->\n");
+    /// let mut buf = Buffer::new();
+    /// writeln!(buf.p(), "Hello")?;
+    /// buf.finish_into(&mut out);
+    /// assert_eq!(out, "<!-- generated -->\n<p>\nHello\n</p>\n");
+    /// # Ok::<(), std::fmt::Error>(())
+    /// ```
+    pub fn finish_into(self, out: &mut String) {
+        let text = self.finish();
+        if out.is_empty() {
+            *out = text;
+        } else {
+            out.push_str(&text);
+        }
+    }
+
+    /// Closes all open tags and returns the buffer's contents as UTF-8
+    /// bytes.
+    ///
+    /// This doesn't copy the output.  Panics if the buffer was created with
+    /// a [custom sink][Buffer::with_sink].
+    pub fn finish_bytes(self) -> Vec<u8> {
+        self.finish().into_bytes()
+    }
+
+    /// Returns the contents written so far, as they would be if the buffer
+    /// were finished now.
+    ///
+    /// Unlike [`finish()`][Buffer::finish], this doesn't consume the
+    /// buffer, so it's handy for debugging a long build function.  The
+    /// open tags are closed in the returned string, but not in the buffer.
+    /// Items collected by the [`HeadManager`] aren't included.  The buffer
+    /// also implements `Display`, which writes the same thing.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new();
+    /// let mut ul = buf.ul();
+    /// ul.li().text("One");
+    /// assert_eq!(buf.preview(), "<ul>\n <li>\nOne\n </li>\n</ul>\n");
+    /// ```
+    ///
+    /// Panics if the buffer was created with a [custom
+    /// sink][Buffer::with_sink].
+    pub fn preview(&self) -> String {
+        self.node.ctx.get_ref().preview()
+    }
+
+    /// Creates a new empty buffer which writes into the given sink.
+    ///
+    /// Use [`into_sink()`][Buffer::into_sink] to get the sink back.
+    /// [Placeholders][Node::placeholder] can only be used when the sink is a
+    /// `String`.
+    pub fn with_sink(sink: impl Sink + 'static) -> Buffer {
+        Buffer::from_ctx(Ctx {
+            wtr: Box::new(sink),
+            ..Ctx::default()
+        })
+    }
+
+    /// Creates a new empty buffer which writes its output to `wtr`.
+    ///
+    /// The output is written out in chunks as it's produced, rather than
+    /// being collected into one big `String`.  Use
+    /// [`finish_writer()`][Buffer::finish_writer] to get the writer back.
+    /// [Placeholders][Node::placeholder] can't be used with this kind of
+    /// buffer.
+    ///
+    /// ```
+    /// use html_builder::*;
+    /// use std::fmt::Write;
+    ///
+    /// let mut buf = Buffer::to_writer(Vec::new());
+    /// writeln!(buf.p(), "Hello")?;
+    /// let bytes: Vec<u8> = buf.finish_writer().unwrap();
+    /// assert_eq!(bytes, b"<p>\nHello\n</p>\n");
+    /// # Ok::<(), std::fmt::Error>(())
+    /// ```
+    pub fn to_writer<W: std::io::Write + Send + 'static>(wtr: W) -> Buffer {
+        Buffer::with_sink(sink::WriterSink::new(wtr))
+    }
+
+    /// Closes all open tags, writes out any remaining output, and returns
+    /// the writer.
+    ///
+    /// If the writer returned an error at any point, that error is returned
+    /// (anything written after the error is lost).
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the buffer wasn't created by
+    /// [`to_writer()`][Buffer::to_writer] with a writer of type `W`.
+    pub fn finish_writer<W: std::io::Write + Send + 'static>(self) -> std::io::Result<W> {
+        self.into_sink::<sink::WriterSink<W>>().finish()
+    }
+
+    /// Creates a new empty buffer which collects its output in segments of
+    /// roughly `size` bytes, rather than in one big `String`.
+    ///
+    /// For very large documents, growing a single `String` means copying
+    /// the whole output each time it runs out of capacity; a segmented
+    /// buffer never moves output once it's written.  Use
+    /// [`finish_segments()`][Buffer::finish_segments] to get the segments,
+    /// or [`finish()`][Buffer::finish] to join them together.
+    /// [Placeholders][Node::placeholder] can't be used with this kind of
+    /// buffer.
+    ///
+    /// A segment only goes over `size` when a single tag or piece of text
+    /// doesn't fit in what's left of it.
+    ///
+    /// ```
+    /// use html_builder::*;
+    /// use std::fmt::Write;
+    ///
+    /// let mut buf = Buffer::segmented(64 * 1024);
+    /// let mut table = buf.table();
+    /// for i in 0..10_000 {
+    ///     writeln!(table.tr().td(), "{}", i)?;
+    /// }
+    /// let segments = buf.finish_segments();
+    /// assert!(segments.len() > 1);
+    /// assert!(segments[0].starts_with("<table>\n"));
+    /// # Ok::<(), std::fmt::Error>(())
+    /// ```
+    pub fn segmented(size: usize) -> Buffer {
+        Buffer::with_sink(sink::SegmentSink::new(size))
+    }
+
+    /// Closes all open tags and returns the output of a
+    /// [segmented][Buffer::segmented] buffer.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the buffer wasn't created by
+    /// [`segmented()`][Buffer::segmented].
+    pub fn finish_segments(self) -> Vec<String> {
+        self.into_sink::<sink::SegmentSink>().finish()
+    }
+
+    /// Closes all open tags and returns the sink.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `S` isn't the type of the buffer's sink.
+    pub fn into_sink<S: Sink + 'static>(self) -> S {
+        let mut ctx = self.into_ctx();
+        ctx.finish();
+        *ctx.into_sink().downcast().expect("wrong sink type")
+    }
+
+    /// Closes all open tags and returns the buffer's contents.
+    ///
+    /// In [strict mode][Buffer::strict], this fails if any problems were
+    /// recorded.  Unlike [`finish()`][Buffer::finish], it also fails rather
+    /// than panicking if the buffer was created with a [custom
+    /// sink][Buffer::with_sink].
+    pub fn try_finish(self) -> Result<String, Error> {
+        let mut ctx = self.into_ctx();
+        ctx.finish();
+        if !ctx.errors.is_empty() {
+            return Err(Error::Invalid(ctx.errors));
+        }
+        ctx.try_into_text().ok_or(Error::NotText)
+    }
+
+    /// Closes all open tags, returns the buffer's contents, and empties the
+    /// buffer so that it can be used again.
+    ///
+    /// The buffer keeps its allocations (and its [strict
+    /// mode][Buffer::strict] setting), so when rendering many similar pages
+    /// in a loop, reusing one buffer saves reallocating as the output grows.
+    /// Validation errors, placeholders, and items given to the
+    /// [`HeadManager`] are cleared.
+    ///
+    /// Panics if the buffer was created with a [custom
+    /// sink][Buffer::with_sink].
+    ///
+    /// ```
+    /// use html_builder::*;
+    /// use std::fmt::Write;
+    ///
+    /// let mut buf = Buffer::new();
+    /// for name in ["Alice", "Bob"] {
+    ///     writeln!(buf.p(), "Hello, {}!", name)?;
+    ///     let page = buf.finish_and_reset();
+    ///     assert_eq!(page, format!("<p>\nHello, {}!\n</p>\n", name));
+    /// }
+    /// # Ok::<(), std::fmt::Error>(())
+    /// ```
+    pub fn finish_and_reset(&mut self) -> String {
+        self.node.finish_and_reset()
+    }
+
+    /// Enables strict mode.
+    ///
+    /// In strict mode, each new element is checked against the content model
+    /// of its parent.  Some common mistakes are detected: list items outside
+    /// a list, table rows and cells outside a table, block-level elements
+    /// inside a `<p>`, nested forms, and so on.  The check isn't exhaustive.
+    ///
+    /// Tag names, and the names of attributes set via [`GlobalAttributes`],
+    /// are also checked for invalid characters.  Attributes added with
+    /// [`attr()`][Node::attr] are written verbatim and not checked.  (An
+    /// attribute with an invalid name is dropped even outside strict mode,
+    /// since writing it could change the meaning of the tag.)
+    ///
+    /// Violations don't prevent anything from being written; instead they
+    /// are recorded, and can be inspected with
+    /// [`validation_errors()`][Buffer::validation_errors].
+    pub fn strict(mut self) -> Buffer {
+        self.node.ctx.get().strict = true;
+        self
+    }
+
+    /// Writes the source location of each element before it, as a comment.
+    ///
+    /// This method is only available with the `source-locations` feature,
+    /// and is meant for debugging: when you're looking at the generated
+    /// HTML and want to know which code wrote a particular element.  The
+    /// location is that of the call which created the element, eg.
+    /// [`div()`][Html5::div] or [`child()`][Node::child].
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new().source_locations();
+    /// buf.p().text("Hello");
+    /// let html = buf.finish();
+    /// assert!(html.starts_with("<!-- src/lib.rs:"));
+    /// assert!(html.ends_with(" -->\n<p>\nHello\n</p>\n"));
+    /// ```
+    #[cfg(feature = "source-locations")]
+    pub fn source_locations(mut self) -> Buffer {
+        self.node.ctx.get().policies.source_locations = true;
+        self
+    }
+
+    /// Returns the problems recorded so far.
+    ///
+    /// This is always empty unless the buffer is in
+    /// [strict mode][Buffer::strict].
+    pub fn validation_errors(&self) -> Vec<ValidationError> {
+        self.node.ctx.get_ref().errors.clone()
+    }
+
+    /// Writes content into a position reserved with
+    /// [`placeholder()`][Node::placeholder].
+    ///
+    /// The content is formatted as if it had been written at the time the
+    /// placeholder was created.  A slot can be filled more than once, in
+    /// which case the contents are written one after another.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `slot` was created by a different buffer.
+    pub fn fill(&mut self, slot: SlotId, f: impl FnOnce(&mut Node)) {
+        self.node.ctx.get().fill(slot, f);
+    }
+
+    fn from_ctx(ctx: Ctx) -> Buffer {
         let node = Node {
             depth: 0,
-            ctx: Arc::downgrade(&ctx),
+            ctx: CtxRef::Owned(Box::new(ctx)),
             escaping: Escaping::Normal,
-            _phantom: std::marker::PhantomData,
         };
-        Buffer { node, ctx }
+        Buffer { node }
+    }
+
+    fn into_ctx(self) -> Ctx {
+        match self.node.ctx {
+            CtxRef::Owned(ctx) => *ctx,
+            CtxRef::Borrowed(_) => unreachable!("the root node owns the state"),
+        }
+    }
+}
+
+impl Default for Buffer {
+    fn default() -> Buffer {
+        Buffer::from_ctx(Ctx::default())
+    }
+}
+
+impl std::fmt::Display for Buffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.preview())
     }
 }
 
@@ -214,79 +746,745 @@ impl std::ops::DerefMut for Buffer {
     }
 }
 
+impl<'a> CtxRef<'a> {
+    fn get(&mut self) -> &mut Ctx {
+        match self {
+            CtxRef::Owned(ctx) => ctx,
+            CtxRef::Borrowed(ctx) => ctx,
+        }
+    }
+
+    fn get_ref(&self) -> &Ctx {
+        match self {
+            CtxRef::Owned(ctx) => ctx,
+            CtxRef::Borrowed(ctx) => ctx,
+        }
+    }
+}
+
 impl Ctx {
     fn close_unclosed(&mut self) {
-        if let Some(closer) = self.tag_open.take() {
-            self.wtr.write_str(closer).unwrap();
+        let depth = self.open_depth.saturating_sub(1);
+        match self.tag_open.take() {
+            Some(Pending::Element) => {
+                if let Some(Frame::Element(tag)) = self.stack.last() {
+                    if let Some(amp) = &self.amp {
+                        amp.start_tag(tag, &mut self.attrs, &mut self.errors);
+                    }
+                    self.policies.apply(tag, &mut self.attrs);
+                    self.wtr.start_tag(depth, tag, &self.attrs);
+                    if let (Some(amp), "head") = (&self.amp, &**tag) {
+                        amp.write_boilerplate(&mut *self.wtr, depth + 1);
+                    }
+                }
+            }
+            Some(Pending::Void(tag)) => {
+                // Markup declarations such as `<!DOCTYPE ...>` are never
+                // self-closing
+                let self_closing = self.xml && !tag.starts_with('!');
+                if let Some(amp) = &self.amp {
+                    amp.start_tag(&tag, &mut self.attrs, &mut self.errors);
+                }
+                self.policies.apply(&tag, &mut self.attrs);
+                self.wtr.void_tag(depth, &tag, &self.attrs, self_closing);
+            }
+            Some(Pending::Comment) => self.wtr.end_comment(depth),
+            None => return,
         }
+        self.attrs.clear();
     }
 
     fn close_deeper_than(&mut self, depth: usize) {
         self.close_unclosed();
         let to_pop = self.stack.len() - depth;
         for _ in 0..to_pop {
-            if let Some(tag) = self.stack.pop() {
-                writeln!(self.wtr, "{:>w$}/{}>", "<", tag, w = self.stack.len() + 1).unwrap();
+            match self.stack.pop() {
+                Some(Frame::Element(tag)) => self.wtr.end_tag(self.stack.len(), &tag),
+                Some(Frame::Conditional) => self.wtr.end_conditional(self.stack.len()),
+                None => (),
+            }
+        }
+    }
+
+    /// Checks that an attribute name can be written without changing the
+    /// meaning of the tag.  If it can't, the attribute is dropped, which is
+    /// recorded as an error in strict mode.
+    fn check_attr_name(&mut self, name: &str) -> bool {
+        match validate::check_attr_name(name) {
+            Ok(()) => true,
+            Err(e) => {
+                if self.strict {
+                    self.errors.push(e);
+                }
+                false
+            }
+        }
+    }
+
+    /// Checks whether the handle at `depth` can still add attributes.
+    ///
+    /// This is only the case if nothing has been written since its open tag.
+    /// Otherwise the attribute is dropped, which is recorded as an error in
+    /// strict mode, and is a panic in debug builds.
+    fn can_add_attr(&mut self, depth: usize, attr: &str) -> bool {
+        if self.tag_open.is_some() && self.open_depth == depth {
+            return true;
+        }
+        let err = ValidationError::LateAttribute(attr.to_string());
+        if self.strict {
+            self.errors.push(err);
+        } else if cfg!(debug_assertions) {
+            panic!("{}", err);
+        }
+        false
+    }
+
+    fn attr(&mut self, depth: usize, attr: &str) {
+        // eg. from an `attrs!` whose attributes were all left out
+        if attr.is_empty() {
+            return;
+        }
+        if self.can_add_attr(depth, attr) {
+            self.attrs.push(Attribute::Raw(attr.to_string()));
+        }
+    }
+
+    /// Sets an attribute on the open tag.
+    ///
+    /// If the attribute has already been set, the new value replaces the old
+    /// one; except for `class`, where the new classes are appended.
+    fn attr_value(&mut self, depth: usize, name: &str, value: &str) {
+        if !self.can_add_attr(depth, name) || !self.check_attr_name(name) {
+            return;
+        }
+        let existing = self.attrs.iter_mut().find_map(|attr| match attr {
+            Attribute::Value(n, v) if n == name => Some(v),
+            _ => None,
+        });
+        match existing {
+            Some(v) if name == "class" => {
+                v.push(' ');
+                v.push_str(value);
+            }
+            Some(v) => *v = value.to_string(),
+            None => self
+                .attrs
+                .push(Attribute::Value(name.to_string(), value.to_string())),
+        }
+    }
+
+    fn attr_flag(&mut self, depth: usize, name: &str) {
+        if !self.can_add_attr(depth, name) || !self.check_attr_name(name) {
+            return;
+        }
+        let exists = self
+            .attrs
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Flag(n) if n == name));
+        if !exists {
+            self.attrs.push(Attribute::Flag(name.to_string()));
+        }
+    }
+
+    /// Writes an open tag.
+    ///
+    /// In strict mode the tag is validated first.  If `fallible` is set and
+    /// validation fails, nothing is written and the problems are returned;
+    /// otherwise they're recorded.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the tag would exceed the depth limit, unless `fallible` is
+    /// set.
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn open(&mut self, tag: &str, depth: usize, fallible: bool) -> Result<(), Error> {
+        if let Some(limit) = self.policies.max_depth {
+            if depth >= limit && !tag.starts_with('!') {
+                if fallible {
+                    return Err(Error::TooDeep(limit));
+                }
+                panic!("{}", Error::TooDeep(limit));
             }
         }
+        self.close_deeper_than(depth);
+        if self.strict && !tag.starts_with('!') {
+            let mut errors = vec![];
+            if let Err(e) = validate::check_tag_name(tag) {
+                errors.push(e);
+            }
+            if let Err(e) = validate::check(&self.stack, tag) {
+                errors.push(ValidationError::Nesting(e));
+            }
+            if fallible && !errors.is_empty() {
+                return Err(Error::Invalid(errors));
+            }
+            self.errors.extend(errors);
+        }
+        if !tag.starts_with('!') {
+            self.stats.record(tag, depth + 1);
+            #[cfg(feature = "source-locations")]
+            self.write_location(depth);
+        }
+        self.tag_open = Some(Pending::Element);
+        self.open_depth = depth + 1;
+        Ok(())
     }
 
-    fn open(&mut self, tag: &str, depth: usize) {
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    fn open_void(&mut self, tag: Tag, depth: usize, fallible: bool) -> Result<(), Error> {
+        if self.amp.is_some() && &*tag == "img" {
+            // `<amp-img>` isn't a void element
+            return self.open_element(tag, depth, fallible);
+        }
+        self.open(&tag, depth, fallible)?;
+        self.tag_open = Some(Pending::Void(tag));
+        Ok(())
+    }
+
+    fn open_conditional(&mut self, cond: &str, depth: usize) {
         self.close_deeper_than(depth);
-        write!(self.wtr, "{:>w$}{}", "<", tag, w = depth + 1).unwrap();
-        self.tag_open = Some(">\n");
+        self.wtr.start_conditional(depth, cond);
+        self.stack.push(Frame::Conditional);
+    }
+
+    fn fill(&mut self, slot: SlotId, f: impl FnOnce(&mut Node)) {
+        let Slot {
+            offset,
+            depth,
+            ref stack,
+        } = self.slots[slot.0];
+        let mut sub = Ctx {
+            stack: stack.clone(),
+            xml: self.xml,
+            strict: self.strict,
+            policies: self.policies.clone(),
+            translator: self.translator.clone(),
+            amp: self.amp.clone(),
+            ..Ctx::default()
+        };
+        sub.wtr.set_format(&self.policies.format);
+        f(&mut Node {
+            depth,
+            ctx: CtxRef::Borrowed(&mut sub),
+            escaping: Escaping::Normal,
+        });
+        sub.close_deeper_than(depth);
+        self.errors.append(&mut sub.errors);
+        self.head.merge(&sub.head);
+        self.stats.merge(&sub.stats, 0);
+        if let (Some(amp), Some(sub_amp)) = (&mut self.amp, &sub.amp) {
+            amp.merge(sub_amp);
+        }
+        let text = sub.into_text();
+        self.text_mut().insert_str(offset, &text);
+        for (i, other) in self.slots.iter_mut().enumerate() {
+            if other.offset > offset || (other.offset == offset && i >= slot.0) {
+                other.offset += text.len();
+            }
+        }
+    }
+
+    /// Writes the head items and closes everything, completing the document
+    fn finish(&mut self) {
+        self.flush_head();
+        self.close_deeper_than(0);
+        self.wtr.end();
+    }
+
+    /// Clears everything except the settings, keeping the allocations
+    fn reset(&mut self) {
+        self.text_mut().clear();
+        self.stack.clear();
+        self.tag_open = None;
+        self.open_depth = 0;
+        self.attrs.clear();
+        self.mid_line = false;
+        self.trailing_space = false;
+        self.partial_utf8.clear();
+        self.errors.clear();
+        self.slots.clear();
+        self.head = HeadItems::default();
+        self.stats = Stats::default();
+        if let Some(amp) = &mut self.amp {
+            amp.reset();
+        }
+    }
+
+    /// The output, if the sink is a `String`
+    fn text_mut(&mut self) -> &mut String {
+        sink::text_mut(self.sink_mut())
+            .expect("placeholders can only be used when writing to a String")
+    }
+
+    /// Renders the output so far with the open tags closed, without
+    /// changing the state
+    fn preview(&self) -> String {
+        let wtr = self.sink();
+        let text = match sink::text(wtr) {
+            Some(text) => text.clone(),
+            None => wtr
+                .downcast_ref::<sink::SegmentSink>()
+                .expect("the buffer isn't writing to a String")
+                .text(),
+        };
+        let mut out = sink::StringSink {
+            out: text,
+            format: self.policies.format.clone(),
+            in_comment: false,
+        };
+        let depth = self.open_depth.saturating_sub(1);
+        let mut attrs = self.attrs.clone();
+        match &self.tag_open {
+            Some(Pending::Element) => {
+                if let Some(Frame::Element(tag)) = self.stack.last() {
+                    self.policies.apply(tag, &mut attrs);
+                    out.start_tag(depth, tag, &attrs);
+                }
+            }
+            Some(Pending::Void(tag)) => {
+                let self_closing = self.xml && !tag.starts_with('!');
+                self.policies.apply(tag, &mut attrs);
+                out.void_tag(depth, tag, &attrs, self_closing);
+            }
+            Some(Pending::Comment) => out.end_comment(depth),
+            None => (),
+        }
+        for (depth, frame) in self.stack.iter().enumerate().rev() {
+            match frame {
+                Frame::Element(tag) => out.end_tag(depth, tag),
+                Frame::Conditional => out.end_conditional(depth),
+            }
+        }
+        out.end();
+        out.out
+    }
+
+    fn into_text(self) -> String {
+        self.try_into_text()
+            .expect("the buffer isn't writing to a String")
+    }
+
+    /// The output, if the sink writes text
+    fn try_into_text(self) -> Option<String> {
+        let wtr = self.into_sink();
+        match wtr.downcast::<sink::SegmentSink>() {
+            Ok(segments) => Some(segments.finish().concat()),
+            Err(wtr) => match wtr.downcast::<sink::StringSink>() {
+                Ok(sink) => Some(sink.out),
+                Err(wtr) => wtr.downcast().ok().map(|text| *text),
+            },
+        }
+    }
+
+    /// A copy of the state, if the sink can be copied
+    fn try_clone(&self) -> Option<Ctx> {
+        Some(Ctx {
+            wtr: sink::try_clone(self.wtr.as_any())?,
+            stack: self.stack.clone(),
+            tag_open: self.tag_open.clone(),
+            open_depth: self.open_depth,
+            attrs: self.attrs.clone(),
+            mid_line: self.mid_line,
+            trailing_space: self.trailing_space,
+            partial_utf8: self.partial_utf8.clone(),
+            xml: self.xml,
+            strict: self.strict,
+            policies: self.policies.clone(),
+            translator: self.translator.clone(),
+            stats: self.stats.clone(),
+            errors: self.errors.clone(),
+            slots: self.slots.clone(),
+            head: self.head.clone(),
+            amp: self.amp.clone(),
+        })
+    }
+
+    /// Writes a comment with the location of the code which opened an
+    /// element
+    #[cfg(feature = "source-locations")]
+    #[track_caller]
+    fn write_location(&mut self, depth: usize) {
+        if !self.policies.source_locations {
+            return;
+        }
+        let location = std::panic::Location::caller();
+        self.wtr.start_comment(depth);
+        self.wtr
+            .raw(&format!("{}:{}", location.file(), location.line()));
+        self.wtr.end_comment(depth);
     }
 
     fn open_comment(&mut self, depth: usize) {
         self.close_deeper_than(depth);
-        write!(self.wtr, "{:>w$}!-- ", "<", w = depth + 1).unwrap();
-        self.tag_open = Some(" -->\n");
+        self.wtr.start_comment(depth);
+        self.tag_open = Some(Pending::Comment);
+        self.open_depth = depth + 1;
     }
 }
 
 impl<'a> Node<'a> {
+    /// Does the work of [`Buffer::finish_and_reset()`] for the root node
+    fn finish_and_reset(&mut self) -> String {
+        let ctx = self.ctx.get();
+        ctx.finish();
+        let text = ctx.text_mut().clone();
+        ctx.reset();
+        self.depth = 0;
+        text
+    }
+
+    #[cfg_attr(feature = "source-locations", track_caller)]
     pub fn child<'b>(&'b mut self, tag: Cow<'static, str>) -> Node<'b> {
-        let ctx = self.ctx.upgrade().unwrap();
-        let mut ctx = ctx.lock().unwrap();
-        ctx.open(&tag, self.depth);
-        ctx.stack.push(tag);
+        self.tag_child(tag.into())
+    }
+
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    pub(crate) fn tag_child<'b>(&'b mut self, tag: Tag) -> Node<'b> {
+        let ctx = self.ctx.get();
+        ctx.open_element(tag, self.depth, false).unwrap();
         Node {
             depth: self.depth + 1,
-            ctx: self.ctx.clone(),
+            ctx: CtxRef::Borrowed(ctx),
             escaping: Escaping::Normal,
-            _phantom: std::marker::PhantomData,
         }
     }
 
+    #[cfg_attr(feature = "source-locations", track_caller)]
     pub fn void_child<'b>(&'b mut self, tag: Cow<'static, str>) -> Void<'b> {
-        let ctx = self.ctx.upgrade().unwrap();
-        let mut ctx = ctx.lock().unwrap();
-        ctx.open(&tag, self.depth);
+        self.void_tag_child(tag.into())
+    }
+
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    pub(crate) fn void_tag_child<'b>(&'b mut self, tag: Tag) -> Void<'b> {
+        let ctx = self.ctx.get();
+        ctx.open_void(tag, self.depth, false).unwrap();
         Void {
-            ctx: self.ctx.clone(),
-            _phantom: std::marker::PhantomData,
+            depth: self.depth + 1,
+            ctx,
         }
     }
 
+    /// Like [`child()`][Node::child], but consumes the node, so the child
+    /// can outlive it
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    pub(crate) fn into_child(self, tag: Cow<'static, str>) -> Node<'a> {
+        let ctx = match self.ctx {
+            CtxRef::Borrowed(ctx) => ctx,
+            CtxRef::Owned(_) => unreachable!("the root node is never moved out of its buffer"),
+        };
+        ctx.open_element(tag.into(), self.depth, false).unwrap();
+        Node {
+            depth: self.depth + 1,
+            ctx: CtxRef::Borrowed(ctx),
+            escaping: Escaping::Normal,
+        }
+    }
+
+    /// Like [`child()`][Node::child], but fails if the element is invalid
+    ///
+    /// In [strict mode][Buffer::strict], an element which fails validation
+    /// is not written, and the problems are returned instead of recorded.
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    pub fn try_child<'b>(&'b mut self, tag: Cow<'static, str>) -> Result<Node<'b>, Error> {
+        let ctx = self.ctx.get();
+        ctx.open_element(tag.into(), self.depth, true)?;
+        Ok(Node {
+            depth: self.depth + 1,
+            ctx: CtxRef::Borrowed(ctx),
+            escaping: Escaping::Normal,
+        })
+    }
+
+    /// Like [`void_child()`][Node::void_child], but fails if the element is
+    /// invalid
+    ///
+    /// In [strict mode][Buffer::strict], an element which fails validation
+    /// is not written, and the problems are returned instead of recorded.
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    pub fn try_void_child<'b>(&'b mut self, tag: Cow<'static, str>) -> Result<Void<'b>, Error> {
+        let ctx = self.ctx.get();
+        ctx.open_void(tag.into(), self.depth, true)?;
+        Ok(Void {
+            depth: self.depth + 1,
+            ctx,
+        })
+    }
+
+    /// Create a child element with the given attributes
+    ///
+    /// This is the same as calling [`child()`][Node::child] followed by
+    /// [`attr_value()`][GlobalAttributes::attr_value] for each attribute.
+    /// The values are escaped.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new();
+    /// let url = "/a?b&c";
+    /// buf.element("a".into(), [("href", url), ("class", "link")]).text("Link");
+    /// assert_eq!(buf.finish(), "\
+    /// <a href=\"/a?b&amp;c\" class=\"link\">
+    /// Link
+    /// </a>
+    /// ");
+    /// ```
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    pub fn element<'b, N: AsRef<str>, V: std::fmt::Display>(
+        &'b mut self,
+        tag: Cow<'static, str>,
+        attrs: impl IntoIterator<Item = (N, V)>,
+    ) -> Node<'b> {
+        attrs
+            .into_iter()
+            .fold(self.child(tag), |node, (name, value)| {
+                node.attr_value(name.as_ref(), value)
+            })
+    }
+
+    /// Create a void child element with the given attributes
+    ///
+    /// See [`element()`][Node::element].
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    pub fn void_element<'b, N: AsRef<str>, V: std::fmt::Display>(
+        &'b mut self,
+        tag: Cow<'static, str>,
+        attrs: impl IntoIterator<Item = (N, V)>,
+    ) -> Void<'b> {
+        attrs
+            .into_iter()
+            .fold(self.void_child(tag), |node, (name, value)| {
+                node.attr_value(name.as_ref(), value)
+            })
+    }
+
     pub fn comment<'b>(&'b mut self) -> Comment<'b> {
-        let ctx = self.ctx.upgrade().unwrap();
-        let mut ctx = ctx.lock().unwrap();
+        let ctx = self.ctx.get();
         ctx.open_comment(self.depth);
-        Comment {
-            ctx: self.ctx.clone(),
-            _phantom: std::marker::PhantomData,
+        Comment { ctx }
+    }
+
+    /// Create a conditional comment
+    ///
+    /// Children of the returned node are wrapped in
+    /// `<!--[if cond]>` ... `<![endif]-->`, so they're only rendered by
+    /// clients which understand the condition (eg. `"if mso"` for Outlook).
+    /// The condition is written verbatim.
+    pub fn conditional_comment<'b>(&'b mut self, cond: &str) -> Node<'b> {
+        let ctx = self.ctx.get();
+        ctx.open_conditional(cond, self.depth);
+        Node {
+            depth: self.depth + 1,
+            ctx: CtxRef::Borrowed(ctx),
+            escaping: Escaping::Normal,
         }
     }
 
-    pub fn attr(self, attr: &str) -> Node<'a> {
-        let ctx = self.ctx.upgrade().unwrap();
-        let mut ctx = ctx.lock().unwrap();
-        if ctx.tag_open.is_some() {
-            write!(ctx.wtr, " {}", attr).unwrap();
+    /// Send the output written so far on to its destination
+    ///
+    /// This only makes a difference for buffers which stream their output,
+    /// such as those created by [`Buffer::to_writer()`].  The node's
+    /// children are closed, and any attributes of the node must be set
+    /// before flushing.
+    pub fn flush(&mut self) {
+        let ctx = self.ctx.get();
+        ctx.close_deeper_than(self.depth);
+        ctx.wtr.flush();
+    }
+
+    /// Reserve a position to be filled in later
+    ///
+    /// This is useful for content which depends on things you only know
+    /// once the rest of the document has been written, such as a page title
+    /// or a total count.  Write the content with [`Buffer::fill()`].
+    ///
+    /// ```
+    /// # use html_builder::*;
+    /// # use std::fmt::Write;
+    /// let mut buf = Buffer::new();
+    /// let mut html = buf.html();
+    /// let title = html.head().placeholder();
+    /// let mut body = html.body();
+    /// let mut list = body.ul();
+    /// let mut count = 0;
+    /// for item in ["apples", "pears"] {
+    ///     writeln!(list.li(), "{}", item)?;
+    ///     count += 1;
+    /// }
+    /// buf.fill(title, |head| {
+    ///     writeln!(head.title(), "{} items", count).unwrap();
+    /// });
+    /// assert_eq!(buf.finish(), "\
+    /// <html>
+    ///  <head>
+    ///   <title>
+    /// 2 items
+    ///   </title>
+    ///  </head>
+    ///  <body>
+    ///   <ul>
+    ///    <li>
+    /// apples
+    ///    </li>
+    ///    <li>
+    /// pears
+    ///    </li>
+    ///   </ul>
+    ///  </body>
+    /// </html>
+    /// ");
+    /// # Ok::<(), std::fmt::Error>(())
+    /// ```
+    pub fn placeholder(&mut self) -> SlotId {
+        let ctx = self.ctx.get();
+        ctx.close_deeper_than(self.depth);
+        let slot = Slot {
+            offset: ctx.text_mut().len(),
+            depth: self.depth,
+            stack: ctx.stack.clone(),
+        };
+        ctx.slots.push(slot);
+        SlotId(ctx.slots.len() - 1)
+    }
+
+    /// Makes an independent copy of the buffer, which carries on inside
+    /// this node
+    ///
+    /// Everything written so far, and everything still open, is copied.
+    /// This is for rendering variants of a document which share the same
+    /// beginning, without building it twice.  Whatever's written into the
+    /// copy goes inside this node; when the copy is finished, the elements
+    /// which are still open are closed, as usual.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new();
+    /// let mut body = buf.body();
+    /// body.h1().text("Checkout");
+    /// let mut failure = body.fork();
+    /// body.p().class("ok").text("Thanks for your order!");
+    /// failure.p().class("error").text("Payment failed");
+    ///
+    /// assert_eq!(buf.finish(), "\
+    /// <body>
+    ///  <h1>
+    /// Checkout
+    ///  </h1>
+    ///  <p class=\"ok\">
+    /// Thanks for your order!
+    ///  </p>
+    /// </body>
+    /// ");
+    /// assert_eq!(failure.finish(), "\
+    /// <body>
+    ///  <h1>
+    /// Checkout
+    ///  </h1>
+    ///  <p class=\"error\">
+    /// Payment failed
+    ///  </p>
+    /// </body>
+    /// ");
+    /// ```
+    ///
+    /// Panics if the buffer was created with a [custom
+    /// sink][Buffer::with_sink], writes to an `io::Write`, or has
+    /// [observers][Buffer::observe].
+    pub fn fork(&self) -> Buffer {
+        let ctx = self
+            .ctx
+            .get_ref()
+            .try_clone()
+            .expect("this kind of buffer can't be forked");
+        Buffer {
+            node: Node {
+                depth: self.depth,
+                ctx: CtxRef::Owned(Box::new(ctx)),
+                escaping: Escaping::Normal,
+            },
+        }
+    }
+
+    /// Finishes another buffer and writes its contents into this node
+    ///
+    /// This is for combining documents which were built separately.  The
+    /// other buffer's tags are indented to this node's depth.  Its text is
+    /// written as it is, which is already where the builder puts text
+    /// (unless it was written with different [formatting options][Format]).
+    /// If it was in [strict mode][Buffer::strict], the problems it recorded
+    /// are added to this buffer's.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut sidebar = Buffer::new();
+    /// sidebar.aside().ul().li().text("Related");
+    ///
+    /// let mut buf = Buffer::new();
+    /// let mut body = buf.body();
+    /// body.main().p().text("Content");
+    /// body.append_buffer(sidebar);
+    /// assert_eq!(buf.finish(), "\
+    /// <body>
+    ///  <main>
+    ///   <p>
+    /// Content
+    ///   </p>
+    ///  </main>
+    ///  <aside>
+    ///   <ul>
+    ///    <li>
+    /// Related
+    ///    </li>
+    ///   </ul>
+    ///  </aside>
+    /// </body>
+    /// ");
+    /// ```
+    ///
+    /// Panics if the other buffer was created with a [custom
+    /// sink][Buffer::with_sink].
+    pub fn append_buffer(&mut self, buf: Buffer) {
+        let mut other = buf.into_ctx();
+        other.finish();
+        let ctx = self.ctx.get();
+        ctx.close_deeper_than(self.depth);
+        ctx.errors.append(&mut other.errors);
+        ctx.stats.merge(&other.stats, self.depth);
+        let text = other.into_text();
+        if ctx.policies.format.minify {
+            ctx.wtr.raw(&text);
+        } else {
+            ctx.wtr.raw(&format::shift(&text, self.depth));
         }
+        ctx.mid_line = !text.is_empty() && !text.ends_with('\n');
+        ctx.trailing_space = false;
+    }
+
+    /// Add an attribute to the element
+    ///
+    /// The attribute is written verbatim, so values must be escaped; the
+    /// [`attrs!`] macro takes care of this.  Attributes can only be added
+    /// before any children or text have been added to the element; doing so
+    /// afterwards panics in debug builds (or is recorded as an error in
+    /// [strict mode][Buffer::strict]).
+    pub fn attr(mut self, attr: &str) -> Node<'a> {
+        self.ctx.get().attr(self.depth, attr);
         self
     }
 
+    /// Set an attribute without consuming the node
+    ///
+    /// This is useful for helper functions which are passed a `&mut Node`.
+    /// Like [`GlobalAttributes::attr_value()`], it replaces any previous
+    /// value of the attribute, except for `class`, where the new classes are
+    /// appended to the existing ones.  It must be called before any children
+    /// or text are added to the node.
+    pub fn set_attr(&mut self, name: &str, value: impl std::fmt::Display) {
+        let ctx = self.ctx.get();
+        ctx.attr_value(self.depth, name, &value.to_string());
+    }
+
     /// Disable escaping
     ///
     /// In this mode, written text is passed through unmodified.
@@ -303,47 +1501,182 @@ impl<'a> Node<'a> {
         self.escaping = Escaping::Safe;
         self
     }
+
+    /// Write a value into the node, followed by a newline
+    ///
+    /// The text is escaped according to the node's escaping mode, just like
+    /// text written with `write!()`.  This saves importing
+    /// [`std::fmt::Write`] for the common case.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new();
+    /// buf.p().text("Fish & chips:").text(4.5);
+    /// assert_eq!(buf.finish(), "<p>\nFish &amp; chips:\n4.5\n</p>\n");
+    /// ```
+    pub fn text(&mut self, text: impl std::fmt::Display) -> &mut Self {
+        // Writing to a node never fails
+        writeln!(self, "{}", text).unwrap();
+        self
+    }
+
+    /// Writes text without escaping it, regardless of the escaping mode
+    fn write_raw(&mut self, s: &str) -> std::fmt::Result {
+        let escaping = std::mem::replace(&mut self.escaping, Escaping::Raw);
+        let ret = self.write_str(s);
+        self.escaping = escaping;
+        ret
+    }
+
+    /// Writes markup without escaping it, re-indented as with
+    /// [`Format::indent_raw()`], and ends the line
+    #[cfg(feature = "macros")]
+    fn write_reindented(&mut self, html: &str) -> std::fmt::Result {
+        let format = &mut self.ctx.get().policies.format;
+        let indent_raw = std::mem::replace(&mut format.indent_raw, true);
+        let mut ret = self.write_raw(html);
+        if ret.is_ok() && !html.ends_with('\n') {
+            ret = self.write_raw("\n");
+        }
+        self.ctx.get().policies.format.indent_raw = indent_raw;
+        ret
+    }
 }
 
 impl<'a> Write for Node<'a> {
     fn write_str(&mut self, s: &str) -> std::fmt::Result {
-        let mutex = self.ctx.upgrade().unwrap();
-        let mut ctx = mutex.lock().unwrap();
+        let ctx = self.ctx.get();
+        // If anything is written before the text, it ends with a newline
+        let tag_between = ctx.tag_open.is_some() || ctx.stack.len() > self.depth;
+        let line_start = !ctx.mid_line || tag_between;
+        let after_space = ctx.trailing_space && !tag_between;
         ctx.close_deeper_than(self.depth);
+        if s.is_empty() {
+            return Ok(());
+        }
+        ctx.mid_line = !s.ends_with('\n');
+        let reformatted;
         let s = match self.escaping {
-            Escaping::Raw => s.into(),
-            Escaping::Normal => html_escape::encode_text(s),
-            Escaping::Safe => html_escape::encode_safe(s),
+            Escaping::Normal | Escaping::Safe if ctx.policies.format.minify && !ctx.verbatim() => {
+                reformatted = Cow::Owned(format::collapse(s, after_space));
+                &reformatted
+            }
+            Escaping::Normal | Escaping::Safe
+                if ctx.policies.format.indent_text && !ctx.preformatted() =>
+            {
+                reformatted = format::indent(s, self.depth, line_start);
+                &reformatted
+            }
+            Escaping::Raw
+                if ctx.policies.format.indent_raw
+                    && !ctx.policies.format.minify
+                    && !ctx.preformatted()
+                    && !format::has_preformatted(s) =>
+            {
+                reformatted = format::reindent(s, self.depth, line_start);
+                &reformatted
+            }
+            _ => s,
+        };
+        ctx.trailing_space = s.ends_with(|c: char| c.is_ascii_whitespace());
+        match self.escaping {
+            Escaping::Raw => ctx.wtr.raw(s),
+            Escaping::Normal if ctx.policies.encoding == Encoding::Minimal => ctx.wtr.text(s),
+            Escaping::Normal | Escaping::Safe => {
+                let mut escaper = escape::Escaper {
+                    wtr: &mut *ctx.wtr,
+                    safe: matches!(self.escaping, Escaping::Safe),
+                    encoding: ctx.policies.encoding,
+                };
+                escaper.write_str(s)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes bytes into a node, for code which only knows how to write to an
+/// `io::Write`.
+///
+/// The bytes must be UTF-8, but a code point can be split across calls to
+/// `write()`; its first bytes are held back until the rest arrive.  They're
+/// escaped in the same way as text written with `fmt::Write`.
+///
+/// ```
+/// use html_builder::*;
+/// use std::io::Write;
+///
+/// let mut buf = Buffer::new();
+/// let mut pre = buf.pre();
+/// pre.write_all(b"a < b \xE2\x9C")?;
+/// pre.write_all(b"\x93\n")?;
+/// assert_eq!(buf.finish(), "<pre>\na &lt; b \u{2713}\n</pre>\n");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// Writing bytes which aren't UTF-8 is an `InvalidData` error, and so is
+/// flushing while a code point is incomplete.
+impl<'a> std::io::Write for Node<'a> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut bytes = std::mem::take(&mut self.ctx.get().partial_utf8);
+        let held = bytes.len();
+        bytes.extend_from_slice(buf);
+        let (valid, rest) = match std::str::from_utf8(&bytes) {
+            Ok(s) => (s, &[][..]),
+            Err(e) => {
+                let (valid, rest) = bytes.split_at(e.valid_up_to());
+                let valid = std::str::from_utf8(valid).unwrap();
+                if e.error_len().is_some() {
+                    if valid.len() <= held {
+                        return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, e));
+                    }
+                    // Write the valid part now, and report the error when
+                    // the caller tries to write the rest
+                    (valid, &[][..])
+                } else {
+                    (valid, rest)
+                }
+            }
         };
-        ctx.wtr.write_str(&s)
+        // Writing to a node never fails
+        self.write_str(valid).unwrap();
+        let written = valid.len() + rest.len() - held;
+        self.ctx.get().partial_utf8 = rest.to_vec();
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        if !self.ctx.get().partial_utf8.is_empty() {
+            self.ctx.get().partial_utf8.clear();
+            let msg = "incomplete UTF-8 code point";
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, msg));
+        }
+        Node::flush(self);
+        Ok(())
     }
 }
 
 impl<'a> Void<'a> {
     pub fn attr(self, attr: &str) -> Void<'a> {
-        let ctx = self.ctx.upgrade().unwrap();
-        let mut ctx = ctx.lock().unwrap();
-        if ctx.tag_open.is_some() {
-            write!(ctx.wtr, " {}", attr).unwrap();
-        }
+        self.ctx.attr(self.depth, attr);
         self
     }
 }
 
 impl<'a> Write for Comment<'a> {
     fn write_char(&mut self, c: char) -> std::fmt::Result {
-        let mutex = self.ctx.upgrade().unwrap();
-        let mut ctx = mutex.lock().unwrap();
-        ctx.wtr.write_char(c)
+        let ctx = &mut *self.ctx;
+        ctx.wtr.raw(c.encode_utf8(&mut [0; 4]));
+        Ok(())
     }
     fn write_fmt(&mut self, args: std::fmt::Arguments<'_>) -> std::fmt::Result {
-        let mutex = self.ctx.upgrade().unwrap();
-        let mut ctx = mutex.lock().unwrap();
-        ctx.wtr.write_fmt(args)
+        let ctx = &mut *self.ctx;
+        sink::RawWriter(&mut *ctx.wtr).write_fmt(args)
     }
     fn write_str(&mut self, s: &str) -> std::fmt::Result {
-        let mutex = self.ctx.upgrade().unwrap();
-        let mut ctx = mutex.lock().unwrap();
-        ctx.wtr.write_str(s)
+        let ctx = &mut *self.ctx;
+        ctx.wtr.raw(s);
+        Ok(())
     }
 }
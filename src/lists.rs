@@ -0,0 +1,116 @@
+use crate::{GlobalAttributes, Html5, Node};
+use std::fmt::Display;
+
+impl<'a> Node<'a> {
+    /// Writes a `<dl>` with a `<dt>` and `<dd>` for each `(term,
+    /// description)` pair
+    ///
+    /// A term with several descriptions can be given as consecutive pairs
+    /// with the same term: the term is only written once, followed by a
+    /// `<dd>` for each description.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new();
+    /// buf.dl_from(vec![
+    ///     ("Name", "Alice"),
+    ///     ("Email", "alice@example.com"),
+    ///     ("Email", "alice@work.example"),
+    /// ]);
+    /// assert_eq!(buf.finish(), "\
+    /// <dl>
+    ///  <dt>
+    /// Name
+    ///  </dt>
+    ///  <dd>
+    /// Alice
+    ///  </dd>
+    ///  <dt>
+    /// Email
+    ///  </dt>
+    ///  <dd>
+    /// alice@example.com
+    ///  </dd>
+    ///  <dd>
+    /// alice@work.example
+    ///  </dd>
+    /// </dl>
+    /// ");
+    /// ```
+    pub fn dl_from<T, D>(&mut self, items: impl IntoIterator<Item = (T, D)>) -> &mut Self
+    where
+        T: Display,
+        D: Display,
+    {
+        let mut dl = self.dl();
+        let mut prev_term = None;
+        for (term, description) in items {
+            let term = term.to_string();
+            if prev_term.as_ref() != Some(&term) {
+                dl.dt_text(&term);
+                prev_term = Some(term);
+            }
+            dl.dd_text(description);
+        }
+        self
+    }
+}
+
+impl<'a> Node<'a> {
+    /// Writes a `<details>` element for each `(summary, contents)` item,
+    /// as used for accordions and FAQs
+    ///
+    /// Each item's `contents` closure writes the body of its `<details>`,
+    /// after the `<summary>`.  If `name` is given, it's set on all the
+    /// elements, so that browsers only allow one of them to be open at a
+    /// time.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let faq: Vec<(&str, fn(&mut Node))> = vec![
+    ///     ("Is it free?", |node| { node.p().text("Yes."); }),
+    ///     ("Is it fast?", |node| { node.p().text("Very."); }),
+    /// ];
+    /// let mut buf = Buffer::new();
+    /// buf.details_group(Some("faq"), faq);
+    /// assert_eq!(buf.finish(), "\
+    /// <details name=\"faq\">
+    ///  <summary>
+    /// Is it free?
+    ///  </summary>
+    ///  <p>
+    /// Yes.
+    ///  </p>
+    /// </details>
+    /// <details name=\"faq\">
+    ///  <summary>
+    /// Is it fast?
+    ///  </summary>
+    ///  <p>
+    /// Very.
+    ///  </p>
+    /// </details>
+    /// ");
+    /// ```
+    pub fn details_group<S, F>(
+        &mut self,
+        name: Option<&str>,
+        items: impl IntoIterator<Item = (S, F)>,
+    ) -> &mut Self
+    where
+        S: Display,
+        F: FnOnce(&mut Node),
+    {
+        for (summary, contents) in items {
+            let mut details = self.details();
+            if let Some(name) = name {
+                details = details.attr_value("name", name);
+            }
+            details.summary().text(summary);
+            contents(&mut details);
+        }
+        self
+    }
+}
@@ -0,0 +1,131 @@
+/// Builds a string of attributes for [`attr()`][crate::Node::attr],
+/// escaping the values.
+///
+/// The macro evaluates to a `&String`, so it can be passed straight to
+/// `attr()`.
+///
+/// Each attribute is one of:
+///
+/// * `name = value`, where `value` is anything which implements `Display`
+/// * `name ?= value`, where `value` is an `Option`; the attribute is left
+///   out if it's `None`
+/// * `name if cond`, a boolean attribute which is only set if `cond` is true
+/// * `name`, a boolean attribute
+///
+/// Attribute names may contain hyphens.
+///
+/// ```
+/// use html_builder::*;
+///
+/// let url = "/search?q=fish&chips";
+/// let title: Option<&str> = None;
+/// let is_disabled = true;
+/// let attrs = attrs!(href = url, class = "btn", title ?= title, aria-disabled = is_disabled, disabled if is_disabled);
+/// assert_eq!(attrs, r#"href="/search?q=fish&amp;chips" class="btn" aria-disabled="true" disabled"#);
+///
+/// let mut buf = Buffer::new();
+/// buf.a().attr(attrs);
+/// ```
+#[macro_export]
+macro_rules! attrs {
+    ($($rest:tt)*) => {
+        &{
+            let mut out = ::std::string::String::new();
+            $crate::__attrs!(out; $($rest)*);
+            out
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __attrs {
+    ($out:ident;) => {};
+    ($out:ident; $first:ident $(- $more:ident)* = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__private::push_attr(
+            &mut $out,
+            ::std::concat!(::std::stringify!($first) $(, "-", ::std::stringify!($more))*),
+            &$value,
+        );
+        $crate::__attrs!($out; $($($rest)*)?);
+    };
+    ($out:ident; $first:ident $(- $more:ident)* ?= $value:expr $(, $($rest:tt)*)?) => {
+        if let ::std::option::Option::Some(value) = &$value {
+            $crate::__private::push_attr(
+                &mut $out,
+                ::std::concat!(::std::stringify!($first) $(, "-", ::std::stringify!($more))*),
+                value,
+            );
+        }
+        $crate::__attrs!($out; $($($rest)*)?);
+    };
+    ($out:ident; $first:ident $(- $more:ident)* if $cond:expr $(, $($rest:tt)*)?) => {
+        if $cond {
+            $crate::__private::push_flag(
+                &mut $out,
+                ::std::concat!(::std::stringify!($first) $(, "-", ::std::stringify!($more))*),
+            );
+        }
+        $crate::__attrs!($out; $($($rest)*)?);
+    };
+    ($out:ident; $first:ident $(- $more:ident)* $(, $($rest:tt)*)?) => {
+        $crate::__private::push_flag(
+            &mut $out,
+            ::std::concat!(::std::stringify!($first) $(, "-", ::std::stringify!($more))*),
+        );
+        $crate::__attrs!($out; $($($rest)*)?);
+    };
+}
+
+/// Used by the macros
+#[doc(hidden)]
+pub mod __private {
+    use crate::{GlobalAttributes, Html5, Node};
+    use std::fmt::Display;
+
+    #[cfg(feature = "test-util")]
+    pub use crate::test_util::assert_html_eq;
+
+    pub fn push_attr(out: &mut String, name: &str, value: &dyn Display) {
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        out.push_str(name);
+        out.push_str("=\"");
+        crate::escape::escape_attr(out, &value.to_string());
+        out.push('"');
+    }
+
+    pub fn push_flag(out: &mut String, name: &str) {
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        out.push_str(name);
+    }
+
+    /// Writes a `<div>` with a label, and returns it
+    pub fn field<'a>(form: &'a mut Node, name: &str, label: &str) -> Node<'a> {
+        let mut div = form.div();
+        div.label().for_(name).text(label);
+        div
+    }
+
+    pub fn input(parent: &mut Node, name: &str, type_: &str, value: &dyn Display) {
+        parent.input().type_(type_).name(name).id(name).value(value);
+    }
+
+    pub fn textarea(parent: &mut Node, name: &str, value: &dyn Display) {
+        parent.textarea().name(name).id(name).text(value);
+    }
+
+    #[cfg(feature = "macros")]
+    pub fn include(node: &mut Node, html: &str) -> std::fmt::Result {
+        node.write_reindented(html)
+    }
+
+    pub fn select(parent: &mut Node, name: &str, value: &dyn Display, options: &[&str]) {
+        let value = value.to_string();
+        let options = options.iter().map(|option| (option, option));
+        parent.select_from(name, options, Some(&value));
+    }
+}
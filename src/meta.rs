@@ -0,0 +1,396 @@
+//! Helpers for the boilerplate in a document's `<head>`
+
+use crate::{validate, GlobalAttributes, Html5, Node};
+use std::fmt::Display;
+
+impl<'a> Node<'a> {
+    /// Writes `<meta charset="utf-8">`
+    ///
+    /// This should be the first thing in the `<head>`.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new();
+    /// buf.head()
+    ///     .meta_charset_utf8()
+    ///     .meta_viewport_default()
+    ///     .meta_description("Fish & chips, delivered")
+    ///     .meta_robots("noindex");
+    /// assert_eq!(buf.finish(), "\
+    /// <head>
+    ///  <meta charset=\"utf-8\">
+    ///  <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">
+    ///  <meta name=\"description\" content=\"Fish &amp; chips, delivered\">
+    ///  <meta name=\"robots\" content=\"noindex\">
+    /// </head>
+    /// ");
+    /// ```
+    pub fn meta_charset_utf8(&mut self) -> &mut Self {
+        self.meta().charset("utf-8");
+        self
+    }
+
+    /// Writes a `<meta name="viewport">` which fits the page to the width
+    /// of the device
+    pub fn meta_viewport_default(&mut self) -> &mut Self {
+        self.meta_name("viewport", "width=device-width, initial-scale=1")
+    }
+
+    /// Writes a `<meta name="robots">` with the given directives, eg.
+    /// `"noindex, nofollow"`
+    pub fn meta_robots(&mut self, directives: impl Display) -> &mut Self {
+        self.meta_name("robots", directives)
+    }
+
+    /// Writes a `<meta name="description">`, which search engines may show
+    /// in their results
+    pub fn meta_description(&mut self, description: impl Display) -> &mut Self {
+        self.meta_name("description", description)
+    }
+
+    /// Writes a `<link rel="manifest">` pointing to the web app manifest
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new();
+    /// buf.head()
+    ///     .manifest("/app.webmanifest")
+    ///     .theme_color("#ffffff", "#101010");
+    /// assert_eq!(buf.finish(), "\
+    /// <head>
+    ///  <link rel=\"manifest\" href=\"/app.webmanifest\">
+    ///  <meta name=\"theme-color\" content=\"#ffffff\" media=\"(prefers-color-scheme: light)\">
+    ///  <meta name=\"theme-color\" content=\"#101010\" media=\"(prefers-color-scheme: dark)\">
+    /// </head>
+    /// ");
+    /// ```
+    pub fn manifest(&mut self, href: impl Display) -> &mut Self {
+        self.link().rel("manifest").href(href);
+        self
+    }
+
+    /// Writes `<meta name="theme-color">` tags for the light and dark
+    /// colour schemes
+    ///
+    /// Browsers use the theme colour for the surrounding UI, such as the
+    /// address bar.  See [`manifest()`][Node::manifest] for an example.
+    pub fn theme_color(&mut self, light: impl Display, dark: impl Display) -> &mut Self {
+        self.meta()
+            .name("theme-color")
+            .content(light)
+            .media("(prefers-color-scheme: light)");
+        self.meta()
+            .name("theme-color")
+            .content(dark)
+            .media("(prefers-color-scheme: dark)");
+        self
+    }
+
+    /// Writes a `<meta name="..." content="...">`
+    pub fn meta_name(&mut self, name: &str, content: impl Display) -> &mut Self {
+        self.meta().name(name).content(content);
+        self
+    }
+}
+
+/// The metadata used when a page is shared on social media.
+///
+/// The same data is written as Open Graph tags with
+/// [`Node::open_graph()`], and as Twitter/X card tags with
+/// [`Node::twitter_card()`], so it only has to be specified once.
+///
+/// ```
+/// use html_builder::*;
+///
+/// let card = OpenGraph::new("Fish & chips")
+///     .description("Delivered hot")
+///     .image("https://example.com/chips.jpg")
+///     .url("https://example.com/chips")
+///     .twitter_site("@chippy");
+/// let mut buf = Buffer::new();
+/// buf.head().open_graph(&card).twitter_card(&card);
+/// assert_eq!(buf.finish(), "\
+/// <head>
+///  <meta property=\"og:type\" content=\"website\">
+///  <meta property=\"og:title\" content=\"Fish &amp; chips\">
+///  <meta property=\"og:description\" content=\"Delivered hot\">
+///  <meta property=\"og:image\" content=\"https://example.com/chips.jpg\">
+///  <meta property=\"og:url\" content=\"https://example.com/chips\">
+///  <meta name=\"twitter:card\" content=\"summary_large_image\">
+///  <meta name=\"twitter:title\" content=\"Fish &amp; chips\">
+///  <meta name=\"twitter:description\" content=\"Delivered hot\">
+///  <meta name=\"twitter:image\" content=\"https://example.com/chips.jpg\">
+///  <meta name=\"twitter:site\" content=\"@chippy\">
+/// </head>
+/// ");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenGraph {
+    type_: String,
+    title: String,
+    description: Option<String>,
+    image: Option<String>,
+    image_alt: Option<String>,
+    url: Option<String>,
+    site_name: Option<String>,
+    twitter_site: Option<String>,
+}
+
+impl OpenGraph {
+    /// Metadata for a page with the given title, of type `website`
+    pub fn new(title: impl Display) -> OpenGraph {
+        OpenGraph {
+            type_: "website".into(),
+            title: title.to_string(),
+            description: None,
+            image: None,
+            image_alt: None,
+            url: None,
+            site_name: None,
+            twitter_site: None,
+        }
+    }
+
+    /// Sets the type of the page, eg. `article`
+    pub fn type_(mut self, type_: impl Display) -> OpenGraph {
+        self.type_ = type_.to_string();
+        self
+    }
+
+    /// Sets a one or two sentence description of the page
+    pub fn description(mut self, description: impl Display) -> OpenGraph {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /// Sets the URL of an image which represents the page
+    pub fn image(mut self, url: impl Display) -> OpenGraph {
+        self.image = Some(url.to_string());
+        self
+    }
+
+    /// Sets the alternative text for the image
+    pub fn image_alt(mut self, alt: impl Display) -> OpenGraph {
+        self.image_alt = Some(alt.to_string());
+        self
+    }
+
+    /// Sets the canonical URL of the page
+    pub fn url(mut self, url: impl Display) -> OpenGraph {
+        self.url = Some(url.to_string());
+        self
+    }
+
+    /// Sets the name of the site which the page is part of
+    pub fn site_name(mut self, name: impl Display) -> OpenGraph {
+        self.site_name = Some(name.to_string());
+        self
+    }
+
+    /// Sets the Twitter/X handle of the site, eg. `@example`
+    ///
+    /// This is only used for the Twitter/X card.
+    pub fn twitter_site(mut self, handle: impl Display) -> OpenGraph {
+        self.twitter_site = Some(handle.to_string());
+        self
+    }
+}
+
+impl<'a> Node<'a> {
+    /// Writes a `<meta property="..." content="...">`, as used by Open
+    /// Graph
+    pub fn meta_property(&mut self, property: &str, content: impl Display) -> &mut Self {
+        self.meta().property(property).content(content);
+        self
+    }
+
+    /// Writes the Open Graph `<meta>` tags for the page
+    ///
+    /// See [`OpenGraph`].
+    pub fn open_graph(&mut self, og: &OpenGraph) -> &mut Self {
+        self.meta_property("og:type", &og.type_);
+        self.meta_property("og:title", &og.title);
+        let optional = [
+            ("og:description", &og.description),
+            ("og:image", &og.image),
+            ("og:image:alt", &og.image_alt),
+            ("og:url", &og.url),
+            ("og:site_name", &og.site_name),
+        ];
+        for (property, content) in optional.iter() {
+            if let Some(content) = content {
+                self.meta_property(property, content);
+            }
+        }
+        self
+    }
+
+    /// Writes the Twitter/X card `<meta>` tags for the page
+    ///
+    /// The card is a `summary_large_image` if there's an image, or a
+    /// `summary` otherwise.  See [`OpenGraph`].
+    pub fn twitter_card(&mut self, og: &OpenGraph) -> &mut Self {
+        let card = if og.image.is_some() {
+            "summary_large_image"
+        } else {
+            "summary"
+        };
+        self.meta_name("twitter:card", card);
+        self.meta_name("twitter:title", &og.title);
+        let optional = [
+            ("twitter:description", &og.description),
+            ("twitter:image", &og.image),
+            ("twitter:image:alt", &og.image_alt),
+            ("twitter:site", &og.twitter_site),
+        ];
+        for (name, content) in optional.iter() {
+            if let Some(content) = content {
+                self.meta_name(name, content);
+            }
+        }
+        self
+    }
+}
+
+impl<'a> Node<'a> {
+    /// Writes a `<link rel="canonical">` with the preferred URL of the page
+    pub fn canonical(&mut self, url: impl Display) -> &mut Self {
+        self.link().rel("canonical").href(url);
+        self
+    }
+
+    /// Writes a `<link rel="alternate" hreflang="...">` for each
+    /// translation of the page
+    ///
+    /// `alternates` maps language tags, eg. `en` or `pt-BR`, to the URL of
+    /// the page in that language; the special value `x-default` marks the
+    /// page to use when no language matches.  The list should include the
+    /// current page.  In [strict mode][crate::Buffer::strict], language
+    /// tags which aren't well-formed according to BCP 47 are recorded as
+    /// errors.
+    ///
+    /// ```
+    /// use html_builder::*;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut alternates = BTreeMap::new();
+    /// alternates.insert("en", "https://example.com/en/");
+    /// alternates.insert("fr", "https://example.com/fr/");
+    /// alternates.insert("x-default", "https://example.com/");
+    ///
+    /// let mut buf = Buffer::new().strict();
+    /// buf.head()
+    ///     .canonical("https://example.com/en/")
+    ///     .alternate_languages(&alternates);
+    /// assert!(buf.validation_errors().is_empty());
+    /// assert_eq!(buf.finish(), "\
+    /// <head>
+    ///  <link rel=\"canonical\" href=\"https://example.com/en/\">
+    ///  <link rel=\"alternate\" hreflang=\"en\" href=\"https://example.com/en/\">
+    ///  <link rel=\"alternate\" hreflang=\"fr\" href=\"https://example.com/fr/\">
+    ///  <link rel=\"alternate\" hreflang=\"x-default\" href=\"https://example.com/\">
+    /// </head>
+    /// ");
+    /// ```
+    pub fn alternate_languages<L, U>(
+        &mut self,
+        alternates: impl IntoIterator<Item = (L, U)>,
+    ) -> &mut Self
+    where
+        L: AsRef<str>,
+        U: Display,
+    {
+        for (lang, url) in alternates {
+            let lang = lang.as_ref();
+            let ctx = self.ctx.get();
+            if ctx.strict && lang != "x-default" {
+                if let Err(e) = validate::check_language_tag(lang) {
+                    ctx.errors.push(e);
+                }
+            }
+            self.link().rel("alternate").hreflang(lang).href(url);
+        }
+        self
+    }
+}
+
+/// The favicon and app icons for a site.
+///
+/// Write the `<link>` tags with [`Node::icons()`].  The icon files are
+/// expected to be in a single directory, named as follows:
+///
+/// * `favicon.ico`, for old browsers
+/// * `icon-{size}.png` for each size, eg. `icon-32.png`
+/// * `apple-touch-icon.png`, 180×180, for iOS home screens
+/// * `mask-icon.svg`, a monochrome icon for Safari's pinned tabs
+///
+/// ```
+/// use html_builder::*;
+///
+/// let icons = Icons::new("/static/icons", &[32, 192]).mask_color("#5bbad5");
+/// let mut buf = Buffer::new();
+/// buf.head().icons(&icons);
+/// assert_eq!(buf.finish(), "\
+/// <head>
+///  <link rel=\"icon\" href=\"/static/icons/favicon.ico\" sizes=\"any\">
+///  <link rel=\"icon\" href=\"/static/icons/icon-32.png\" type=\"image/png\" sizes=\"32x32\">
+///  <link rel=\"icon\" href=\"/static/icons/icon-192.png\" type=\"image/png\" sizes=\"192x192\">
+///  <link rel=\"apple-touch-icon\" href=\"/static/icons/apple-touch-icon.png\">
+///  <link rel=\"mask-icon\" href=\"/static/icons/mask-icon.svg\" color=\"#5bbad5\">
+/// </head>
+/// ");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Icons {
+    base: String,
+    sizes: Vec<u32>,
+    mask_color: String,
+}
+
+impl Icons {
+    /// Icons in the directory `base`, with PNGs in the given sizes
+    pub fn new(base: impl Display, sizes: &[u32]) -> Icons {
+        let base = base.to_string();
+        Icons {
+            base: base.trim_end_matches('/').to_string(),
+            sizes: sizes.to_vec(),
+            mask_color: "#000000".into(),
+        }
+    }
+
+    /// Sets the colour of Safari's pinned tab icon (the default is black)
+    pub fn mask_color(mut self, color: impl Display) -> Icons {
+        self.mask_color = color.to_string();
+        self
+    }
+}
+
+impl<'a> Node<'a> {
+    /// Writes the `<link>` tags for the site's icons
+    ///
+    /// See [`Icons`].
+    pub fn icons(&mut self, icons: &Icons) -> &mut Self {
+        let base = &icons.base;
+        self.link()
+            .rel("icon")
+            .href(format!("{}/favicon.ico", base))
+            .sizes("any");
+        for size in &icons.sizes {
+            self.link()
+                .rel("icon")
+                .href(format!("{}/icon-{}.png", base, size))
+                .type_("image/png")
+                .sizes(format!("{0}x{0}", size));
+        }
+        self.link()
+            .rel("apple-touch-icon")
+            .href(format!("{}/apple-touch-icon.png", base));
+        self.link()
+            .rel("mask-icon")
+            .href(format!("{}/mask-icon.svg", base))
+            .attr_value("color", &icons.mask_color);
+        self
+    }
+}
@@ -0,0 +1,234 @@
+/*!
+An optional layer which enforces content models at compile time.
+
+The nodes in this module only expose the children which are permitted by
+the HTML spec.  For instance, the only thing you can put in a [`List`] is
+`<li>` elements, and the only things you can put in a [`TableRow`] are
+`<td>` and `<th>` elements.  Trying to put a `<div>` directly inside a
+`<ul>` is a compile error:
+
+```compile_fail
+# use html_builder::*;
+# use html_builder::model::*;
+let mut buf = Buffer::new();
+let mut list = List::ul(&mut buf);
+list.div();
+```
+
+```
+use html_builder::*;
+use html_builder::model::*;
+use std::fmt::Write;
+
+let mut buf = Buffer::new();
+let mut table = Table::new(&mut buf).class("results");
+let mut head = table.thead();
+let mut row = head.tr();
+writeln!(row.th(), "Name")?;
+writeln!(row.th(), "Score")?;
+let mut body = table.tbody();
+for (name, score) in [("Alice", 9), ("Bob", 7)] {
+    let mut row = body.tr();
+    writeln!(row.td(), "{}", name)?;
+    writeln!(row.td(), "{}", score)?;
+}
+# Ok::<(), std::fmt::Error>(())
+```
+
+The leaves of the tree (eg. table cells and list items) are ordinary nodes,
+so you can put arbitrary content in them.  You can mix and match freely with
+the untyped API: to enter the typed layer, construct one of these nodes with
+its `new()` method, passing the parent.
+*/
+
+use crate::{ColElement, Comment, GlobalAttributes, Node, OptionElement, TdElement, ThElement};
+use std::borrow::Cow;
+use std::fmt::Display;
+
+macro_rules! model {
+    ($($(#[$m:meta])* $ty:ident,)*) => {$(
+        $(#[$m])*
+        pub struct $ty<'a>(Node<'a>);
+
+        impl<'a> $ty<'a> {
+            /// Adds a raw attribute; see [`Node::attr`]
+            pub fn attr(self, attr: &str) -> Self {
+                $ty(self.0.attr(attr))
+            }
+
+            /// Adds a comment
+            pub fn comment(&mut self) -> Comment<'_> {
+                self.0.comment()
+            }
+        }
+
+        impl<'a> GlobalAttributes for $ty<'a> {
+            fn attr_value(self, name: &str, value: impl Display) -> Self {
+                $ty(self.0.attr_value(name, value))
+            }
+
+            fn attr_flag(self, name: &str, on: bool) -> Self {
+                $ty(self.0.attr_flag(name, on))
+            }
+        }
+    )*};
+}
+
+model! {
+    /// A `<table>` element
+    Table,
+    /// A `<thead>`, `<tbody>`, or `<tfoot>` element
+    TableSection,
+    /// A `<tr>` element
+    TableRow,
+    /// A `<colgroup>` element
+    ColumnGroup,
+    /// A `<ul>`, `<ol>`, or `<menu>` element
+    List,
+    /// A `<dl>` element
+    DescriptionList,
+    /// A `<select>` element
+    Select,
+    /// An `<optgroup>` element
+    OptionGroup,
+    /// A `<datalist>` element
+    DataList,
+}
+
+impl<'a> Table<'a> {
+    /// Creates a `<table>` element
+    pub fn new(parent: &'a mut Node<'_>) -> Table<'a> {
+        Table(parent.child(Cow::Borrowed("table")))
+    }
+
+    /// Defines a table caption
+    pub fn caption(&mut self) -> Node<'_> {
+        self.0.child(Cow::Borrowed("caption"))
+    }
+
+    /// Specifies a group of one or more columns in a table for formatting
+    pub fn colgroup(&mut self) -> ColumnGroup<'_> {
+        ColumnGroup(self.0.child(Cow::Borrowed("colgroup")))
+    }
+
+    /// Groups the header content in a table
+    pub fn thead(&mut self) -> TableSection<'_> {
+        TableSection(self.0.child(Cow::Borrowed("thead")))
+    }
+
+    /// Groups the body content in a table
+    pub fn tbody(&mut self) -> TableSection<'_> {
+        TableSection(self.0.child(Cow::Borrowed("tbody")))
+    }
+
+    /// Groups the footer content in a table
+    pub fn tfoot(&mut self) -> TableSection<'_> {
+        TableSection(self.0.child(Cow::Borrowed("tfoot")))
+    }
+
+    /// Defines a row in a table
+    pub fn tr(&mut self) -> TableRow<'_> {
+        TableRow(self.0.child(Cow::Borrowed("tr")))
+    }
+}
+
+impl<'a> TableSection<'a> {
+    /// Defines a row in a table
+    pub fn tr(&mut self) -> TableRow<'_> {
+        TableRow(self.0.child(Cow::Borrowed("tr")))
+    }
+}
+
+impl<'a> TableRow<'a> {
+    /// Defines a cell in a table
+    pub fn td(&mut self) -> TdElement<'_> {
+        self.0.child(Cow::Borrowed("td")).into()
+    }
+
+    /// Defines a header cell in a table
+    pub fn th(&mut self) -> ThElement<'_> {
+        self.0.child(Cow::Borrowed("th")).into()
+    }
+}
+
+impl<'a> ColumnGroup<'a> {
+    /// Specifies column properties for each column within the group
+    pub fn col(&mut self) -> ColElement<'_> {
+        self.0.void_child(Cow::Borrowed("col")).into()
+    }
+}
+
+impl<'a> List<'a> {
+    /// Creates a `<ul>` element
+    pub fn ul(parent: &'a mut Node<'_>) -> List<'a> {
+        List(parent.child(Cow::Borrowed("ul")))
+    }
+
+    /// Creates an `<ol>` element
+    pub fn ol(parent: &'a mut Node<'_>) -> List<'a> {
+        List(parent.child(Cow::Borrowed("ol")))
+    }
+
+    /// Creates a `<menu>` element
+    pub fn menu(parent: &'a mut Node<'_>) -> List<'a> {
+        List(parent.child(Cow::Borrowed("menu")))
+    }
+
+    /// Defines a list item
+    pub fn li(&mut self) -> Node<'_> {
+        self.0.child(Cow::Borrowed("li"))
+    }
+}
+
+impl<'a> DescriptionList<'a> {
+    /// Creates a `<dl>` element
+    pub fn new(parent: &'a mut Node<'_>) -> DescriptionList<'a> {
+        DescriptionList(parent.child(Cow::Borrowed("dl")))
+    }
+
+    /// Defines a term/name in the list
+    pub fn dt(&mut self) -> Node<'_> {
+        self.0.child(Cow::Borrowed("dt"))
+    }
+
+    /// Defines a description/value of a term in the list
+    pub fn dd(&mut self) -> Node<'_> {
+        self.0.child(Cow::Borrowed("dd"))
+    }
+}
+
+impl<'a> Select<'a> {
+    /// Creates a `<select>` element
+    pub fn new(parent: &'a mut Node<'_>) -> Select<'a> {
+        Select(parent.child(Cow::Borrowed("select")))
+    }
+
+    /// Defines an option in the drop-down list
+    pub fn option(&mut self) -> OptionElement<'_> {
+        self.0.child(Cow::Borrowed("option")).into()
+    }
+
+    /// Defines a group of related options in the drop-down list
+    pub fn optgroup(&mut self) -> OptionGroup<'_> {
+        OptionGroup(self.0.child(Cow::Borrowed("optgroup")))
+    }
+}
+
+impl<'a> OptionGroup<'a> {
+    /// Defines an option in the group
+    pub fn option(&mut self) -> OptionElement<'_> {
+        self.0.child(Cow::Borrowed("option")).into()
+    }
+}
+
+impl<'a> DataList<'a> {
+    /// Creates a `<datalist>` element
+    pub fn new(parent: &'a mut Node<'_>) -> DataList<'a> {
+        DataList(parent.child(Cow::Borrowed("datalist")))
+    }
+
+    /// Defines a suggested value
+    pub fn option(&mut self) -> OptionElement<'_> {
+        self.0.child(Cow::Borrowed("option")).into()
+    }
+}
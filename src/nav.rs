@@ -0,0 +1,104 @@
+use crate::{AriaCurrent, GlobalAttributes, Html5, Node};
+use std::fmt::Display;
+
+impl<'a> Node<'a> {
+    /// Writes a breadcrumb trail
+    ///
+    /// `items` are `(label, href)` pairs, starting from the root of the
+    /// site.  The last item is the current page, which is marked with
+    /// `aria-current="page"`.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new();
+    /// buf.nav_breadcrumbs(vec![("Home", "/"), ("Docs", "/docs/")]);
+    /// assert_eq!(buf.finish(), "\
+    /// <nav aria-label=\"Breadcrumb\">
+    ///  <ol>
+    ///   <li>
+    ///    <a href=\"/\">
+    /// Home
+    ///    </a>
+    ///   </li>
+    ///   <li>
+    ///    <a href=\"/docs/\" aria-current=\"page\">
+    /// Docs
+    ///    </a>
+    ///   </li>
+    ///  </ol>
+    /// </nav>
+    /// ");
+    /// ```
+    pub fn nav_breadcrumbs<L, H>(&mut self, items: impl IntoIterator<Item = (L, H)>) -> &mut Self
+    where
+        L: Display,
+        H: Display,
+    {
+        let mut nav = self.nav().aria_label("Breadcrumb");
+        let mut ol = nav.ol();
+        let mut items = items.into_iter().peekable();
+        while let Some((label, href)) = items.next() {
+            let mut li = ol.li();
+            let mut a = li.a().href(href);
+            if items.peek().is_none() {
+                a = a.aria_current(AriaCurrent::Page);
+            }
+            a.text(label);
+        }
+        self
+    }
+
+    /// Writes a navigation menu
+    ///
+    /// `label` describes the menu to screen readers, eg. `"Main"`.  `items`
+    /// are `(label, href)` pairs; the link whose href is `current` is
+    /// marked with `aria-current="page"`.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new();
+    /// buf.nav_list("Main", vec![("Home", "/"), ("Blog", "/blog/")], "/blog/");
+    /// assert_eq!(buf.finish(), "\
+    /// <nav aria-label=\"Main\">
+    ///  <ul>
+    ///   <li>
+    ///    <a href=\"/\">
+    /// Home
+    ///    </a>
+    ///   </li>
+    ///   <li>
+    ///    <a href=\"/blog/\" aria-current=\"page\">
+    /// Blog
+    ///    </a>
+    ///   </li>
+    ///  </ul>
+    /// </nav>
+    /// ");
+    /// ```
+    pub fn nav_list<L, H>(
+        &mut self,
+        label: impl Display,
+        items: impl IntoIterator<Item = (L, H)>,
+        current: &str,
+    ) -> &mut Self
+    where
+        L: Display,
+        H: Display,
+    {
+        let mut nav = self.nav().aria_label(label);
+        let mut ul = nav.ul();
+        for (label, href) in items {
+            let href = href.to_string();
+            let is_current = href == current;
+            let mut li = ul.li();
+            let mut a = li.a().href(href);
+            if is_current {
+                a = a.aria_current(AriaCurrent::Page);
+            }
+            a.text(label);
+        }
+        self
+    }
+}
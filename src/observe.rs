@@ -0,0 +1,210 @@
+//! Watching what's written to a buffer
+
+use crate::sink::AnySink;
+use crate::{Attribute, Buffer, Ctx, Format, Sink};
+use std::any::Any;
+
+/// Something written to a buffer, as seen by an observer; see
+/// [`Buffer::observe()`].
+///
+/// Text is given unescaped.  The attributes of an element can be changed
+/// by the observer before they're written.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Event<'a> {
+    /// An element was opened
+    StartElement {
+        tag: &'a str,
+        attrs: &'a mut Vec<Attribute>,
+    },
+    /// A void element was written
+    VoidElement {
+        tag: &'a str,
+        attrs: &'a mut Vec<Attribute>,
+    },
+    /// An element was closed
+    EndElement { tag: &'a str },
+    /// Text was written
+    Text(&'a str),
+    /// Markup was written verbatim, eg. by a node in [raw
+    /// mode][crate::Node::raw]
+    Raw(&'a str),
+    /// A comment was written
+    Comment(&'a str),
+    /// A [conditional comment][crate::Node::conditional_comment] was opened
+    StartConditional { cond: &'a str },
+    /// A conditional comment was closed
+    EndConditional,
+}
+
+type Observer = Box<dyn FnMut(&mut Event) + Send>;
+
+/// Passes each event to the observers, and then on to the real sink
+pub(crate) struct Observed {
+    pub(crate) inner: Box<dyn AnySink>,
+    observers: Vec<Observer>,
+    /// The text of a comment which is being written
+    comment: Option<String>,
+}
+
+impl Observed {
+    fn notify(&mut self, mut event: Event) {
+        for observer in &mut self.observers {
+            observer(&mut event);
+        }
+    }
+}
+
+impl Sink for Observed {
+    fn start_tag(&mut self, depth: usize, tag: &str, attrs: &[Attribute]) {
+        let mut attrs = attrs.to_vec();
+        self.notify(Event::StartElement {
+            tag,
+            attrs: &mut attrs,
+        });
+        self.inner.start_tag(depth, tag, &attrs);
+    }
+
+    fn void_tag(&mut self, depth: usize, tag: &str, attrs: &[Attribute], self_closing: bool) {
+        let mut attrs = attrs.to_vec();
+        self.notify(Event::VoidElement {
+            tag,
+            attrs: &mut attrs,
+        });
+        self.inner.void_tag(depth, tag, &attrs, self_closing);
+    }
+
+    fn end_tag(&mut self, depth: usize, tag: &str) {
+        self.notify(Event::EndElement { tag });
+        self.inner.end_tag(depth, tag);
+    }
+
+    fn text(&mut self, text: &str) {
+        self.notify(Event::Text(text));
+        self.inner.text(text);
+    }
+
+    fn raw(&mut self, html: &str) {
+        match &mut self.comment {
+            Some(comment) => comment.push_str(html),
+            None => self.notify(Event::Raw(html)),
+        }
+        self.inner.raw(html);
+    }
+
+    fn start_comment(&mut self, depth: usize) {
+        self.comment = Some(String::new());
+        self.inner.start_comment(depth);
+    }
+
+    fn end_comment(&mut self, depth: usize) {
+        if let Some(text) = self.comment.take() {
+            self.notify(Event::Comment(&text));
+        }
+        self.inner.end_comment(depth);
+    }
+
+    fn start_conditional(&mut self, depth: usize, cond: &str) {
+        self.notify(Event::StartConditional { cond });
+        self.inner.start_conditional(depth, cond);
+    }
+
+    fn end_conditional(&mut self, depth: usize) {
+        self.notify(Event::EndConditional);
+        self.inner.end_conditional(depth);
+    }
+
+    fn flush(&mut self) {
+        self.inner.flush();
+    }
+
+    fn set_format(&mut self, format: &Format) {
+        self.inner.set_format(format);
+    }
+
+    fn end(&mut self) {
+        self.inner.end();
+    }
+}
+
+impl Buffer {
+    /// Calls `f` with each thing that's written to the buffer.
+    ///
+    /// This works alongside the buffer's usual output, for things like
+    /// collecting metrics or rewriting links.  To get only the events,
+    /// use a buffer which discards its output, ie. `Buffer::with_sink(())`.
+    /// An observer can't stop anything being written, but it can change
+    /// the attributes of an element; the observers are called in the order
+    /// they were added.
+    ///
+    /// Content written into a [placeholder][crate::Node::placeholder],
+    /// including the [`HeadManager`][crate::HeadManager]'s items, isn't
+    /// seen by observers.
+    ///
+    /// ```
+    /// use html_builder::*;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let links = Arc::new(Mutex::new(vec![]));
+    /// let seen = links.clone();
+    /// let mut buf = Buffer::new().observe(move |event| {
+    ///     if let Event::StartElement { tag: "a", attrs } = event {
+    ///         for attr in attrs.iter_mut() {
+    ///             if let Attribute::Value(name, href) = attr {
+    ///                 if name == "href" && href.starts_with('/') {
+    ///                     seen.lock().unwrap().push(href.clone());
+    ///                     href.insert_str(0, "https://example.com");
+    ///                 }
+    ///             }
+    ///         }
+    ///     }
+    /// });
+    /// buf.a().href("/docs").text("Docs");
+    /// assert_eq!(buf.finish(), "\
+    /// <a href=\"https://example.com/docs\">
+    /// Docs
+    /// </a>
+    /// ");
+    /// assert_eq!(*links.lock().unwrap(), ["/docs"]);
+    /// ```
+    pub fn observe(mut self, f: impl FnMut(&mut Event) + Send + 'static) -> Buffer {
+        let ctx = self.node.ctx.get();
+        if !ctx.wtr.as_any().is::<Observed>() {
+            let inner = std::mem::replace(&mut ctx.wtr, Box::new(()));
+            ctx.wtr = Box::new(Observed {
+                inner,
+                observers: vec![],
+                comment: None,
+            });
+        }
+        let observed: &mut Observed = ctx.wtr.as_any_mut().downcast_mut().unwrap();
+        observed.observers.push(Box::new(f));
+        self
+    }
+}
+
+impl Ctx {
+    /// The sink, without any observers
+    pub(crate) fn sink(&self) -> &dyn Any {
+        match self.wtr.as_any().downcast_ref::<Observed>() {
+            Some(observed) => observed.inner.as_any(),
+            None => self.wtr.as_any(),
+        }
+    }
+
+    /// The sink, without any observers
+    pub(crate) fn sink_mut(&mut self) -> &mut dyn Any {
+        if self.wtr.as_any().is::<Observed>() {
+            let observed: &mut Observed = self.wtr.as_any_mut().downcast_mut().unwrap();
+            return observed.inner.as_any_mut();
+        }
+        self.wtr.as_any_mut()
+    }
+
+    /// The sink, without any observers
+    pub(crate) fn into_sink(self) -> Box<dyn Any> {
+        match self.wtr.into_any().downcast::<Observed>() {
+            Ok(observed) => observed.inner.into_any(),
+            Err(wtr) => wtr,
+        }
+    }
+}
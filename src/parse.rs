@@ -0,0 +1,192 @@
+//! Importing existing HTML with html5ever
+
+use crate::{Buffer, Content, Element, Parent, Tree};
+use html5ever::tendril::TendrilSink;
+use html5ever::{local_name, namespace_url, ns, QualName};
+use markup5ever_rcdom::{Handle, NodeData, RcDom};
+
+impl Tree {
+    /// Parses an HTML document or fragment.
+    ///
+    /// This method is only available with the `parse` feature.  The input is
+    /// parsed with [html5ever], so it's handled the same way a browser would
+    /// handle it: missing end tags are inferred, entities are decoded, and so
+    /// on.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut tree = Tree::parse("<ul><li>One<li>Two</ul>");
+    /// tree.find_mut("ul").unwrap().child("li").append_text("Three");
+    /// assert_eq!(tree.render(), "\
+    /// <ul>
+    ///  <li>
+    /// One
+    ///  </li>
+    ///  <li>
+    /// Two
+    ///  </li>
+    ///  <li>
+    /// Three
+    ///  </li>
+    /// </ul>
+    /// ");
+    /// ```
+    ///
+    /// If the input starts with a doctype or an `<html>` tag, it's parsed as
+    /// a full document, and the `<html>`, `<head>`, and `<body>` elements are
+    /// filled in if they're missing.  Otherwise it's parsed as the contents
+    /// of a `<body>` element.
+    ///
+    /// Whitespace at the start and end of text is dropped, since the builder
+    /// puts text on its own line anyway.  The contents of `<pre>`,
+    /// `<textarea>`, `<script>`, and `<style>` are kept verbatim.
+    ///
+    /// [html5ever]: https://docs.rs/html5ever
+    pub fn parse(html: &str) -> Tree {
+        Tree::parse_with(html, false)
+    }
+
+    /// Parses some HTML like [`Tree::parse()`], optionally keeping all the
+    /// whitespace in text
+    pub(crate) fn parse_with(html: &str, keep_whitespace: bool) -> Tree {
+        let start = html.trim_start();
+        let mut children = vec![];
+        if starts_with_ignore_case(start, "<!doctype") || starts_with_ignore_case(start, "<html") {
+            let dom = html5ever::parse_document(RcDom::default(), Default::default()).one(html);
+            convert_children(&dom.document, &mut children, false, keep_whitespace);
+        } else {
+            let context = QualName::new(None, ns!(html), local_name!("body"));
+            let dom =
+                html5ever::parse_fragment(RcDom::default(), Default::default(), context, vec![])
+                    .one(html);
+            // The fragment's contents are wrapped in an `<html>` element
+            for node in dom.document.children.borrow().iter() {
+                convert_children(node, &mut children, false, keep_whitespace);
+            }
+        }
+        let mut tree = Tree::new();
+        *tree.children_mut() = children;
+        tree
+    }
+}
+
+fn starts_with_ignore_case(s: &str, prefix: &str) -> bool {
+    s.len() >= prefix.len() && s.as_bytes()[..prefix.len()].eq_ignore_ascii_case(prefix.as_bytes())
+}
+
+impl Buffer {
+    /// Parses an HTML document or fragment and writes it into a new buffer.
+    ///
+    /// This method is only available with the `parse` feature.
+    /// The parsed content is re-serialized with the buffer's usual
+    /// formatting, and you can carry on appending to the buffer afterwards.
+    /// If you want to add things _inside_ the parsed elements, use
+    /// [`Tree::parse()`] instead.
+    ///
+    /// ```
+    /// use html_builder::*;
+    /// use std::fmt::Write;
+    ///
+    /// let mut buf = Buffer::parse("<p>Imported<br>text</p>");
+    /// writeln!(buf.p(), "New")?;
+    /// assert_eq!(buf.finish(), "\
+    /// <p>
+    /// Imported
+    ///  <br>
+    /// text
+    /// </p>
+    /// <p>
+    /// New
+    /// </p>
+    /// ");
+    /// # Ok::<(), std::fmt::Error>(())
+    /// ```
+    pub fn parse(html: &str) -> Buffer {
+        let mut buf = Buffer::new();
+        Tree::parse(html).write_to(&mut buf);
+        buf
+    }
+}
+
+fn convert_children(
+    handle: &Handle,
+    out: &mut Vec<Content>,
+    verbatim: bool,
+    keep_whitespace: bool,
+) {
+    for child in handle.children.borrow().iter() {
+        if let Some(content) = convert(child, verbatim, keep_whitespace) {
+            out.push(content);
+        }
+    }
+}
+
+fn convert(handle: &Handle, verbatim: bool, keep_whitespace: bool) -> Option<Content> {
+    match &handle.data {
+        NodeData::Document | NodeData::ProcessingInstruction { .. } => None,
+        NodeData::Doctype {
+            name,
+            public_id,
+            system_id,
+        } => {
+            let mut decl = name.to_string();
+            if !public_id.is_empty() {
+                decl.push_str(&format!(" PUBLIC \"{}\"", public_id));
+            }
+            if !system_id.is_empty() {
+                if public_id.is_empty() {
+                    decl.push_str(" SYSTEM");
+                }
+                decl.push_str(&format!(" \"{}\"", system_id));
+            }
+            Some(Content::Doctype(decl))
+        }
+        NodeData::Text { contents } => {
+            let contents = contents.borrow();
+            let text = if verbatim || keep_whitespace {
+                contents.as_ref()
+            } else {
+                contents.trim()
+            };
+            if text.is_empty() || (!keep_whitespace && text.trim().is_empty()) {
+                None
+            } else {
+                Some(Content::Text(text.to_string()))
+            }
+        }
+        NodeData::Comment { contents } => Some(Content::Comment(contents.trim().to_string())),
+        NodeData::Element {
+            name,
+            attrs,
+            template_contents,
+            ..
+        } => {
+            let mut el = Element::new(name.local.to_string());
+            for attr in attrs.borrow().iter() {
+                let name = match &attr.name.prefix {
+                    Some(prefix) => format!("{}:{}", prefix, attr.name.local),
+                    None => attr.name.local.to_string(),
+                };
+                if attr.value.is_empty() {
+                    el.set_flag(name);
+                } else {
+                    el.set_attr(name, attr.value.to_string());
+                }
+            }
+            let verbatim = matches!(
+                el.tag(),
+                "pre" | "textarea" | "script" | "style" | "listing" | "plaintext"
+            );
+            let mut children = vec![];
+            match template_contents.borrow().as_ref() {
+                Some(contents) => {
+                    convert_children(contents, &mut children, verbatim, keep_whitespace)
+                }
+                None => convert_children(handle, &mut children, verbatim, keep_whitespace),
+            }
+            *el.children_mut() = children;
+            Some(Content::Element(el))
+        }
+    }
+}
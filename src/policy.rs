@@ -0,0 +1,237 @@
+//! Rules which a buffer applies to every element it writes
+
+use crate::{Attribute, Buffer, Encoding, Format};
+
+/// The policies enabled on a buffer
+#[derive(Clone, Default)]
+pub(crate) struct Policies {
+    noopener: bool,
+    nonce: Option<String>,
+    lazy_images: bool,
+    pub(crate) max_depth: Option<usize>,
+    sort_attributes: bool,
+    pub(crate) encoding: Encoding,
+    pub(crate) format: Format,
+    #[cfg(feature = "source-locations")]
+    pub(crate) source_locations: bool,
+}
+
+impl Buffer {
+    /// Adds `rel="noopener noreferrer"` to links which open in a new tab.
+    ///
+    /// When an `<a>` element has `target="_blank"`, the linked page can
+    /// otherwise navigate the original page via `window.opener`.  With this
+    /// policy enabled, any missing `noopener` and `noreferrer` keywords are
+    /// added to the link's `rel` attribute.  Attributes added with
+    /// [`attr()`][crate::Node::attr] are written verbatim, and aren't
+    /// checked.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new().noopener();
+    /// buf.a().href("/docs").target("_blank").rel("help").text("Docs");
+    /// assert_eq!(buf.finish(), "\
+    /// <a href=\"/docs\" target=\"_blank\" rel=\"help noopener noreferrer\">
+    /// Docs
+    /// </a>
+    /// ");
+    /// ```
+    pub fn noopener(mut self) -> Buffer {
+        self.node.ctx.get().policies.noopener = true;
+        self
+    }
+
+    /// Adds a CSP nonce to every `<script>` and `<style>` element.
+    ///
+    /// A `Content-Security-Policy` header with `script-src 'nonce-...'`
+    /// only allows scripts which carry the matching `nonce` attribute.
+    /// Rather than passing the nonce to every function which writes a
+    /// script, set it once on the buffer; elements which already have a
+    /// `nonce` are left alone.  The nonce should be freshly generated for
+    /// each response, and can be read back with
+    /// [`nonce()`][Buffer::nonce] to build the header.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new().csp_nonce("r4nd0m");
+    /// let header = format!("script-src 'nonce-{}'", buf.nonce().unwrap());
+    /// buf.script().raw().text("init();");
+    /// assert_eq!(header, "script-src 'nonce-r4nd0m'");
+    /// assert_eq!(buf.finish(), "<script nonce=\"r4nd0m\">\ninit();\n</script>\n");
+    /// ```
+    pub fn csp_nonce(mut self, nonce: impl Into<String>) -> Buffer {
+        self.node.ctx.get().policies.nonce = Some(nonce.into());
+        self
+    }
+
+    /// The nonce set with [`csp_nonce()`][Buffer::csp_nonce], if any
+    pub fn nonce(&self) -> Option<&str> {
+        self.node.ctx.get_ref().policies.nonce.as_deref()
+    }
+
+    /// Makes images load lazily by default.
+    ///
+    /// With this policy enabled, `<img>` elements get `loading="lazy"` and
+    /// `decoding="async"`, unless those attributes have been set
+    /// explicitly.  Images which are visible when the page loads, such as a
+    /// logo or hero image, should be given `loading="eager"`.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new().lazy_images();
+    /// buf.img().src("/logo.png").loading("eager");
+    /// buf.img().src("/photo.jpg");
+    /// assert_eq!(buf.finish(), "\
+    /// <img src=\"/logo.png\" loading=\"eager\" decoding=\"async\">
+    /// <img src=\"/photo.jpg\" loading=\"lazy\" decoding=\"async\">
+    /// ");
+    /// ```
+    pub fn lazy_images(mut self) -> Buffer {
+        self.node.ctx.get().policies.lazy_images = true;
+        self
+    }
+
+    /// Limits how deeply elements can be nested.
+    ///
+    /// Code which renders a recursive structure, such as a thread of
+    /// replies, can run away if the structure is unexpectedly deep (or
+    /// cyclic).  With a limit set, the fallible methods such as
+    /// [`try_child()`][crate::Node::try_child] return
+    /// [`Error::TooDeep`][crate::Error::TooDeep] instead of writing an
+    /// element which would be nested more than `limit` deep, and the other
+    /// methods panic.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// fn thread(node: &mut Node, replies: u32) -> Result<(), Error> {
+    ///     let mut div = node.try_child("div".into())?;
+    ///     if replies > 0 {
+    ///         thread(&mut div, replies - 1)?;
+    ///     }
+    ///     Ok(())
+    /// }
+    ///
+    /// let mut buf = Buffer::new().max_depth(3);
+    /// assert_eq!(thread(&mut buf, 2), Ok(()));
+    /// assert_eq!(thread(&mut buf, 3), Err(Error::TooDeep(3)));
+    /// ```
+    pub fn max_depth(mut self, limit: usize) -> Buffer {
+        self.node.ctx.get().policies.max_depth = Some(limit);
+        self
+    }
+
+    /// Writes each element's attributes in order of name.
+    ///
+    /// Normally attributes are written in the order they're set.  When
+    /// they come from a `HashMap`, eg. via
+    /// [`element()`][crate::Node::element], that order changes from run to
+    /// run; sorting them makes the output reproducible.  Attributes added
+    /// with [`attr()`][crate::Node::attr] are sorted by their text.
+    ///
+    /// ```
+    /// use html_builder::*;
+    /// use std::collections::HashMap;
+    ///
+    /// let attrs: HashMap<_, _> = [("id", "x"), ("class", "y"), ("title", "z")].into();
+    /// let mut buf = Buffer::new().sort_attributes();
+    /// buf.element("div".into(), attrs).hidden(true);
+    /// assert_eq!(buf.finish(), "\
+    /// <div class=\"y\" hidden id=\"x\" title=\"z\">
+    /// </div>
+    /// ");
+    /// ```
+    pub fn sort_attributes(mut self) -> Buffer {
+        self.node.ctx.get().policies.sort_attributes = true;
+        self
+    }
+}
+
+impl Policies {
+    /// These policies without the ones which [`apply()`][Policies::apply]
+    /// handles.  Cached fragments are recorded with these, and the rest are
+    /// applied each time the fragment is replayed.
+    pub(crate) fn for_recording(&self) -> Policies {
+        Policies {
+            max_depth: self.max_depth,
+            format: self.format.clone(),
+            #[cfg(feature = "source-locations")]
+            source_locations: self.source_locations,
+            ..Policies::default()
+        }
+    }
+
+    /// Adjusts the attributes of an element before its start tag is written
+    pub(crate) fn apply(&self, tag: &str, attrs: &mut Vec<Attribute>) {
+        if self.noopener && tag == "a" {
+            ensure_noopener(attrs);
+        }
+        if let Some(nonce) = &self.nonce {
+            if tag == "script" || tag == "style" {
+                set_default(attrs, "nonce", nonce);
+            }
+        }
+        if self.lazy_images && tag == "img" {
+            set_default(attrs, "loading", "lazy");
+            set_default(attrs, "decoding", "async");
+        }
+        if self.sort_attributes {
+            attrs.sort_by(|a, b| sort_key(a).cmp(sort_key(b)));
+        }
+        if self.encoding != Encoding::Minimal {
+            for attr in attrs.iter_mut() {
+                self.encoding.encode_attr(attr);
+            }
+        }
+    }
+}
+
+fn sort_key(attr: &Attribute) -> &str {
+    match attr {
+        Attribute::Raw(x) | Attribute::Flag(x) | Attribute::Value(x, _) => x,
+    }
+}
+
+/// Sets an attribute, unless it has already been set
+fn set_default(attrs: &mut Vec<Attribute>, name: &str, value: &str) {
+    let exists = attrs
+        .iter()
+        .any(|attr| matches!(attr, Attribute::Value(n, _) if n == name));
+    if !exists {
+        attrs.push(Attribute::Value(name.into(), value.into()));
+    }
+}
+
+fn ensure_noopener(attrs: &mut Vec<Attribute>) {
+    let blank = attrs.iter().any(|attr| {
+        matches!(attr, Attribute::Value(n, v) if n == "target" && v.eq_ignore_ascii_case("_blank"))
+    });
+    if !blank {
+        return;
+    }
+    let i = attrs
+        .iter()
+        .position(|attr| matches!(attr, Attribute::Value(n, _) if n == "rel"))
+        .unwrap_or_else(|| {
+            attrs.push(Attribute::Value("rel".into(), String::new()));
+            attrs.len() - 1
+        });
+    let rel = match &mut attrs[i] {
+        Attribute::Value(_, rel) => rel,
+        _ => unreachable!(),
+    };
+    for keyword in ["noopener", "noreferrer"].iter() {
+        if !rel
+            .split_ascii_whitespace()
+            .any(|k| k.eq_ignore_ascii_case(keyword))
+        {
+            if !rel.is_empty() {
+                rel.push(' ');
+            }
+            rel.push_str(keyword);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Splicing in untrusted HTML, via `ammonia`
+
+use crate::Node;
+
+impl<'a> Node<'a> {
+    /// Write untrusted HTML into the node, after sanitizing it
+    ///
+    /// This method is only available with the `ammonia` feature.  It's a
+    /// middle ground between escaping and [raw][Node::raw()] mode, for
+    /// HTML which comes from users or a CMS: the markup is kept, but
+    /// anything dangerous (scripts, event handlers, `javascript:` URLs, and
+    /// so on) is stripped out by [`ammonia::clean()`].  The result is
+    /// trimmed and followed by a newline, like text written with
+    /// [`Node::text()`].
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new();
+    /// buf.div().raw_sanitized("<b onclick=\"evil()\">Hi</b><script>evil()</script>\n");
+    /// assert_eq!(buf.finish(), "<div>\n<b>Hi</b>\n</div>\n");
+    /// ```
+    pub fn raw_sanitized(&mut self, html: &str) -> &mut Self {
+        self.raw_sanitized_with(&ammonia::Builder::default(), html)
+    }
+
+    /// Write untrusted HTML into the node, after sanitizing it with the
+    /// given settings
+    ///
+    /// This method is only available with the `ammonia` feature.  See
+    /// [`Node::raw_sanitized()`].
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut sanitizer = ammonia::Builder::default();
+    /// sanitizer.rm_tags(&["img"]);
+    /// let mut buf = Buffer::new();
+    /// buf.div().raw_sanitized_with(&sanitizer, "<img src=\"x.png\"><i>Hi</i>");
+    /// assert_eq!(buf.finish(), "<div>\n<i>Hi</i>\n</div>\n");
+    /// ```
+    pub fn raw_sanitized_with(&mut self, sanitizer: &ammonia::Builder, html: &str) -> &mut Self {
+        let clean = sanitizer.clean(html).to_string();
+        let clean = clean.trim();
+        if !clean.is_empty() {
+            // Writing to a node never fails
+            self.write_raw(clean).unwrap();
+            self.write_raw("\n").unwrap();
+        }
+        self
+    }
+}
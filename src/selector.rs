@@ -0,0 +1,269 @@
+use crate::Element;
+use std::fmt;
+
+/// A CSS selector, for querying a [`Tree`][crate::Tree].
+///
+/// A subset of CSS selector syntax is supported:
+///
+/// * type selectors (`div`) and the universal selector (`*`)
+/// * id selectors (`#main`) and class selectors (`.card`)
+/// * attribute selectors: `[href]`, `[type=text]`, `[lang="en"]`
+/// * descendant (`nav a`) and child (`ul > li`) combinators
+/// * selector lists (`h1, h2`)
+///
+/// Pseudo-classes and other combinators aren't supported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    alternatives: Vec<Complex>,
+}
+
+/// A selector couldn't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectorError {
+    selector: String,
+    pos: usize,
+    msg: &'static str,
+}
+
+impl fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid selector {:?} at position {}: {}",
+            self.selector, self.pos, self.msg
+        )
+    }
+}
+
+impl std::error::Error for SelectorError {}
+
+/// A sequence of compound selectors joined by combinators.  The combinator
+/// in each part describes the relationship with the previous part.
+type Complex = Vec<(Combinator, Compound)>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Compound {
+    tag: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+    attrs: Vec<(String, Option<String>)>,
+}
+
+impl Selector {
+    /// Parses a selector.
+    pub fn parse(selector: &str) -> Result<Selector, SelectorError> {
+        Parser {
+            src: selector,
+            pos: 0,
+        }
+        .selector()
+    }
+
+    /// Whether `el` matches, given its ancestors (outermost first)
+    pub(crate) fn matches(&self, el: &Element, ancestors: &[&Element]) -> bool {
+        self.alternatives
+            .iter()
+            .any(|parts| matches_complex(parts, el, ancestors))
+    }
+}
+
+impl std::str::FromStr for Selector {
+    type Err = SelectorError;
+    fn from_str(s: &str) -> Result<Selector, SelectorError> {
+        Selector::parse(s)
+    }
+}
+
+fn matches_complex(parts: &[(Combinator, Compound)], el: &Element, ancestors: &[&Element]) -> bool {
+    let ((comb, last), rest) = match parts.split_last() {
+        Some(x) => x,
+        None => return true,
+    };
+    if !last.matches(el) {
+        return false;
+    }
+    if rest.is_empty() {
+        return true;
+    }
+    match comb {
+        Combinator::Child => match ancestors.split_last() {
+            Some((parent, ancestors)) => matches_complex(rest, parent, ancestors),
+            None => false,
+        },
+        Combinator::Descendant => (0..ancestors.len())
+            .rev()
+            .any(|i| matches_complex(rest, ancestors[i], &ancestors[..i])),
+    }
+}
+
+impl Compound {
+    fn matches(&self, el: &Element) -> bool {
+        if let Some(tag) = &self.tag {
+            if !tag.eq_ignore_ascii_case(el.tag()) {
+                return false;
+            }
+        }
+        if let Some(id) = &self.id {
+            if el.attr("id") != Some(id.as_str()) {
+                return false;
+            }
+        }
+        self.classes.iter().all(|c| el.has_class(c))
+            && self.attrs.iter().all(|(name, value)| match value {
+                None => el.attr(name).is_some(),
+                Some(value) => el.attr(name) == Some(value.as_str()),
+            })
+    }
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn err<T>(&self, msg: &'static str) -> Result<T, SelectorError> {
+        Err(SelectorError {
+            selector: self.src.to_string(),
+            pos: self.pos,
+            msg,
+        })
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.peek() {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn skip_ws(&mut self) -> bool {
+        let start = self.pos;
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+        self.pos != start
+    }
+
+    fn ident(&mut self) -> Result<String, SelectorError> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        {
+            self.bump();
+        }
+        if self.pos == start {
+            return self.err("expected an identifier");
+        }
+        Ok(self.src[start..self.pos].to_string())
+    }
+
+    fn selector(mut self) -> Result<Selector, SelectorError> {
+        let mut alternatives = vec![self.complex()?];
+        while self.peek() == Some(',') {
+            self.bump();
+            alternatives.push(self.complex()?);
+        }
+        if self.peek().is_some() {
+            return self.err("unexpected character");
+        }
+        Ok(Selector { alternatives })
+    }
+
+    fn complex(&mut self) -> Result<Complex, SelectorError> {
+        self.skip_ws();
+        let mut parts = vec![(Combinator::Descendant, self.compound()?)];
+        loop {
+            let ws = self.skip_ws();
+            let comb = match self.peek() {
+                None | Some(',') => return Ok(parts),
+                Some('>') => {
+                    self.bump();
+                    self.skip_ws();
+                    Combinator::Child
+                }
+                Some(_) if ws => Combinator::Descendant,
+                Some(_) => return self.err("unexpected character"),
+            };
+            parts.push((comb, self.compound()?));
+        }
+    }
+
+    fn compound(&mut self) -> Result<Compound, SelectorError> {
+        let mut x = Compound::default();
+        let start = self.pos;
+        match self.peek() {
+            Some('*') => self.bump(),
+            Some(c) if c.is_alphabetic() => x.tag = Some(self.ident()?),
+            _ => (),
+        }
+        loop {
+            match self.peek() {
+                Some('#') => {
+                    self.bump();
+                    x.id = Some(self.ident()?);
+                }
+                Some('.') => {
+                    self.bump();
+                    x.classes.push(self.ident()?);
+                }
+                Some('[') => {
+                    self.bump();
+                    x.attrs.push(self.attr()?);
+                }
+                Some(':') => return self.err("pseudo-classes aren't supported"),
+                _ if self.pos == start => return self.err("expected a selector"),
+                _ => return Ok(x),
+            }
+        }
+    }
+
+    fn attr(&mut self) -> Result<(String, Option<String>), SelectorError> {
+        self.skip_ws();
+        let name = self.ident()?;
+        self.skip_ws();
+        let value = match self.peek() {
+            Some('=') => {
+                self.bump();
+                self.skip_ws();
+                Some(self.attr_value()?)
+            }
+            _ => None,
+        };
+        self.skip_ws();
+        if self.peek() != Some(']') {
+            return self.err("expected `]`");
+        }
+        self.bump();
+        Ok((name, value))
+    }
+
+    fn attr_value(&mut self) -> Result<String, SelectorError> {
+        match self.peek() {
+            Some(q @ '"') | Some(q @ '\'') => {
+                self.bump();
+                let start = self.pos;
+                while self.peek().is_some_and(|c| c != q) {
+                    self.bump();
+                }
+                if self.peek().is_none() {
+                    return self.err("unterminated string");
+                }
+                let value = self.src[start..self.pos].to_string();
+                self.bump();
+                Ok(value)
+            }
+            _ => self.ident(),
+        }
+    }
+}
@@ -0,0 +1,364 @@
+//! Rendering `Serialize` values as HTML
+
+use crate::{Error, Html5, Node};
+use serde::ser::{self, Serialize};
+
+impl<'a> Node<'a> {
+    /// Write any `Serialize` value into the node, as HTML.
+    ///
+    /// This method is only available with the `serde` feature.  It's meant
+    /// for debug pages and the like, where you want to show some data
+    /// without writing any code to format it:
+    ///
+    /// * Structs and maps become a `<dl>`, with the field names or keys in
+    ///   the `<dt>`s and the values in the `<dd>`s.
+    /// * Sequences and tuples become an `<ol>`.
+    /// * Enum variants with data become a `<dl>` with a single entry, named
+    ///   after the variant.  Other variants are written as their name.
+    /// * Strings, numbers, and so on are written as text (escaped, of
+    ///   course).  `None` and `()` aren't written at all.
+    ///
+    /// ```
+    /// use html_builder::*;
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// struct User {
+    ///     name: &'static str,
+    ///     roles: Vec<&'static str>,
+    /// }
+    ///
+    /// let user = User { name: "Alice", roles: vec!["admin"] };
+    /// let mut buf = Buffer::new();
+    /// buf.serialize(&user)?;
+    /// assert_eq!(buf.finish(), "\
+    /// <dl>
+    ///  <dt>
+    /// name
+    ///  </dt>
+    ///  <dd>
+    /// Alice
+    ///  </dd>
+    ///  <dt>
+    /// roles
+    ///  </dt>
+    ///  <dd>
+    ///   <ol>
+    ///    <li>
+    /// admin
+    ///    </li>
+    ///   </ol>
+    ///  </dd>
+    /// </dl>
+    /// ");
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn serialize<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(Serializer(self))
+    }
+}
+
+impl ser::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Error {
+        Error::Serialize(msg.to_string())
+    }
+}
+
+/// Writes a value into a node
+struct Serializer<'s, 'a>(&'s mut Node<'a>);
+
+/// Writes the contents of a `<dl>` or `<ol>`
+struct Compound<'s>(Node<'s>);
+
+impl<'s, 'a> Serializer<'s, 'a> {
+    fn text(self, text: impl std::fmt::Display) -> Result<(), Error> {
+        self.0.text(text);
+        Ok(())
+    }
+
+    /// Starts a `<dl>` with a single entry, and returns its `<dd>`
+    fn variant(self, name: &str) -> Node<'s> {
+        let mut dl = self.0.dl();
+        dl.dt().text(name);
+        dl.into_child("dd".into())
+    }
+}
+
+impl<'s, 'a> ser::Serializer for Serializer<'s, 'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Compound<'s>;
+    type SerializeTuple = Compound<'s>;
+    type SerializeTupleStruct = Compound<'s>;
+    type SerializeTupleVariant = Compound<'s>;
+    type SerializeMap = Compound<'s>;
+    type SerializeStruct = Compound<'s>;
+    type SerializeStructVariant = Compound<'s>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        self.text(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        self.text(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), Error> {
+        self.text(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), Error> {
+        self.text(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        self.text(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), Error> {
+        self.text(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        self.text(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), Error> {
+        self.text(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), Error> {
+        self.text(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        self.text(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), Error> {
+        self.text(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), Error> {
+        self.text(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), Error> {
+        self.text(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        self.text(v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        self.text(v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+        ser::Serializer::collect_seq(self, v)
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.text(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(Serializer(&mut self.variant(variant)))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound<'s>, Error> {
+        Ok(Compound(self.0.child("ol".into())))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Compound<'s>, Error> {
+        Ok(Compound(self.0.child("ol".into())))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'s>, Error> {
+        Ok(Compound(self.0.child("ol".into())))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'s>, Error> {
+        Ok(Compound(self.variant(variant).into_child("ol".into())))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound<'s>, Error> {
+        Ok(Compound(self.0.dl()))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Compound<'s>, Error> {
+        Ok(Compound(self.0.dl()))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'s>, Error> {
+        Ok(Compound(self.variant(variant).into_child("dl".into())))
+    }
+}
+
+impl<'s> Compound<'s> {
+    /// Writes a `<li>`
+    fn item<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(Serializer(&mut self.0.li()))
+    }
+
+    /// Writes a `<dt>` and `<dd>`
+    fn field<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), Error> {
+        self.0.dt().text(key);
+        value.serialize(Serializer(&mut self.0.dd()))
+    }
+}
+
+impl<'s> ser::SerializeSeq for Compound<'s> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.item(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<'s> ser::SerializeTuple for Compound<'s> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.item(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<'s> ser::SerializeTupleStruct for Compound<'s> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.item(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<'s> ser::SerializeTupleVariant for Compound<'s> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.item(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<'s> ser::SerializeMap for Compound<'s> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        key.serialize(Serializer(&mut self.0.dt()))
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(Serializer(&mut self.0.dd()))
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<'s> ser::SerializeStruct for Compound<'s> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<'s> ser::SerializeStructVariant for Compound<'s> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
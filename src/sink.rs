@@ -0,0 +1,674 @@
+use crate::{escape, Format};
+use std::any::Any;
+use std::fmt::Write;
+use std::io;
+
+/// Somewhere for a [`Buffer`][crate::Buffer] to send its output.
+///
+/// A buffer doesn't write text directly: instead it calls the methods of its
+/// sink as elements are opened and closed and text is added.  The default
+/// sink is a `String`, which formats the output as HTML; by implementing
+/// this trait you can use the same builder API to produce something else,
+/// such as a byte stream, a stream of tokens, or a live DOM.  Use
+/// [`Buffer::with_sink()`][crate::Buffer::with_sink] to write into a
+/// custom sink.
+///
+/// `depth` is the number of elements enclosing the thing being written.
+///
+/// ```
+/// use html_builder::*;
+/// use std::fmt::Write;
+///
+/// /// Counts the elements in the document
+/// #[derive(Default)]
+/// struct Counter(usize);
+///
+/// impl Sink for Counter {
+///     fn start_tag(&mut self, _: usize, _: &str, _: &[Attribute]) {
+///         self.0 += 1;
+///     }
+///     fn void_tag(&mut self, _: usize, _: &str, _: &[Attribute], _: bool) {
+///         self.0 += 1;
+///     }
+///     fn end_tag(&mut self, _: usize, _: &str) {}
+///     fn text(&mut self, _: &str) {}
+///     fn raw(&mut self, _: &str) {}
+///     fn start_comment(&mut self, _: usize) {}
+///     fn end_comment(&mut self, _: usize) {}
+///     fn start_conditional(&mut self, _: usize, _: &str) {}
+///     fn end_conditional(&mut self, _: usize) {}
+/// }
+///
+/// let mut buf = Buffer::with_sink(Counter::default());
+/// let mut list = buf.ul();
+/// for i in 0..3 {
+///     writeln!(list.li(), "{}", i)?;
+/// }
+/// list.hr();
+/// assert_eq!(buf.into_sink::<Counter>().0, 5);
+/// # Ok::<(), std::fmt::Error>(())
+/// ```
+pub trait Sink: Send {
+    /// An element was opened
+    ///
+    /// This is only called once the element's attributes are known, which is
+    /// when its first child or text is written (or when its parent moves on
+    /// to something else).
+    fn start_tag(&mut self, depth: usize, tag: &str, attrs: &[Attribute]);
+
+    /// A void element was written
+    ///
+    /// `self_closing` is set if the tag should be written as `<tag/>`, as is
+    /// the case in XML documents.
+    fn void_tag(&mut self, depth: usize, tag: &str, attrs: &[Attribute], self_closing: bool);
+
+    /// An element was closed
+    fn end_tag(&mut self, depth: usize, tag: &str);
+
+    /// Text was written, which needs to be escaped
+    fn text(&mut self, text: &str);
+
+    /// Markup was written, which should be passed through verbatim
+    ///
+    /// This is used for nodes in [raw mode][crate::Node::raw], for text
+    /// which has already been escaped, and for the contents of comments.
+    fn raw(&mut self, html: &str);
+
+    /// A comment was opened; its contents will be passed to
+    /// [`raw()`][Sink::raw]
+    fn start_comment(&mut self, depth: usize);
+
+    /// A comment was closed
+    fn end_comment(&mut self, depth: usize);
+
+    /// A conditional comment was opened
+    fn start_conditional(&mut self, depth: usize, cond: &str);
+
+    /// A conditional comment was closed
+    fn end_conditional(&mut self, depth: usize);
+
+    /// The output so far should be sent on, as requested by
+    /// [`Node::flush()`][crate::Node::flush]
+    fn flush(&mut self) {}
+
+    /// The formatting options were set, with
+    /// [`Buffer::format()`][crate::Buffer::format]
+    ///
+    /// Sinks which don't write text can ignore this.
+    fn set_format(&mut self, _format: &Format) {}
+
+    /// The document is complete: everything has been closed
+    fn end(&mut self) {}
+}
+
+/// An attribute passed to a [`Sink`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Attribute {
+    /// An attribute added with [`attr()`][crate::Node::attr], which is
+    /// written verbatim
+    Raw(String),
+    /// A name and an unescaped value
+    Value(String, String),
+    /// A boolean attribute
+    Flag(String),
+}
+
+/// Formats the output as HTML.
+impl Sink for String {
+    fn start_tag(&mut self, depth: usize, tag: &str, attrs: &[Attribute]) {
+        open_tag(self, depth, tag);
+        write_attrs(self, attrs);
+        self.push_str(">\n");
+    }
+
+    fn void_tag(&mut self, depth: usize, tag: &str, attrs: &[Attribute], self_closing: bool) {
+        open_tag(self, depth, tag);
+        write_attrs(self, attrs);
+        self.push_str(if self_closing { "/>\n" } else { ">\n" });
+    }
+
+    fn end_tag(&mut self, depth: usize, tag: &str) {
+        open_tag(self, depth, "/");
+        self.push_str(tag);
+        self.push_str(">\n");
+    }
+
+    fn text(&mut self, text: &str) {
+        escape::escape_text(self, text);
+    }
+
+    fn raw(&mut self, html: &str) {
+        self.push_str(html);
+    }
+
+    fn start_comment(&mut self, depth: usize) {
+        open_tag(self, depth, "!-- ");
+    }
+
+    fn end_comment(&mut self, _depth: usize) {
+        self.push_str(" -->\n");
+    }
+
+    fn start_conditional(&mut self, depth: usize, cond: &str) {
+        open_tag(self, depth, "!--[");
+        self.push_str(cond);
+        self.push_str("]>\n");
+    }
+
+    fn end_conditional(&mut self, depth: usize) {
+        open_tag(self, depth, "![endif]-->\n");
+    }
+}
+
+/// Discards the output.
+///
+/// This is handy with [`Buffer::observe()`][crate::Buffer::observe], when
+/// you only want the events.
+impl Sink for () {
+    fn start_tag(&mut self, _depth: usize, _tag: &str, _attrs: &[Attribute]) {}
+    fn void_tag(&mut self, _depth: usize, _tag: &str, _attrs: &[Attribute], _self_closing: bool) {}
+    fn end_tag(&mut self, _depth: usize, _tag: &str) {}
+    fn text(&mut self, _text: &str) {}
+    fn raw(&mut self, _html: &str) {}
+    fn start_comment(&mut self, _depth: usize) {}
+    fn end_comment(&mut self, _depth: usize) {}
+    fn start_conditional(&mut self, _depth: usize, _cond: &str) {}
+    fn end_conditional(&mut self, _depth: usize) {}
+}
+
+/// Formats the output as HTML, like a `String`, following the buffer's
+/// [`Format`].  This is the default sink.
+#[derive(Clone, Default)]
+pub(crate) struct StringSink {
+    pub(crate) out: String,
+    pub(crate) format: Format,
+    /// Whether a comment is being left out of minified output
+    pub(crate) in_comment: bool,
+}
+
+impl Sink for StringSink {
+    fn start_tag(&mut self, depth: usize, tag: &str, attrs: &[Attribute]) {
+        if self.format.minify {
+            return write_minified(&mut self.out, tag, attrs, true, ">");
+        }
+        match self.format.wrap_attributes {
+            Some(width) => write_wrapped(&mut self.out, width, depth, tag, attrs, ">\n"),
+            None => self.out.start_tag(depth, tag, attrs),
+        }
+    }
+
+    fn void_tag(&mut self, depth: usize, tag: &str, attrs: &[Attribute], self_closing: bool) {
+        let end = if self_closing { "/>\n" } else { ">\n" };
+        if self.format.minify {
+            // An unquoted value would run into the `/`
+            return write_minified(&mut self.out, tag, attrs, !self_closing, end.trim_end());
+        }
+        match self.format.wrap_attributes {
+            Some(width) => write_wrapped(&mut self.out, width, depth, tag, attrs, end),
+            None => self.out.void_tag(depth, tag, attrs, self_closing),
+        }
+    }
+
+    fn end_tag(&mut self, depth: usize, tag: &str) {
+        if self.format.minify {
+            self.out.push_str("</");
+            self.out.push_str(tag);
+            self.out.push('>');
+        } else {
+            self.out.end_tag(depth, tag);
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        self.out.text(text);
+    }
+
+    fn raw(&mut self, html: &str) {
+        if !self.in_comment {
+            self.out.raw(html);
+        }
+    }
+
+    fn start_comment(&mut self, depth: usize) {
+        if self.format.minify {
+            self.in_comment = true;
+        } else {
+            self.out.start_comment(depth);
+        }
+    }
+
+    fn end_comment(&mut self, depth: usize) {
+        if self.format.minify {
+            self.in_comment = false;
+        } else {
+            self.out.end_comment(depth);
+        }
+    }
+
+    fn start_conditional(&mut self, depth: usize, cond: &str) {
+        if self.format.minify {
+            self.out.push_str("<!--[");
+            self.out.push_str(cond);
+            self.out.push_str("]>");
+        } else {
+            self.out.start_conditional(depth, cond);
+        }
+    }
+
+    fn end_conditional(&mut self, depth: usize) {
+        if self.format.minify {
+            self.out.push_str("<![endif]-->");
+        } else {
+            self.out.end_conditional(depth);
+        }
+    }
+
+    fn set_format(&mut self, format: &Format) {
+        self.format = format.clone();
+    }
+
+    fn end(&mut self) {
+        end_document(&mut self.out, &self.format);
+    }
+}
+
+/// The output of a `String` or [`StringSink`]
+pub(crate) fn text(wtr: &dyn Any) -> Option<&String> {
+    match wtr.downcast_ref::<StringSink>() {
+        Some(sink) => Some(&sink.out),
+        None => wtr.downcast_ref(),
+    }
+}
+
+/// The output of a `String` or [`StringSink`]
+pub(crate) fn text_mut(wtr: &mut dyn Any) -> Option<&mut String> {
+    if wtr.is::<String>() {
+        return wtr.downcast_mut();
+    }
+    wtr.downcast_mut::<StringSink>().map(|sink| &mut sink.out)
+}
+
+/// Copies a sink, if it's of the right type
+type CopySink = fn(&dyn Any) -> Option<Box<dyn AnySink>>;
+
+/// A copy of a sink, if it's one of the built-in sinks which can be copied
+pub(crate) fn try_clone(wtr: &dyn Any) -> Option<Box<dyn AnySink>> {
+    fn copy<S: Sink + Clone + 'static>(wtr: &dyn Any) -> Option<Box<dyn AnySink>> {
+        let sink: Box<dyn AnySink> = Box::new(wtr.downcast_ref::<S>()?.clone());
+        Some(sink)
+    }
+    let copies: &[CopySink] = &[
+        copy::<StringSink>,
+        copy::<String>,
+        copy::<SegmentSink>,
+        #[cfg(feature = "digest")]
+        copy::<crate::digest::DigestSink>,
+    ];
+    copies.iter().find_map(|copy| copy(wtr))
+}
+
+/// Adjusts the end of a finished document
+fn end_document(out: &mut String, format: &Format) {
+    if format.blank_line_at_end {
+        out.push('\n');
+    } else if !format.trailing_newline && out.ends_with('\n') {
+        out.pop();
+    }
+}
+
+/// Writes the indentation and `<`, followed by `rest`
+fn open_tag(out: &mut String, depth: usize, rest: &str) {
+    spaces(out, depth);
+    out.push('<');
+    out.push_str(rest);
+}
+
+/// Writes `n` spaces
+pub(crate) fn spaces(out: &mut String, n: usize) {
+    for _ in 0..n {
+        out.push(' ');
+    }
+}
+
+fn write_attrs(out: &mut String, attrs: &[Attribute]) {
+    for attr in attrs {
+        out.push(' ');
+        write_attr(out, attr);
+    }
+}
+
+fn write_attr(out: &mut String, attr: &Attribute) {
+    match attr {
+        Attribute::Raw(x) | Attribute::Flag(x) => out.push_str(x),
+        Attribute::Value(name, value) => {
+            out.push_str(name);
+            out.push_str("=\"");
+            escape::escape_attr(out, value);
+            out.push('"');
+        }
+    }
+}
+
+/// Writes a start tag with one attribute per line, if it would otherwise be
+/// wider than `width`.  `end` is the rest of the tag after the attributes.
+fn write_wrapped(
+    out: &mut String,
+    width: usize,
+    depth: usize,
+    tag: &str,
+    attrs: &[Attribute],
+    end: &str,
+) {
+    let start = out.len();
+    open_tag(out, depth, tag);
+    write_attrs(out, attrs);
+    let line = &out[start..];
+    if attrs.len() < 2 || line.chars().count() + end.trim_end().len() <= width {
+        out.push_str(end);
+        return;
+    }
+    out.truncate(start);
+    open_tag(out, depth, tag);
+    for attr in attrs {
+        out.push('\n');
+        spaces(out, depth + 2);
+        write_attr(out, attr);
+    }
+    out.push_str(end);
+}
+
+/// Writes a start tag without any whitespace that isn't needed.  `end` is
+/// the rest of the tag after the attributes.
+fn write_minified(out: &mut String, tag: &str, attrs: &[Attribute], unquote: bool, end: &str) {
+    out.push('<');
+    out.push_str(tag);
+    for attr in attrs {
+        out.push(' ');
+        match attr {
+            Attribute::Value(name, value) if unquote && can_unquote(value) => {
+                out.push_str(name);
+                out.push('=');
+                escape::escape_attr(out, value);
+            }
+            attr => write_attr(out, attr),
+        }
+    }
+    out.push_str(end);
+}
+
+/// Whether an attribute value can be written without quotes
+fn can_unquote(value: &str) -> bool {
+    !value.is_empty()
+        && !value.contains(|c: char| {
+            c.is_ascii_whitespace() || matches!(c, '"' | '\'' | '=' | '<' | '>' | '`')
+        })
+}
+
+/// How much output [`WriterSink`] collects before writing it out
+const WRITER_CHUNK: usize = 8 * 1024;
+
+/// Formats the output as HTML, like a `String`, and writes it to an
+/// `io::Write` in chunks.
+pub(crate) struct WriterSink<W> {
+    wtr: W,
+    buf: StringSink,
+    /// The first error returned by `wtr`; after this, nothing more is written
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> WriterSink<W> {
+    pub(crate) fn new(wtr: W) -> WriterSink<W> {
+        WriterSink {
+            wtr,
+            buf: StringSink {
+                out: String::with_capacity(WRITER_CHUNK),
+                format: Format::default(),
+                in_comment: false,
+            },
+            error: None,
+        }
+    }
+
+    fn write_all(&mut self) {
+        if self.error.is_none() {
+            if let Err(e) = self.wtr.write_all(self.buf.out.as_bytes()) {
+                self.error = Some(e);
+            }
+        }
+        self.buf.out.clear();
+    }
+
+    /// Writes out the output so far, except for a final newline, which is
+    /// held back in case it's the end of the document
+    fn write_out(&mut self) {
+        let newline = self.buf.out.ends_with('\n');
+        if newline {
+            self.buf.out.pop();
+        }
+        self.write_all();
+        if newline {
+            self.buf.out.push('\n');
+        }
+    }
+
+    fn maybe_write_out(&mut self) {
+        if self.buf.out.len() >= WRITER_CHUNK {
+            self.write_out();
+        }
+    }
+
+    /// Writes out anything remaining and flushes the writer
+    pub(crate) fn finish(mut self) -> io::Result<W> {
+        self.write_all();
+        match self.error {
+            Some(e) => Err(e),
+            None => self.wtr.flush().map(|()| self.wtr),
+        }
+    }
+}
+
+impl<W: io::Write + Send> Sink for WriterSink<W> {
+    fn start_tag(&mut self, depth: usize, tag: &str, attrs: &[Attribute]) {
+        self.buf.start_tag(depth, tag, attrs);
+        self.maybe_write_out();
+    }
+
+    fn void_tag(&mut self, depth: usize, tag: &str, attrs: &[Attribute], self_closing: bool) {
+        self.buf.void_tag(depth, tag, attrs, self_closing);
+        self.maybe_write_out();
+    }
+
+    fn end_tag(&mut self, depth: usize, tag: &str) {
+        self.buf.end_tag(depth, tag);
+        self.maybe_write_out();
+    }
+
+    fn text(&mut self, text: &str) {
+        self.buf.text(text);
+        self.maybe_write_out();
+    }
+
+    fn raw(&mut self, html: &str) {
+        self.buf.raw(html);
+        self.maybe_write_out();
+    }
+
+    fn start_comment(&mut self, depth: usize) {
+        self.buf.start_comment(depth);
+    }
+
+    fn end_comment(&mut self, depth: usize) {
+        self.buf.end_comment(depth);
+        self.maybe_write_out();
+    }
+
+    fn start_conditional(&mut self, depth: usize, cond: &str) {
+        self.buf.start_conditional(depth, cond);
+        self.maybe_write_out();
+    }
+
+    fn end_conditional(&mut self, depth: usize) {
+        self.buf.end_conditional(depth);
+        self.maybe_write_out();
+    }
+
+    fn set_format(&mut self, format: &Format) {
+        self.buf.set_format(format);
+    }
+
+    fn end(&mut self) {
+        self.buf.end();
+    }
+
+    fn flush(&mut self) {
+        // The final newline only needs holding back if it'll be removed
+        if self.buf.format.trailing_newline {
+            self.write_all();
+        } else {
+            self.write_out();
+        }
+        if self.error.is_none() {
+            if let Err(e) = self.wtr.flush() {
+                self.error = Some(e);
+            }
+        }
+    }
+}
+
+/// Formats the output as HTML, like a `String`, but into a list of
+/// segments rather than one contiguous allocation.
+#[derive(Clone)]
+pub(crate) struct SegmentSink {
+    segments: Vec<String>,
+    buf: StringSink,
+    size: usize,
+}
+
+impl SegmentSink {
+    pub(crate) fn new(size: usize) -> SegmentSink {
+        SegmentSink {
+            segments: vec![],
+            buf: StringSink {
+                out: String::with_capacity(size),
+                format: Format::default(),
+                in_comment: false,
+            },
+            size,
+        }
+    }
+
+    fn maybe_seal(&mut self) {
+        if self.buf.out.len() >= self.size {
+            let next = String::with_capacity(self.size);
+            self.segments
+                .push(std::mem::replace(&mut self.buf.out, next));
+        }
+    }
+
+    /// The number of bytes written so far
+    pub(crate) fn len(&self) -> usize {
+        self.segments.iter().map(String::len).sum::<usize>() + self.buf.out.len()
+    }
+
+    /// Everything written so far
+    pub(crate) fn text(&self) -> String {
+        let mut text = self.segments.concat();
+        text.push_str(&self.buf.out);
+        text
+    }
+
+    pub(crate) fn finish(mut self) -> Vec<String> {
+        if !self.buf.out.is_empty() {
+            self.segments.push(self.buf.out);
+        }
+        self.segments
+    }
+}
+
+impl Sink for SegmentSink {
+    fn start_tag(&mut self, depth: usize, tag: &str, attrs: &[Attribute]) {
+        self.buf.start_tag(depth, tag, attrs);
+        self.maybe_seal();
+    }
+
+    fn void_tag(&mut self, depth: usize, tag: &str, attrs: &[Attribute], self_closing: bool) {
+        self.buf.void_tag(depth, tag, attrs, self_closing);
+        self.maybe_seal();
+    }
+
+    fn end_tag(&mut self, depth: usize, tag: &str) {
+        self.buf.end_tag(depth, tag);
+        self.maybe_seal();
+    }
+
+    fn text(&mut self, text: &str) {
+        self.buf.text(text);
+        self.maybe_seal();
+    }
+
+    fn raw(&mut self, html: &str) {
+        self.buf.raw(html);
+        self.maybe_seal();
+    }
+
+    fn start_comment(&mut self, depth: usize) {
+        self.buf.start_comment(depth);
+    }
+
+    fn end_comment(&mut self, depth: usize) {
+        self.buf.end_comment(depth);
+        self.maybe_seal();
+    }
+
+    fn start_conditional(&mut self, depth: usize, cond: &str) {
+        self.buf.start_conditional(depth, cond);
+        self.maybe_seal();
+    }
+
+    fn end_conditional(&mut self, depth: usize) {
+        self.buf.end_conditional(depth);
+        self.maybe_seal();
+    }
+
+    fn set_format(&mut self, format: &Format) {
+        self.buf.set_format(format);
+    }
+
+    fn end(&mut self) {
+        match self.segments.last_mut() {
+            Some(last) if self.buf.out.is_empty() => end_document(last, &self.buf.format),
+            _ => self.buf.end(),
+        }
+    }
+}
+
+/// Lets the buffer get its sink back out.
+pub(crate) trait AnySink: Sink {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<S: Sink + 'static> AnySink for S {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+/// Adapts a sink's [`raw()`][Sink::raw] method to `fmt::Write`.
+pub(crate) struct RawWriter<'a>(pub(crate) &'a mut dyn AnySink);
+
+impl<'a> Write for RawWriter<'a> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.0.raw(s);
+        Ok(())
+    }
+}
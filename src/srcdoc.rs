@@ -0,0 +1,36 @@
+//! Embedding documents in iframes
+
+use crate::{Buffer, Html5, IframeElement, Node};
+
+impl<'a> Node<'a> {
+    /// Writes an `<iframe>` whose content is a document written by `f`
+    ///
+    /// `f` writes a complete document into a new buffer, which has the same
+    /// [format][Buffer::format] as this one.  The document is set as the
+    /// `srcdoc` attribute, escaped like any other attribute value, so its
+    /// markup and its own escaped text both survive intact.  This is useful
+    /// for previewing untrusted content; remember to set
+    /// [`sandbox()`][IframeElement::sandbox] as well.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new();
+    /// buf.iframe_srcdoc(|doc| {
+    ///     doc.p().text("Fish & chips");
+    /// })
+    /// .sandbox(Sandbox::NONE);
+    /// assert_eq!(buf.finish(), "\
+    /// <iframe srcdoc=\"&lt;p&gt;\nFish &amp;amp; chips\n&lt;/p&gt;\" sandbox=\"\">
+    /// </iframe>
+    /// ");
+    /// ```
+    #[cfg_attr(feature = "source-locations", track_caller)]
+    pub fn iframe_srcdoc(&mut self, f: impl FnOnce(&mut Buffer)) -> IframeElement<'_> {
+        let format = self.ctx.get_ref().policies.format.clone();
+        let mut doc = Buffer::new().format(format.trailing_newline(false));
+        f(&mut doc);
+        let srcdoc = doc.finish();
+        self.iframe().srcdoc(srcdoc)
+    }
+}
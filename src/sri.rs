@@ -0,0 +1,102 @@
+//! Subresource integrity
+
+use crate::{Html5, LinkElement, Node, ScriptElement};
+use std::fmt;
+
+/// The expected digest of a subresource, for the `integrity` attribute.
+///
+/// Either compute it from the resource's contents with
+/// [`Integrity::sha384()`] (available with the `sri` feature), or convert a
+/// precomputed value such as `"sha384-oqVuAfXRKap7fdgcCY5uykM6+R9GqQ8K/uxy9rx7HNQlGYl1kPzQho1wx4JwY8wC"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Integrity(String);
+
+impl Integrity {
+    /// Computes the SHA-384 digest of the resource's contents
+    ///
+    /// This method is only available with the `sri` feature.
+    #[cfg(feature = "sri")]
+    pub fn sha384(contents: impl AsRef<[u8]>) -> Integrity {
+        use base64::Engine;
+        use sha2::Digest;
+        let digest = sha2::Sha384::digest(contents.as_ref());
+        let digest = base64::engine::general_purpose::STANDARD.encode(digest);
+        Integrity(format!("sha384-{}", digest))
+    }
+}
+
+impl From<&str> for Integrity {
+    fn from(x: &str) -> Integrity {
+        Integrity(x.to_string())
+    }
+}
+
+impl From<String> for Integrity {
+    fn from(x: String) -> Integrity {
+        Integrity(x)
+    }
+}
+
+impl fmt::Display for Integrity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<'a> Node<'a> {
+    /// Writes a `<script>` for a pinned third-party script
+    ///
+    /// The `integrity` attribute is set, so the browser refuses to run the
+    /// script if it has been tampered with, and `crossorigin="anonymous"`,
+    /// which is needed for the check to work on other origins.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new();
+    /// buf.script_sri("https://cdn.example.com/lib.js", "sha384-abc").defer(true);
+    /// assert_eq!(buf.finish(), "\
+    /// <script src=\"https://cdn.example.com/lib.js\" integrity=\"sha384-abc\" crossorigin=\"anonymous\" defer>
+    /// </script>
+    /// ");
+    /// ```
+    pub fn script_sri(
+        &mut self,
+        url: impl fmt::Display,
+        integrity: impl Into<Integrity>,
+    ) -> ScriptElement<'_> {
+        self.script()
+            .src(url)
+            .integrity(integrity.into())
+            .crossorigin("anonymous")
+    }
+
+    /// Writes a `<link rel="stylesheet">` for a pinned third-party
+    /// stylesheet
+    ///
+    /// See [`script_sri()`][Node::script_sri].
+    ///
+    /// ```
+    /// # #[cfg(feature = "sri")] {
+    /// use html_builder::*;
+    ///
+    /// let css = "body { margin: 0 }";
+    /// let mut buf = Buffer::new();
+    /// buf.stylesheet_sri("https://cdn.example.com/reset.css", Integrity::sha384(css));
+    /// assert_eq!(buf.finish(), "\
+    /// <link rel=\"stylesheet\" href=\"https://cdn.example.com/reset.css\" integrity=\"sha384-LqROt08EVeMth/KLSsFLNEYyDxdxkMNHUVSlR1OFXlOstklYqxMOwTAUvGyhIq1U\" crossorigin=\"anonymous\">
+    /// ");
+    /// # }
+    /// ```
+    pub fn stylesheet_sri(
+        &mut self,
+        url: impl fmt::Display,
+        integrity: impl Into<Integrity>,
+    ) -> LinkElement<'_> {
+        self.link()
+            .rel("stylesheet")
+            .href(url)
+            .integrity(integrity.into())
+            .crossorigin("anonymous")
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{
+    sink::{self, SegmentSink},
+    Buffer,
+};
+use std::collections::BTreeMap;
+
+/// Statistics about a buffer's output, from [`Buffer::stats()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// The number of bytes written so far, or `None` if the buffer writes
+    /// to a custom sink
+    pub bytes: Option<usize>,
+    /// The number of elements
+    pub elements: usize,
+    /// The depth of the most deeply nested element; top-level elements
+    /// have depth 1
+    pub max_depth: usize,
+    /// The number of elements with each tag
+    pub tags: BTreeMap<String, usize>,
+}
+
+impl Stats {
+    /// Counts an element which has been opened at `depth`
+    pub(crate) fn record(&mut self, tag: &str, depth: usize) {
+        self.elements += 1;
+        self.max_depth = self.max_depth.max(depth);
+        match self.tags.get_mut(tag) {
+            Some(count) => *count += 1,
+            None => {
+                self.tags.insert(tag.to_string(), 1);
+            }
+        }
+    }
+
+    /// Adds the counts from `other`, whose depths are relative to `base`
+    pub(crate) fn merge(&mut self, other: &Stats, base: usize) {
+        if other.elements == 0 {
+            return;
+        }
+        self.elements += other.elements;
+        self.max_depth = self.max_depth.max(base + other.max_depth);
+        for (tag, count) in &other.tags {
+            *self.tags.entry(tag.clone()).or_insert(0) += count;
+        }
+    }
+}
+
+impl Buffer {
+    /// Returns statistics about the output so far.
+    ///
+    /// This is cheap: the counts are kept up to date as elements are
+    /// written.  Elements which have been opened but not yet written are
+    /// included in the counts, but not in `bytes`.
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new();
+    /// let mut ul = buf.ul();
+    /// ul.li().text("One");
+    /// ul.li().text("Two");
+    /// let stats = buf.stats();
+    /// assert_eq!(stats.elements, 3);
+    /// assert_eq!(stats.max_depth, 2);
+    /// assert_eq!(stats.tags["li"], 2);
+    /// assert_eq!(stats.bytes, Some(32));
+    /// ```
+    pub fn stats(&self) -> Stats {
+        let ctx = self.node.ctx.get_ref();
+        let wtr = ctx.sink();
+        let bytes = match sink::text(wtr) {
+            Some(text) => Some(text.len()),
+            None => wtr.downcast_ref::<SegmentSink>().map(SegmentSink::len),
+        };
+        Stats {
+            bytes,
+            ..ctx.stats.clone()
+        }
+    }
+}
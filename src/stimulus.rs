@@ -0,0 +1,93 @@
+use crate::GlobalAttributes;
+use std::fmt::Display;
+
+/// Setters for the attributes used by [Stimulus](https://stimulus.hotwired.dev/).
+///
+/// Stimulus connects JavaScript controllers to elements via `data-*`
+/// attributes with particular naming conventions; these setters take care
+/// of them.  Value and class names are given in camelCase, as they are in
+/// the controller, and converted to kebab-case for the attribute name.
+///
+/// ```
+/// use html_builder::*;
+///
+/// let mut buf = Buffer::new();
+/// let mut div = buf
+///     .div()
+///     .stimulus_controller("clipboard")
+///     .stimulus_value("clipboard", "successDuration", 2000)
+///     .stimulus_class("clipboard", "copied", "text-green");
+/// div.input().stimulus_target("clipboard", "source").value("PIN-1234");
+/// div.button()
+///     .stimulus_action("clipboard#copy")
+///     .text("Copy");
+/// assert_eq!(buf.finish(), "\
+/// <div data-controller=\"clipboard\" data-clipboard-success-duration-value=\"2000\" data-clipboard-copied-class=\"text-green\">
+///  <input data-clipboard-target=\"source\" value=\"PIN-1234\">
+///  <button data-action=\"clipboard#copy\">
+/// Copy
+///  </button>
+/// </div>
+/// ");
+/// ```
+///
+/// This trait is implemented for everything which implements
+/// [`GlobalAttributes`].
+pub trait Stimulus: GlobalAttributes {
+    /// Attaches controllers to the element (`data-controller`)
+    ///
+    /// Several controllers can be given, separated by spaces.
+    fn stimulus_controller(self, identifiers: impl Display) -> Self {
+        self.attr_value("data-controller", identifiers)
+    }
+
+    /// Connects events to controller methods (`data-action`), eg.
+    /// `click->gallery#next`
+    ///
+    /// Several actions can be given, separated by spaces.
+    fn stimulus_action(self, actions: impl Display) -> Self {
+        self.attr_value("data-action", actions)
+    }
+
+    /// Marks the element as a target of the controller
+    /// (`data-{controller}-target`)
+    fn stimulus_target(self, controller: &str, name: impl Display) -> Self {
+        self.attr_value(&format!("data-{}-target", controller), name)
+    }
+
+    /// Sets a value of the controller (`data-{controller}-{name}-value`)
+    fn stimulus_value(self, controller: &str, name: &str, value: impl Display) -> Self {
+        let attr = format!("data-{}-{}-value", controller, kebab_case(name));
+        self.attr_value(&attr, value)
+    }
+
+    /// Sets a CSS class used by the controller
+    /// (`data-{controller}-{name}-class`)
+    fn stimulus_class(self, controller: &str, name: &str, class: impl Display) -> Self {
+        let attr = format!("data-{}-{}-class", controller, kebab_case(name));
+        self.attr_value(&attr, class)
+    }
+
+    /// Connects the controller to other controllers
+    /// (`data-{controller}-{name}-outlet`), given a CSS selector
+    fn stimulus_outlet(self, controller: &str, name: &str, selector: impl Display) -> Self {
+        let attr = format!("data-{}-{}-outlet", controller, name);
+        self.attr_value(&attr, selector)
+    }
+}
+
+impl<T: GlobalAttributes> Stimulus for T {}
+
+/// Converts `camelCase` to `kebab-case`
+fn kebab_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            out.push('-');
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
@@ -0,0 +1,161 @@
+//! Streaming output to async consumers
+
+use crate::Buffer;
+use bytes::Bytes;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+/// The output of a streaming [`Buffer`], as a stream of chunks.
+///
+/// This is created by [`Buffer::streaming()`], and is only available with
+/// the `tokio` feature.  It implements `Stream<Item = Bytes>`, or you can
+/// use [`write_to()`][HtmlStream::write_to] to copy it into an
+/// `AsyncWrite`.
+pub struct HtmlStream {
+    rx: UnboundedReceiver<Bytes>,
+}
+
+/// Sends everything written to it down a channel
+struct ChannelWriter(UnboundedSender<Bytes>);
+
+impl io::Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .send(Bytes::copy_from_slice(buf))
+            .map_err(|_| io::ErrorKind::BrokenPipe)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Buffer {
+    /// Creates a new empty buffer whose output can be consumed while it's
+    /// still being written.
+    ///
+    /// This method is only available with the `tokio` feature.  Output is
+    /// sent to the [`HtmlStream`] in chunks as it's produced; call
+    /// [`flush()`][crate::Node::flush] to send what's been written so far
+    /// straight away, eg. before doing something slow.  The stream ends when
+    /// [`finish_stream()`][Buffer::finish_stream] is called.
+    ///
+    /// Writing never waits for the consumer, so if the consumer is slower
+    /// than the producer the chunks will queue up in memory.
+    /// [Placeholders][crate::Node::placeholder] can't be used with this kind
+    /// of buffer.
+    ///
+    /// ```
+    /// use html_builder::*;
+    /// use std::fmt::Write;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let (mut buf, stream) = Buffer::streaming();
+    /// let consumer = tokio::spawn(async move {
+    ///     let mut out = Vec::new();
+    ///     stream.write_to(&mut out).await.unwrap();
+    ///     out
+    /// });
+    /// let mut html = buf.html();
+    /// writeln!(html.head().title(), "Report")?;
+    /// html.flush(); // The consumer can now send the <head>
+    /// writeln!(html.body(), "Lots of data")?;
+    /// buf.finish_stream();
+    /// let out = consumer.await.unwrap();
+    /// assert!(out.starts_with(b"<html>\n <head>\n  <title>\n"));
+    /// # Ok::<(), std::fmt::Error>(())
+    /// # }).unwrap();
+    /// ```
+    pub fn streaming() -> (Buffer, HtmlStream) {
+        let (tx, rx) = unbounded_channel();
+        (Buffer::to_writer(ChannelWriter(tx)), HtmlStream { rx })
+    }
+
+    /// Closes all open tags, sends any remaining output, and ends the
+    /// stream.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the buffer wasn't created by
+    /// [`streaming()`][Buffer::streaming].
+    pub fn finish_stream(self) {
+        // The only possible error is that the stream was dropped, in which
+        // case nobody is interested in the output
+        let _ = self.finish_writer::<ChannelWriter>();
+    }
+}
+
+impl HtmlStream {
+    /// Copies the chunks into `wtr` as they arrive, until the stream ends.
+    pub async fn write_to<W: AsyncWrite + Unpin + ?Sized>(mut self, wtr: &mut W) -> io::Result<()> {
+        while let Some(chunk) = self.rx.recv().await {
+            wtr.write_all(&chunk).await?;
+            wtr.flush().await?;
+        }
+        Ok(())
+    }
+}
+
+/// Turns a finished buffer into a stream with a single chunk.
+impl From<Buffer> for HtmlStream {
+    fn from(buf: Buffer) -> HtmlStream {
+        let (tx, rx) = unbounded_channel();
+        let _ = tx.send(Bytes::from(buf.finish()));
+        HtmlStream { rx }
+    }
+}
+
+impl futures_core::Stream for HtmlStream {
+    type Item = Bytes;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Bytes>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+/// An [`HtmlStream`] as a stream of `Result`s, as expected by web frameworks
+#[cfg(any(feature = "actix", feature = "axum"))]
+pub(crate) struct TryChunks(pub(crate) HtmlStream);
+
+#[cfg(any(feature = "actix", feature = "axum"))]
+impl futures_core::Stream for TryChunks {
+    type Item = Result<Bytes, std::convert::Infallible>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0.rx.poll_recv(cx).map(|chunk| chunk.map(Ok))
+    }
+}
+
+/// Serves the output as an HTTP body, for use with hyper.
+///
+/// This is only available with the `http-body` feature.  A finished
+/// [`Buffer`] can be served by converting it into an `HtmlStream` first.
+///
+/// ```
+/// use html_builder::*;
+/// use std::fmt::Write;
+///
+/// fn page() -> impl http_body::Body<Data = bytes::Bytes> {
+///     let mut buf = Buffer::new();
+///     writeln!(buf.p(), "Hello!").unwrap();
+///     HtmlStream::from(buf)
+/// }
+/// ```
+#[cfg(feature = "http-body")]
+impl http_body::Body for HtmlStream {
+    type Data = Bytes;
+    type Error = std::convert::Infallible;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<http_body::Frame<Bytes>, Self::Error>>> {
+        self.rx
+            .poll_recv(cx)
+            .map(|chunk| chunk.map(|x| Ok(http_body::Frame::data(x))))
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{Buffer, Ctx, GlobalAttributes, Node};
+use std::borrow::Cow;
+
+/// The SVG namespace
+const SVG_NS: &str = "http://www.w3.org/2000/svg";
+
+macro_rules! svg_elements {
+    ($($(#[$m:meta])* $method:ident => $tag:literal,)*) => {
+        /// Helper methods for generating SVG.
+        ///
+        /// These work both for inline SVG, inside an HTML
+        /// [`svg()`][crate::Html5::svg] element, and for standalone SVG files
+        /// written with an [`SvgBuffer`].
+        ///
+        /// SVG elements have no void elements, so shapes are written with
+        /// an explicit end tag; they can contain a `<title>` or animation
+        /// elements.
+        pub trait SvgElements {
+            $(
+                $(#[$m])*
+                fn $method(&mut self) -> Node<'_>;
+            )*
+        }
+
+        impl<'a> SvgElements for Node<'a> {
+            $(
+                #[cfg_attr(feature = "source-locations", track_caller)]
+                fn $method(&mut self) -> Node<'_> {
+                    self.child(Cow::Borrowed($tag))
+                }
+            )*
+        }
+    };
+}
+
+svg_elements! {
+    /// Defines a group of elements
+    g => "g",
+    /// Defines elements to be referenced later, without drawing them
+    defs => "defs",
+    /// Defines a template to be instantiated with `<use>`
+    symbol => "symbol",
+    /// Draws a copy of another element
+    use_ => "use",
+    /// Draws a rectangle
+    rect => "rect",
+    /// Draws a circle
+    circle => "circle",
+    /// Draws an ellipse
+    ellipse => "ellipse",
+    /// Draws a straight line
+    line => "line",
+    /// Draws a series of connected straight lines
+    polyline => "polyline",
+    /// Draws a closed shape made of straight lines
+    polygon => "polygon",
+    /// Draws an arbitrary shape
+    path => "path",
+    /// Draws text (the `<text>` element)
+    text_element => "text",
+    /// Defines a span of text within a `<text>` element
+    tspan => "tspan",
+    /// Draws a raster image
+    image => "image",
+    /// Defines a linear gradient
+    linear_gradient => "linearGradient",
+    /// Defines a radial gradient
+    radial_gradient => "radialGradient",
+    /// Defines a colour stop of a gradient
+    stop => "stop",
+    /// Defines a clipping path
+    clip_path => "clipPath",
+    /// Defines a mask
+    mask => "mask",
+    /// Defines a pattern for filling shapes
+    pattern => "pattern",
+    /// Defines a marker for the vertices of a line
+    marker => "marker",
+    /// Defines a description of the element
+    desc => "desc",
+    /// Embeds content from another namespace, such as HTML
+    foreign_object => "foreignObject",
+}
+
+/// A buffer for writing a standalone SVG file.
+///
+/// The file starts with an XML declaration, followed by an `<svg>` element
+/// with the SVG namespace, and the given size and `viewBox`.  The buffer
+/// derefs to the `<svg>` element, so shapes can be added to it directly.
+/// Like an [`XmlBuffer`][crate::XmlBuffer], the output is well-formed XML.
+///
+/// ```
+/// use html_builder::*;
+///
+/// let mut buf = SvgBuffer::new(90, 20);
+/// buf.rect().attr_value("width", 90).attr_value("height", 20).attr_value("fill", "#4c1");
+/// buf.text_element()
+///     .attr_value("x", 45)
+///     .attr_value("y", 14)
+///     .attr_value("text-anchor", "middle")
+///     .text("passing");
+/// assert_eq!(buf.finish(), "\
+/// <?xml version=\"1.0\" encoding=\"UTF-8\"?>
+/// <svg xmlns=\"http://www.w3.org/2000/svg\" width=\"90\" height=\"20\" viewBox=\"0 0 90 20\">
+///  <rect width=\"90\" height=\"20\" fill=\"#4c1\">
+///  </rect>
+///  <text x=\"45\" y=\"14\" text-anchor=\"middle\">
+/// passing
+///  </text>
+/// </svg>
+/// ");
+/// ```
+pub struct SvgBuffer {
+    buf: Buffer,
+}
+
+impl SvgBuffer {
+    /// Creates a new SVG document with the given size
+    pub fn new(width: impl Into<f64>, height: impl Into<f64>) -> SvgBuffer {
+        let (width, height) = (width.into(), height.into());
+        let mut ctx = Ctx {
+            xml: true,
+            ..Ctx::default()
+        };
+        ctx.wtr.raw(crate::xml::DECLARATION);
+        let mut buf = Buffer::from_ctx(ctx);
+        buf.child(Cow::Borrowed("svg"))
+            .attr_value("xmlns", SVG_NS)
+            .attr_value("width", width)
+            .attr_value("height", height)
+            .attr_value("viewBox", format!("0 0 {} {}", width, height));
+        // The buffer's root node becomes the `<svg>` element
+        buf.node.depth = 1;
+        SvgBuffer { buf }
+    }
+
+    /// Closes all open tags and returns the document.
+    pub fn finish(self) -> String {
+        self.buf.finish()
+    }
+}
+
+impl std::ops::Deref for SvgBuffer {
+    type Target = Node<'static>;
+    fn deref(&self) -> &Node<'static> {
+        &self.buf
+    }
+}
+
+impl std::ops::DerefMut for SvgBuffer {
+    fn deref_mut(&mut self) -> &mut Node<'static> {
+        &mut self.buf
+    }
+}
@@ -0,0 +1,242 @@
+use crate::{GlobalAttributes, Html5, Node};
+use std::fmt::Display;
+
+/// Types which can be shown as rows of a table.
+///
+/// With the `macros` feature this can be derived for structs with named
+/// fields: there's a column for each field, headed by the field's name, and
+/// the cells contain the fields' `Display` output.  Use
+/// `#[html(rename = "...")]` on a field to change its heading, and
+/// `#[html(skip)]` to leave it out.
+///
+/// ```
+/// use html_builder::*;
+///
+/// struct User {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// impl ToHtmlTable for User {
+///     const HEADINGS: &'static [&'static str] = &["Name", "Age"];
+///
+///     fn write_cells(&self, tr: &mut Node) {
+///         tr.td_text(&self.name).td_text(self.age);
+///     }
+/// }
+///
+/// let users = [User { name: "Alice".into(), age: 30 }];
+/// let mut buf = Buffer::new();
+/// User::to_html_table(&users, &mut buf);
+/// assert_eq!(buf.finish(), "\
+/// <table>
+///  <thead>
+///   <tr>
+///    <th>
+/// Name
+///    </th>
+///    <th>
+/// Age
+///    </th>
+///   </tr>
+///  </thead>
+///  <tbody>
+///   <tr>
+///    <td>
+/// Alice
+///    </td>
+///    <td>
+/// 30
+///    </td>
+///   </tr>
+///  </tbody>
+/// </table>
+/// ");
+/// ```
+pub trait ToHtmlTable {
+    /// The text of the column headings
+    const HEADINGS: &'static [&'static str];
+
+    /// Writes the `<td>`s for this value
+    fn write_cells(&self, tr: &mut Node);
+
+    /// Writes a `<table>` with a row for each value
+    fn to_html_table(rows: &[Self], parent: &mut Node)
+    where
+        Self: Sized,
+    {
+        let mut table = parent.table();
+        let mut thead = table.thead();
+        let mut tr = thead.tr();
+        for heading in Self::HEADINGS {
+            tr.th_text(heading);
+        }
+        let mut tbody = table.tbody();
+        for row in rows {
+            row.write_cells(&mut tbody.tr());
+        }
+    }
+}
+
+/// Writes a table from column definitions and an iterator of rows.
+///
+/// Each column has a heading and a closure which writes its cell for a
+/// given row into the `<td>`.  A column can also have a class, which is
+/// set on its `<th>` and `<td>`s.  The rows are written as they're
+/// produced by the iterator, so they don't need to be collected first.
+///
+/// ```
+/// use html_builder::*;
+///
+/// struct Item {
+///     name: &'static str,
+///     price: u32,
+/// }
+///
+/// let items = vec![Item { name: "Tea", price: 3 }, Item { name: "Cake", price: 4 }];
+/// let total: u32 = items.iter().map(|item| item.price).sum();
+/// let table = TableBuilder::new()
+///     .column("Item", |td, item: &Item| {
+///         td.text(item.name);
+///     })
+///     .column("Price", |td, item: &Item| {
+///         td.text(item.price);
+///     })
+///     .class("numeric")
+///     .footer(move |tr| {
+///         tr.th_text("Total").td().class("numeric").text(total);
+///     });
+/// let mut buf = Buffer::new();
+/// table.write(&mut buf, items);
+/// assert_eq!(buf.finish(), "\
+/// <table>
+///  <thead>
+///   <tr>
+///    <th>
+/// Item
+///    </th>
+///    <th class=\"numeric\">
+/// Price
+///    </th>
+///   </tr>
+///  </thead>
+///  <tbody>
+///   <tr>
+///    <td>
+/// Tea
+///    </td>
+///    <td class=\"numeric\">
+/// 3
+///    </td>
+///   </tr>
+///   <tr>
+///    <td>
+/// Cake
+///    </td>
+///    <td class=\"numeric\">
+/// 4
+///    </td>
+///   </tr>
+///  </tbody>
+///  <tfoot>
+///   <tr>
+///    <th>
+/// Total
+///    </th>
+///    <td class=\"numeric\">
+/// 7
+///    </td>
+///   </tr>
+///  </tfoot>
+/// </table>
+/// ");
+/// ```
+pub struct TableBuilder<'f, T> {
+    columns: Vec<Column<'f, T>>,
+    footer: Option<WriteFn<'f>>,
+}
+
+/// Writes the contents of an element
+type WriteFn<'f> = Box<dyn Fn(&mut Node) + 'f>;
+
+/// Writes the contents of a cell for the given row
+type CellFn<'f, T> = Box<dyn Fn(&mut Node, &T) + 'f>;
+
+struct Column<'f, T> {
+    heading: String,
+    class: Option<String>,
+    cell: CellFn<'f, T>,
+}
+
+impl<'f, T> TableBuilder<'f, T> {
+    /// Creates a table with no columns
+    pub fn new() -> Self {
+        TableBuilder {
+            columns: vec![],
+            footer: None,
+        }
+    }
+
+    /// Adds a column
+    ///
+    /// `cell` writes the column's cell for a row into the `<td>`.
+    pub fn column(mut self, heading: impl Display, cell: impl Fn(&mut Node, &T) + 'f) -> Self {
+        self.columns.push(Column {
+            heading: heading.to_string(),
+            class: None,
+            cell: Box::new(cell),
+        });
+        self
+    }
+
+    /// Sets the class of the most recently added column
+    ///
+    /// ## Panics
+    ///
+    /// Panics if no columns have been added.
+    pub fn class(mut self, class: impl Display) -> Self {
+        let column = self.columns.last_mut().expect("no columns");
+        column.class = Some(class.to_string());
+        self
+    }
+
+    /// Adds a `<tfoot>`; `footer` writes the contents of its `<tr>`
+    pub fn footer(mut self, footer: impl Fn(&mut Node) + 'f) -> Self {
+        self.footer = Some(Box::new(footer));
+        self
+    }
+
+    /// Writes the `<table>`, with a row for each item of `rows`
+    pub fn write(&self, parent: &mut Node, rows: impl IntoIterator<Item = T>) {
+        let mut table = parent.table();
+        let mut thead = table.thead();
+        let mut tr = thead.tr();
+        for column in &self.columns {
+            let mut th = tr.th();
+            if let Some(class) = &column.class {
+                th = th.class(class);
+            }
+            th.text(&column.heading);
+        }
+        let mut tbody = table.tbody();
+        for row in rows {
+            let mut tr = tbody.tr();
+            for column in &self.columns {
+                let mut td = tr.td();
+                if let Some(class) = &column.class {
+                    td = td.class(class);
+                }
+                (column.cell)(&mut td, &row);
+            }
+        }
+        if let Some(footer) = &self.footer {
+            footer(&mut table.tfoot().tr());
+        }
+    }
+}
+
+impl<'f, T> Default for TableBuilder<'f, T> {
+    fn default() -> Self {
+        TableBuilder::new()
+    }
+}
@@ -0,0 +1,76 @@
+//! The names of open elements
+
+use std::borrow::Cow;
+use std::ops::Deref;
+
+/// The longest name which is stored inline
+const INLINE: usize = 22;
+
+/// The name of an element which has been opened.
+///
+/// Most names are string literals.  The others, such as those of custom
+/// elements or of a parsed [`Tree`][crate::Tree], are almost always short,
+/// so they're kept inline rather than in an allocation of their own.  This
+/// also makes copying the stack, eg. for a placeholder, cheap.
+#[derive(Clone)]
+pub(crate) enum Tag {
+    Static(&'static str),
+    Inline(u8, [u8; INLINE]),
+    Heap(Box<str>),
+}
+
+impl Tag {
+    fn copy(tag: &str) -> Tag {
+        if tag.len() > INLINE {
+            return Tag::Heap(tag.into());
+        }
+        let mut buf = [0; INLINE];
+        buf[..tag.len()].copy_from_slice(tag.as_bytes());
+        Tag::Inline(tag.len() as u8, buf)
+    }
+}
+
+impl From<Cow<'static, str>> for Tag {
+    fn from(tag: Cow<'static, str>) -> Tag {
+        match tag {
+            Cow::Borrowed(tag) => Tag::Static(tag),
+            Cow::Owned(tag) if tag.len() > INLINE => Tag::Heap(tag.into_boxed_str()),
+            Cow::Owned(tag) => Tag::copy(&tag),
+        }
+    }
+}
+
+/// Copies a name, unless it's `'static`
+impl From<&Cow<'static, str>> for Tag {
+    fn from(tag: &Cow<'static, str>) -> Tag {
+        match tag {
+            Cow::Borrowed(tag) => Tag::Static(tag),
+            Cow::Owned(tag) => Tag::copy(tag),
+        }
+    }
+}
+
+impl From<String> for Tag {
+    fn from(tag: String) -> Tag {
+        Tag::from(Cow::Owned(tag))
+    }
+}
+
+impl Deref for Tag {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match self {
+            Tag::Static(tag) => tag,
+            // It was copied from a `str`
+            Tag::Inline(len, buf) => std::str::from_utf8(&buf[..*len as usize]).unwrap(),
+            Tag::Heap(tag) => tag,
+        }
+    }
+}
+
+impl AsRef<str> for Tag {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
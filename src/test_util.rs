@@ -0,0 +1,332 @@
+//! Helpers for testing code which generates HTML
+
+use crate::{Content, Element, Parent, Tree};
+use regex::Regex;
+use std::fmt;
+
+/// Asserts that two pieces of HTML are the same, ignoring insignificant
+/// differences.
+///
+/// This macro is only available with the `test-util` feature.  Both sides
+/// are parsed with [html5ever] and written out again before they're
+/// compared, so that:
+///
+/// * whitespace at the start and end of an element, and next to
+///   block-level elements such as `<p>` or `<li>`, is ignored;
+/// * runs of whitespace are treated as a single space, so `<b>a</b> <i>b</i>`
+///   differs from `<b>a</b><i>b</i>`;
+/// * the order of an element's attributes doesn't matter;
+/// * tags which the parser would infer (eg. a missing `</li>`) can be left
+///   out.
+///
+/// The contents of `<pre>`, `<textarea>`, `<script>`, and `<style>` are
+/// compared exactly.  On failure, the normalized HTML of both sides is
+/// shown.  An optional message can be given after the arguments, as with
+/// [`assert_eq!`].
+///
+/// ```
+/// use html_builder::*;
+///
+/// let mut buf = Buffer::new();
+/// buf.a().href("/").class("home").text("Home   page");
+/// assert_html_eq!(buf.finish(), r#"<a class="home" href="/">Home page</a>"#);
+/// ```
+///
+/// [html5ever]: https://docs.rs/html5ever
+#[macro_export]
+macro_rules! assert_html_eq {
+    ($actual:expr, $expected:expr $(,)?) => {
+        $crate::__private::assert_html_eq(
+            ::std::convert::AsRef::<str>::as_ref(&$actual),
+            ::std::convert::AsRef::<str>::as_ref(&$expected),
+            ::std::option::Option::None,
+        )
+    };
+    ($actual:expr, $expected:expr, $($arg:tt)+) => {
+        $crate::__private::assert_html_eq(
+            ::std::convert::AsRef::<str>::as_ref(&$actual),
+            ::std::convert::AsRef::<str>::as_ref(&$expected),
+            ::std::option::Option::Some(::std::format_args!($($arg)+)),
+        )
+    };
+}
+
+/// Used by [`assert_html_eq!`]
+#[track_caller]
+pub fn assert_html_eq(actual: &str, expected: &str, msg: Option<std::fmt::Arguments>) {
+    let actual = normalize(actual);
+    let expected = normalize(expected);
+    if actual != expected {
+        match msg {
+            Some(msg) => panic!(
+                "HTML differs: {}\n  actual:\n{}\nexpected:\n{}",
+                msg, actual, expected
+            ),
+            None => panic!(
+                "HTML differs\n  actual:\n{}\nexpected:\n{}",
+                actual, expected
+            ),
+        }
+    }
+}
+
+/// Some rendered HTML, parsed so that tests can query it with CSS selectors.
+///
+/// This is only available with the `test-util` feature.  Rather than
+/// comparing a whole document, a test can pick out the elements it cares
+/// about:
+///
+/// ```
+/// use html_builder::*;
+///
+/// let mut buf = Buffer::new();
+/// let mut nav = buf.nav();
+/// let mut ul = nav.ul();
+/// for (href, label) in [("/", "Home"), ("/blog", "Blog"), ("/about", "About")] {
+///     ul.li().a().href(href).text(label);
+/// }
+/// let rendered = Rendered::new(buf.finish());
+///
+/// let links = rendered.select("nav li > a");
+/// assert_eq!(links.len(), 3);
+/// let hrefs: Vec<_> = links.iter().map(|a| a.attr("href").unwrap()).collect();
+/// assert_eq!(hrefs, ["/", "/blog", "/about"]);
+/// assert!(rendered.find("a[href=\"/contact\"]").is_none());
+/// ```
+///
+/// The HTML is parsed in the same way as [`Tree::parse()`], and the
+/// selectors are those supported by [`Selector`][crate::Selector].
+#[derive(Debug, Clone)]
+pub struct Rendered {
+    html: String,
+    tree: Tree,
+}
+
+impl Rendered {
+    /// Parses some HTML
+    pub fn new(html: impl Into<String>) -> Rendered {
+        let html = html.into();
+        let tree = Tree::parse(&html);
+        Rendered { html, tree }
+    }
+
+    /// The HTML, as it was given
+    pub fn html(&self) -> &str {
+        &self.html
+    }
+
+    /// The parsed HTML
+    pub fn tree(&self) -> &Tree {
+        &self.tree
+    }
+
+    /// Finds all the elements matching a CSS selector, in document order
+    ///
+    /// Panics if the selector is invalid.
+    pub fn select(&self, selector: &str) -> Vec<&Element> {
+        self.tree.select(selector)
+    }
+
+    /// Finds the first element matching a CSS selector
+    ///
+    /// Panics if the selector is invalid.
+    pub fn find(&self, selector: &str) -> Option<&Element> {
+        self.tree.find(selector)
+    }
+}
+
+impl From<String> for Rendered {
+    fn from(html: String) -> Rendered {
+        Rendered::new(html)
+    }
+}
+
+impl From<&str> for Rendered {
+    fn from(html: &str) -> Rendered {
+        Rendered::new(html)
+    }
+}
+
+impl fmt::Display for Rendered {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.html)
+    }
+}
+
+/// Rules for [`normalize_for_snapshot()`]: patterns to find in the HTML, and
+/// what to replace them with.
+///
+/// This is only available with the `test-util` feature.  The default rules
+/// are [`timestamps()`][SnapshotRules::timestamps],
+/// [`nonces()`][SnapshotRules::nonces], and
+/// [`asset_hashes()`][SnapshotRules::asset_hashes]; use
+/// [`new()`][SnapshotRules::new] to start with no rules.  The rules are
+/// applied in the order they were added.
+#[derive(Debug, Clone)]
+pub struct SnapshotRules {
+    rules: Vec<(Regex, String)>,
+}
+
+impl Default for SnapshotRules {
+    fn default() -> SnapshotRules {
+        SnapshotRules::new().timestamps().nonces().asset_hashes()
+    }
+}
+
+impl SnapshotRules {
+    /// No rules
+    pub fn new() -> SnapshotRules {
+        SnapshotRules { rules: vec![] }
+    }
+
+    /// Replaces each match of the regex `pattern` with `replacement`
+    ///
+    /// The replacement can refer to capture groups, eg. `$1` or `${name}`;
+    /// see [`Regex::replace_all()`].
+    ///
+    /// Panics if the pattern is invalid.
+    ///
+    /// [`Regex::replace_all()`]: https://docs.rs/regex/1/regex/struct.Regex.html#method.replace_all
+    pub fn replace(mut self, pattern: &str, replacement: impl Into<String>) -> SnapshotRules {
+        let regex = Regex::new(pattern).unwrap_or_else(|e| panic!("{}", e));
+        self.rules.push((regex, replacement.into()));
+        self
+    }
+
+    /// Replaces ISO 8601 dates and times, such as `2024-03-01T12:30:00Z`,
+    /// with `[timestamp]`
+    pub fn timestamps(self) -> SnapshotRules {
+        self.replace(
+            r"\b\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}(:\d{2}(\.\d+)?)?(Z|[+-]\d{2}:?\d{2})?",
+            "[timestamp]",
+        )
+    }
+
+    /// Replaces CSP nonces, in `nonce` attributes and in `'nonce-...'`
+    /// sources, with `[nonce]`
+    pub fn nonces(self) -> SnapshotRules {
+        self.replace(r#"\bnonce="[^"]*""#, r#"nonce="[nonce]""#)
+            .replace(r"'nonce-[^']*'", "'nonce-[nonce]'")
+    }
+
+    /// Replaces the hashes in fingerprinted asset names, such as
+    /// `app.3f2a9c1b.js` or `app-3f2a9c1b.css`, with `[hash]`; and the
+    /// values of `integrity` attributes with `[integrity]`
+    ///
+    /// A fingerprint is 8 or more hex digits, between a `.` or `-` and the
+    /// file's extension.
+    pub fn asset_hashes(self) -> SnapshotRules {
+        self.replace(r"([.-])[0-9a-fA-F]{8,}(\.[A-Za-z0-9]+)\b", "$1[hash]$2")
+            .replace(r#"\bintegrity="[^"]*""#, r#"integrity="[integrity]""#)
+    }
+}
+
+/// Replaces the volatile parts of some rendered HTML, so that it can be
+/// compared with a snapshot.
+///
+/// This is only available with the `test-util` feature.  Things like the
+/// current time, CSP nonces, and the hashes of assets change from run to
+/// run; `rules` says how to find them.
+///
+/// ```
+/// use html_builder::*;
+///
+/// let mut buf = Buffer::new().csp_nonce("r4nd0m");
+/// buf.script().src("/static/app.3f2a9c1b.js");
+/// buf.p().text("Generated at 2024-03-01T12:30:00Z");
+/// let html = normalize_for_snapshot(&buf.finish(), &SnapshotRules::default());
+/// assert_eq!(html, "\
+/// <script src=\"/static/app.[hash].js\" nonce=\"[nonce]\">
+/// </script>
+/// <p>
+/// Generated at [timestamp]
+/// </p>
+/// ");
+///
+/// let rules = SnapshotRules::new().replace(r"request-\d+", "request-N");
+/// assert_eq!(normalize_for_snapshot("<p>request-42</p>", &rules), "<p>request-N</p>");
+/// ```
+pub fn normalize_for_snapshot(html: &str, rules: &SnapshotRules) -> String {
+    let mut out = html.to_string();
+    for (regex, replacement) in &rules.rules {
+        out = regex.replace_all(&out, replacement.as_str()).into_owned();
+    }
+    out
+}
+
+/// Parses some HTML and writes it out again, without the differences which
+/// [`assert_html_eq!`] ignores
+fn normalize(html: &str) -> String {
+    let mut tree = Tree::new();
+    *tree.children_mut() = normalize_children(Tree::parse_with(html, true).children(), false);
+    tree.render()
+}
+
+fn normalize_children(children: &[Content], verbatim: bool) -> Vec<Content> {
+    let is_inline = |x: Option<&Content>| match x {
+        Some(Content::Element(el)) => INLINE.contains(&el.tag()),
+        Some(Content::Text(_)) => true,
+        _ => false,
+    };
+    let mut out = vec![];
+    for (i, x) in children.iter().enumerate() {
+        match x {
+            Content::Element(el) => out.push(Content::Element(normalize_element(el))),
+            Content::Text(text) if !verbatim => {
+                let mut text = collapse_whitespace(text);
+                if !is_inline(children.get(i + 1)) && text.ends_with(' ') {
+                    text.pop();
+                }
+                if !is_inline(i.checked_sub(1).and_then(|i| children.get(i)))
+                    && text.starts_with(' ')
+                {
+                    text.remove(0);
+                }
+                if !text.is_empty() {
+                    out.push(Content::Text(text));
+                }
+            }
+            x => out.push(x.clone()),
+        }
+    }
+    out
+}
+
+/// Replaces each run of whitespace with a single space
+fn collapse_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if !c.is_whitespace() {
+            out.push(c);
+        } else if !out.ends_with(' ') {
+            out.push(' ');
+        }
+    }
+    out
+}
+
+/// Elements which are laid out inline, so whitespace around them is
+/// significant
+const INLINE: &[&str] = &[
+    "a", "abbr", "b", "bdi", "bdo", "br", "button", "cite", "code", "data", "dfn", "em", "i",
+    "img", "input", "kbd", "label", "mark", "q", "s", "samp", "select", "small", "span", "strong",
+    "sub", "sup", "textarea", "time", "u", "var", "wbr",
+];
+
+fn normalize_element(el: &Element) -> Element {
+    let mut out = Element::new(el.tag().to_string());
+    let mut attrs = el.attrs().collect::<Vec<_>>();
+    attrs.sort();
+    for (name, value) in attrs {
+        match value {
+            Some(value) => out.set_attr(name, value),
+            None => out.set_flag(name),
+        };
+    }
+    let verbatim = matches!(
+        el.tag(),
+        "pre" | "textarea" | "script" | "style" | "listing" | "plaintext"
+    );
+    *out.children_mut() = normalize_children(el.children(), verbatim);
+    out
+}
@@ -0,0 +1,208 @@
+//! Exporting the output as html5ever tokens
+
+use crate::cache::{Event, Recorder};
+use crate::{Attribute, Buffer, Sink};
+use html5ever::tendril::StrTendril;
+use html5ever::tokenizer::{
+    BufferQueue, Tag, TagKind, Token, TokenSink, TokenSinkResult, Tokenizer,
+};
+use html5ever::{namespace_url, ns, LocalName, QualName};
+
+impl Buffer {
+    /// Creates a new empty buffer which produces [html5ever] tokens, rather
+    /// than text.
+    ///
+    /// This method is only available with the `html5ever` feature.  Use
+    /// [`finish_tokens()`][Buffer::finish_tokens] to send the tokens to a
+    /// [`TokenSink`].  Since html5ever's tree builder is a token sink,
+    /// this can be used to build a DOM for any [`TreeSink`] without
+    /// writing out the HTML and parsing it again:
+    ///
+    /// ```
+    /// use html_builder::*;
+    /// use html5ever::tree_builder::TreeBuilder;
+    /// use markup5ever_rcdom::{RcDom, SerializableHandle};
+    ///
+    /// let mut buf = Buffer::tokens();
+    /// buf.doctype();
+    /// let mut html = buf.html();
+    /// let mut body = html.body();
+    /// body.p().class("intro").text("Hello");
+    /// body.hr();
+    /// let builder = TreeBuilder::new(RcDom::default(), Default::default());
+    /// let dom = buf.finish_tokens(builder).sink;
+    ///
+    /// let document: SerializableHandle = dom.document.into();
+    /// let mut out = vec![];
+    /// html5ever::serialize(&mut out, &document, Default::default()).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(out).unwrap(),
+    ///     "<!DOCTYPE html><html><head></head><body><p class=\"intro\">Hello\n</p><hr></body></html>",
+    /// );
+    /// ```
+    ///
+    /// Raw markup, and attributes added with [`attr()`][crate::Node::attr],
+    /// are tokenized.  Conditional comments become two comments, around
+    /// their contents.
+    ///
+    /// [html5ever]: https://docs.rs/html5ever
+    /// [`TreeSink`]: html5ever::tree_builder::TreeSink
+    pub fn tokens() -> Buffer {
+        Buffer::with_sink(Recorder::default())
+    }
+
+    /// Closes all open tags, and sends the tokens to `sink`, followed by
+    /// the end of the stream.
+    ///
+    /// The line numbers given with the tokens are those of the output
+    /// which [`finish()`][Buffer::finish] would return.  The sink is
+    /// returned, so you can get at the result.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the buffer wasn't created by [`tokens()`][Buffer::tokens].
+    pub fn finish_tokens<S: TokenSink>(self, sink: S) -> S {
+        let recorder = self.into_sink::<Recorder>();
+        let mut tokens = Tokens {
+            sink,
+            line: 1,
+            comment: None,
+        };
+        for event in recorder.events {
+            tokens.event(event);
+        }
+        tokens.emit(Token::EOFToken);
+        tokens.sink.end();
+        tokens.sink
+    }
+}
+
+/// Turns events into tokens
+struct Tokens<S> {
+    sink: S,
+    /// The line number in the formatted output
+    line: u64,
+    /// The text of a comment which is being written
+    comment: Option<String>,
+}
+
+impl<S: TokenSink> Tokens<S> {
+    fn emit(&mut self, token: Token) {
+        // The sink may ask for the tokenizer to change state, eg. for the
+        // contents of a `<script>`; but the text isn't being tokenized
+        let _: TokenSinkResult<S::Handle> = self.sink.process_token(token, self.line);
+    }
+
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::StartTag(_, tag, attrs) => {
+                self.emit(tag_token(TagKind::StartTag, &tag, &attrs, false));
+                self.line += 1;
+            }
+            Event::VoidTag(_, tag, attrs, self_closing) => {
+                if tag.starts_with('!') {
+                    // eg. a doctype
+                    let mut html = String::new();
+                    html.void_tag(0, &tag, &attrs, false);
+                    for token in tokenize(html.trim_end()) {
+                        self.emit(token);
+                    }
+                } else {
+                    self.emit(tag_token(TagKind::StartTag, &tag, &attrs, self_closing));
+                }
+                self.line += 1;
+            }
+            Event::EndTag(_, tag) => {
+                self.emit(tag_token(TagKind::EndTag, &tag, &[], false));
+                self.line += 1;
+            }
+            Event::Text(text) => {
+                let lines = text.matches('\n').count() as u64;
+                self.emit(Token::CharacterTokens(text.into()));
+                self.line += lines;
+            }
+            Event::Raw(html) => {
+                if let Some(comment) = &mut self.comment {
+                    comment.push_str(&html);
+                    return;
+                }
+                for token in tokenize(&html) {
+                    self.emit(token);
+                }
+                self.line += html.matches('\n').count() as u64;
+            }
+            Event::StartComment(_) => self.comment = Some(String::new()),
+            Event::EndComment(_) => {
+                if let Some(text) = self.comment.take() {
+                    let lines = text.matches('\n').count() as u64;
+                    self.emit(Token::CommentToken(text.into()));
+                    self.line += lines + 1;
+                }
+            }
+            Event::StartConditional(_, cond) => {
+                let text = format!("[{}]>", cond);
+                self.emit(Token::CommentToken(text.into()));
+                self.line += 1;
+            }
+            Event::EndConditional(_) => {
+                self.emit(Token::CommentToken("<![endif]".into()));
+                self.line += 1;
+            }
+        }
+    }
+}
+
+fn tag_token(kind: TagKind, tag: &str, attrs: &[Attribute], self_closing: bool) -> Token {
+    let mut converted = vec![];
+    for attr in attrs {
+        match attr {
+            Attribute::Value(name, value) => converted.push(attribute(name, value.as_str().into())),
+            Attribute::Flag(name) => converted.push(attribute(name, StrTendril::new())),
+            Attribute::Raw(raw) => {
+                for token in tokenize(&format!("<b {}>", raw)) {
+                    if let Token::TagToken(tag) = token {
+                        converted.extend(tag.attrs);
+                    }
+                }
+            }
+        }
+    }
+    Token::TagToken(Tag {
+        kind,
+        name: LocalName::from(tag),
+        self_closing,
+        attrs: converted,
+    })
+}
+
+fn attribute(name: &str, value: StrTendril) -> html5ever::Attribute {
+    html5ever::Attribute {
+        name: QualName::new(None, ns!(), LocalName::from(name)),
+        value,
+    }
+}
+
+/// Tokenizes some HTML, without the end-of-file token
+fn tokenize(html: &str) -> Vec<Token> {
+    let mut input = BufferQueue::default();
+    input.push_back(html.into());
+    let mut tokenizer = Tokenizer::new(Collector(vec![]), Default::default());
+    let _ = tokenizer.feed(&mut input);
+    tokenizer.end();
+    tokenizer.sink.0
+}
+
+/// A token sink which keeps the tokens
+struct Collector(Vec<Token>);
+
+impl TokenSink for Collector {
+    type Handle = ();
+
+    fn process_token(&mut self, token: Token, _line_number: u64) -> TokenSinkResult<()> {
+        match token {
+            Token::EOFToken | Token::ParseError(_) => (),
+            token => self.0.push(token),
+        }
+        TokenSinkResult::Continue
+    }
+}
@@ -0,0 +1,490 @@
+/*!
+An in-memory document tree.
+
+[`Buffer`] writes elements out as soon as they're created, which is fast and
+simple, but it means you can't go back and change something you've already
+written.  A [`Tree`] holds the whole document in memory instead.  Elements
+can be added, removed, and modified at any point, and the tree is only
+serialized when you call [`render()`][Tree::render].
+
+```
+use html_builder::*;
+
+let mut tree = Tree::new();
+let html = tree.child("html");
+html.child("head");
+let body = html.child("body");
+body.child("p").append_text("Hello!");
+
+// Later, go back and add something to the <head>
+let head = tree.find_mut("head").unwrap();
+head.child("style").append_text("p { color: red; }");
+
+assert_eq!(tree.render(), "\
+<html>
+ <head>
+  <style>
+p { color: red; }
+  </style>
+ </head>
+ <body>
+  <p>
+Hello!
+  </p>
+ </body>
+</html>
+");
+```
+
+The output is formatted in the same way as [`Buffer`]'s.
+*/
+
+use crate::tag::Tag;
+use crate::{Buffer, GlobalAttributes, Html5, Node, Selector};
+use std::borrow::Cow;
+use std::fmt::Write;
+
+/// The elements which never have any contents or an end tag
+pub(crate) const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// An HTML document (or fragment), held in memory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Tree {
+    children: Vec<Content>,
+}
+
+/// An element in a [`Tree`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Element {
+    tag: Cow<'static, str>,
+    attrs: Vec<(String, Option<String>)>,
+    children: Vec<Content>,
+}
+
+/// Something which can appear inside an [`Element`] or at the top level of
+/// a [`Tree`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Content {
+    /// A child element
+    Element(Element),
+    /// Text, which is escaped when rendered (except inside `<script>` and
+    /// `<style>`)
+    Text(String),
+    /// HTML which is rendered verbatim
+    Raw(String),
+    /// A comment
+    Comment(String),
+    /// A document type declaration, such as `html`
+    Doctype(String),
+}
+
+impl From<Element> for Content {
+    fn from(x: Element) -> Content {
+        Content::Element(x)
+    }
+}
+
+/// Operations on things which contain [`Content`]: [`Tree`]s and
+/// [`Element`]s.
+pub trait Parent {
+    /// The contents, in order
+    fn children(&self) -> &[Content];
+
+    /// The contents, for modification
+    fn children_mut(&mut self) -> &mut Vec<Content>;
+
+    /// Appends a new element with the given tag, and returns it
+    fn child(&mut self, tag: impl Into<Cow<'static, str>>) -> &mut Element {
+        self.append(Element::new(tag))
+    }
+
+    /// Appends an element, and returns it
+    fn append(&mut self, element: Element) -> &mut Element {
+        let children = self.children_mut();
+        children.push(Content::Element(element));
+        match children.last_mut() {
+            Some(Content::Element(el)) => el,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Appends some text, which will be escaped when rendered
+    fn append_text(&mut self, text: impl Into<String>) -> &mut Self {
+        self.children_mut().push(Content::Text(text.into()));
+        self
+    }
+
+    /// Appends some HTML, which will be rendered verbatim
+    fn append_raw(&mut self, html: impl Into<String>) -> &mut Self {
+        self.children_mut().push(Content::Raw(html.into()));
+        self
+    }
+
+    /// Appends a comment
+    fn append_comment(&mut self, text: impl Into<String>) -> &mut Self {
+        self.children_mut().push(Content::Comment(text.into()));
+        self
+    }
+
+    /// Inserts content at position `index`, shifting later content along
+    ///
+    /// Panics if `index` is out of bounds.
+    fn insert(&mut self, index: usize, content: impl Into<Content>) {
+        self.children_mut().insert(index, content.into());
+    }
+
+    /// Removes and returns the content at position `index`
+    ///
+    /// Panics if `index` is out of bounds.
+    fn remove(&mut self, index: usize) -> Content {
+        self.children_mut().remove(index)
+    }
+
+    /// Iterates over the child elements, skipping other content
+    fn elements(&self) -> Box<dyn Iterator<Item = &Element> + '_> {
+        Box::new(self.children().iter().filter_map(|x| match x {
+            Content::Element(el) => Some(el),
+            _ => None,
+        }))
+    }
+
+    /// Finds the first descendant element matching `pred`, in document order
+    fn find_where(&self, pred: &dyn Fn(&Element) -> bool) -> Option<&Element> {
+        for el in self.elements() {
+            if pred(el) {
+                return Some(el);
+            }
+            if let Some(x) = el.find_where(pred) {
+                return Some(x);
+            }
+        }
+        None
+    }
+
+    /// Finds the first descendant element matching `pred`, in document
+    /// order, for modification
+    fn find_where_mut(&mut self, pred: &dyn Fn(&Element) -> bool) -> Option<&mut Element> {
+        for x in self.children_mut() {
+            if let Content::Element(el) = x {
+                if pred(el) {
+                    return Some(el);
+                }
+                if let Some(x) = el.find_where_mut(pred) {
+                    return Some(x);
+                }
+            }
+        }
+        None
+    }
+
+    /// Finds the first descendant element matching a [CSS selector][Selector]
+    ///
+    /// Panics if the selector is invalid.
+    fn find(&self, selector: &str) -> Option<&Element> {
+        let selector = parse(selector);
+        let mut found = None;
+        walk(self.children(), &mut vec![], &mut |el, ancestors| {
+            if selector.matches(el, ancestors) {
+                found = Some(el);
+            }
+            found.is_none()
+        });
+        found
+    }
+
+    /// Finds the first descendant element matching a [CSS selector][Selector],
+    /// for modification
+    ///
+    /// Panics if the selector is invalid.
+    fn find_mut(&mut self, selector: &str) -> Option<&mut Element> {
+        let path = match_paths(self.children(), &parse(selector), true).pop()?;
+        resolve_mut(self.children_mut(), &path)
+    }
+
+    /// Finds all descendant elements matching a [CSS selector][Selector], in
+    /// document order
+    ///
+    /// Panics if the selector is invalid.
+    fn select(&self, selector: &str) -> Vec<&Element> {
+        let selector = parse(selector);
+        let mut out = vec![];
+        let mut ancestors = vec![];
+        walk(self.children(), &mut ancestors, &mut |el, ancestors| {
+            if selector.matches(el, ancestors) {
+                out.push(el);
+            }
+            true
+        });
+        out
+    }
+
+    /// Calls `f` on each descendant element matching a
+    /// [CSS selector][Selector], in document order
+    ///
+    /// The matches are found before `f` is called for the first time.  If
+    /// `f` removes or moves elements, some matches may be skipped.
+    ///
+    /// Panics if the selector is invalid.
+    fn select_mut(&mut self, selector: &str, mut f: impl FnMut(&mut Element))
+    where
+        Self: Sized,
+    {
+        for path in match_paths(self.children(), &parse(selector), false) {
+            if let Some(el) = resolve_mut(self.children_mut(), &path) {
+                f(el);
+            }
+        }
+    }
+}
+
+fn parse(selector: &str) -> Selector {
+    Selector::parse(selector).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Visits elements depth-first, in document order, along with their
+/// ancestors.  Stops early if `f` returns false.
+fn walk<'a>(
+    children: &'a [Content],
+    ancestors: &mut Vec<&'a Element>,
+    f: &mut dyn FnMut(&'a Element, &[&'a Element]) -> bool,
+) -> bool {
+    for x in children {
+        if let Content::Element(el) = x {
+            if !f(el, ancestors) {
+                return false;
+            }
+            ancestors.push(el);
+            let go_on = walk(&el.children, ancestors, f);
+            ancestors.pop();
+            if !go_on {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Finds the positions of the elements matching `selector`, as lists of
+/// indices into successive `children` vectors
+fn match_paths(children: &[Content], selector: &Selector, first_only: bool) -> Vec<Vec<usize>> {
+    fn go<'a>(
+        children: &'a [Content],
+        selector: &Selector,
+        first_only: bool,
+        ancestors: &mut Vec<&'a Element>,
+        path: &mut Vec<usize>,
+        out: &mut Vec<Vec<usize>>,
+    ) -> bool {
+        for (i, x) in children.iter().enumerate() {
+            if let Content::Element(el) = x {
+                path.push(i);
+                if selector.matches(el, ancestors) {
+                    out.push(path.clone());
+                    if first_only {
+                        return false;
+                    }
+                }
+                ancestors.push(el);
+                let go_on = go(&el.children, selector, first_only, ancestors, path, out);
+                ancestors.pop();
+                path.pop();
+                if !go_on {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+    let mut out = vec![];
+    go(
+        children,
+        selector,
+        first_only,
+        &mut vec![],
+        &mut vec![],
+        &mut out,
+    );
+    out
+}
+
+fn resolve_mut<'a>(children: &'a mut [Content], path: &[usize]) -> Option<&'a mut Element> {
+    let (first, rest) = path.split_first()?;
+    match children.get_mut(*first)? {
+        Content::Element(el) => match rest {
+            [] => Some(el),
+            _ => resolve_mut(&mut el.children, rest),
+        },
+        _ => None,
+    }
+}
+
+impl Tree {
+    /// Creates an empty tree.
+    pub fn new() -> Tree {
+        Tree::default()
+    }
+
+    /// Appends a document type declaration
+    pub fn doctype(&mut self) -> &mut Tree {
+        self.children.push(Content::Doctype("html".into()));
+        self
+    }
+
+    /// Serializes the tree.
+    pub fn render(&self) -> String {
+        let mut buf = Buffer::new();
+        self.write_to(&mut buf);
+        buf.finish()
+    }
+
+    /// Writes the contents of the tree into a node of a [`Buffer`].
+    pub fn write_to(&self, node: &mut Node) {
+        write_contents(&self.children, node, false);
+    }
+}
+
+impl Parent for Tree {
+    fn children(&self) -> &[Content] {
+        &self.children
+    }
+
+    fn children_mut(&mut self) -> &mut Vec<Content> {
+        &mut self.children
+    }
+}
+
+impl Element {
+    /// Creates an element with the given tag.
+    pub fn new(tag: impl Into<Cow<'static, str>>) -> Element {
+        Element {
+            tag: tag.into(),
+            attrs: vec![],
+            children: vec![],
+        }
+    }
+
+    /// The element's tag
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Whether this is a void element, which has no contents or end tag
+    pub fn is_void(&self) -> bool {
+        VOID_ELEMENTS.contains(&self.tag.as_ref())
+    }
+
+    /// The value of an attribute
+    ///
+    /// Boolean attributes which are set have the value `""`.
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_deref().unwrap_or(""))
+    }
+
+    /// The element's attributes, in order
+    ///
+    /// Boolean attributes have a value of `None`.
+    pub fn attrs(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        self.attrs.iter().map(|(n, v)| (n.as_str(), v.as_deref()))
+    }
+
+    /// Sets an attribute, replacing any existing value
+    pub fn set_attr(&mut self, name: impl Into<String>, value: impl Into<String>) -> &mut Element {
+        self.set(name.into(), Some(value.into()))
+    }
+
+    /// Sets a boolean attribute
+    pub fn set_flag(&mut self, name: impl Into<String>) -> &mut Element {
+        self.set(name.into(), None)
+    }
+
+    /// Removes an attribute, returning whether it was set
+    pub fn remove_attr(&mut self, name: &str) -> bool {
+        let len = self.attrs.len();
+        self.attrs.retain(|(n, _)| n != name);
+        self.attrs.len() != len
+    }
+
+    /// Whether the element's `class` attribute contains `class`
+    pub fn has_class(&self, class: &str) -> bool {
+        self.attr("class")
+            .is_some_and(|x| x.split_ascii_whitespace().any(|c| c == class))
+    }
+
+    /// Adds a class to the element's `class` attribute
+    pub fn add_class(&mut self, class: &str) -> &mut Element {
+        if !self.has_class(class) {
+            let classes = match self.attr("class") {
+                Some("") | None => class.to_string(),
+                Some(x) => format!("{} {}", x, class),
+            };
+            self.set_attr("class", classes);
+        }
+        self
+    }
+
+    fn set(&mut self, name: String, value: Option<String>) -> &mut Element {
+        match self.attrs.iter_mut().find(|(n, _)| *n == name) {
+            Some(attr) => attr.1 = value,
+            None => self.attrs.push((name, value)),
+        }
+        self
+    }
+
+    fn write_to(&self, parent: &mut Node) {
+        if self.is_void() {
+            let mut void = parent.void_tag_child(Tag::from(&self.tag));
+            for (name, value) in &self.attrs {
+                void = match value {
+                    Some(value) => void.attr_value(name, value),
+                    None => void.attr_flag(name, true),
+                };
+            }
+            return;
+        }
+        let mut node = parent.tag_child(Tag::from(&self.tag));
+        for (name, value) in &self.attrs {
+            node = match value {
+                Some(value) => node.attr_value(name, value),
+                None => node.attr_flag(name, true),
+            };
+        }
+        let raw_text = matches!(self.tag.as_ref(), "script" | "style");
+        write_contents(&self.children, &mut node, raw_text);
+    }
+}
+
+impl Parent for Element {
+    fn children(&self) -> &[Content] {
+        &self.children
+    }
+
+    fn children_mut(&mut self) -> &mut Vec<Content> {
+        &mut self.children
+    }
+}
+
+fn write_contents(contents: &[Content], node: &mut Node, raw_text: bool) {
+    for x in contents {
+        match x {
+            Content::Element(el) => {
+                el.write_to(node);
+                Ok(())
+            }
+            Content::Text(text) if raw_text => node.write_raw(text).and(node.write_raw("\n")),
+            Content::Text(text) => writeln!(node, "{}", text),
+            Content::Raw(html) => node.write_raw(html).and(node.write_raw("\n")),
+            Content::Comment(text) => write!(node.comment(), "{}", text),
+            Content::Doctype(decl) => {
+                node.doctype_custom(decl);
+                Ok(())
+            }
+        }
+        .unwrap();
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{Buffer, GlobalAttributes, Node};
+use std::borrow::Cow;
+use std::fmt::Display;
+
+impl<'a> Node<'a> {
+    /// Defines a [Turbo](https://turbo.hotwired.dev/) frame, a part of the
+    /// page which can be replaced independently
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new();
+    /// buf.turbo_frame("messages").p().text("No messages");
+    /// assert_eq!(buf.finish(), "\
+    /// <turbo-frame id=\"messages\">
+    ///  <p>
+    /// No messages
+    ///  </p>
+    /// </turbo-frame>
+    /// ");
+    /// ```
+    pub fn turbo_frame(&mut self, id: impl Display) -> Node<'_> {
+        self.child(Cow::Borrowed("turbo-frame")).id(id)
+    }
+
+    /// Writes a Turbo stream element, and returns its `<template>`
+    ///
+    /// The contents of the template are used by the `action` (eg.
+    /// `append`) on the element with the id `target`.  See
+    /// [`TurboStream`].
+    pub fn turbo_stream(&mut self, action: &str, target: impl Display) -> Node<'_> {
+        self.child(Cow::Borrowed("turbo-stream"))
+            .attr_value("action", action)
+            .attr_value("target", target)
+            .into_child(Cow::Borrowed("template"))
+    }
+}
+
+/// A buffer for writing a Turbo stream response.
+///
+/// A response made of `<turbo-stream>` elements, served with the
+/// [`CONTENT_TYPE`][TurboStream::CONTENT_TYPE], tells Turbo to update
+/// parts of the current page.  Each action returns the `<template>` whose
+/// contents are used for the update.
+///
+/// ```
+/// use html_builder::*;
+///
+/// let mut stream = TurboStream::new();
+/// stream.append("messages").p().id("message_7").text("Hello!");
+/// stream.remove("message_1");
+/// assert_eq!(stream.finish(), "\
+/// <turbo-stream action=\"append\" target=\"messages\">
+///  <template>
+///   <p id=\"message_7\">
+/// Hello!
+///   </p>
+///  </template>
+/// </turbo-stream>
+/// <turbo-stream action=\"remove\" target=\"message_1\">
+/// </turbo-stream>
+/// ");
+/// ```
+#[derive(Default)]
+pub struct TurboStream {
+    buf: Buffer,
+}
+
+impl TurboStream {
+    /// The MIME type of a Turbo stream response
+    pub const CONTENT_TYPE: &'static str = "text/vnd.turbo-stream.html";
+
+    /// Creates an empty response
+    pub fn new() -> TurboStream {
+        TurboStream::default()
+    }
+
+    /// Writes a stream element with an arbitrary action
+    ///
+    /// See [`Node::turbo_stream()`].
+    pub fn action(&mut self, action: &str, target: impl Display) -> Node<'_> {
+        self.buf.turbo_stream(action, target)
+    }
+
+    /// Adds the template's contents to the end of the target
+    pub fn append(&mut self, target: impl Display) -> Node<'_> {
+        self.action("append", target)
+    }
+
+    /// Adds the template's contents to the start of the target
+    pub fn prepend(&mut self, target: impl Display) -> Node<'_> {
+        self.action("prepend", target)
+    }
+
+    /// Replaces the target with the template's contents
+    pub fn replace(&mut self, target: impl Display) -> Node<'_> {
+        self.action("replace", target)
+    }
+
+    /// Replaces the contents of the target with the template's contents
+    pub fn update(&mut self, target: impl Display) -> Node<'_> {
+        self.action("update", target)
+    }
+
+    /// Inserts the template's contents before the target
+    pub fn before(&mut self, target: impl Display) -> Node<'_> {
+        self.action("before", target)
+    }
+
+    /// Inserts the template's contents after the target
+    pub fn after(&mut self, target: impl Display) -> Node<'_> {
+        self.action("after", target)
+    }
+
+    /// Removes the target
+    pub fn remove(&mut self, target: impl Display) {
+        self.buf
+            .child(Cow::Borrowed("turbo-stream"))
+            .attr_value("action", "remove")
+            .attr_value("target", target);
+    }
+
+    /// Closes all open tags and returns the response body.
+    pub fn finish(self) -> String {
+        self.buf.finish()
+    }
+}
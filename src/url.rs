@@ -0,0 +1,69 @@
+//! Guarding URL attributes against script injection
+
+/// The URL which is written in place of an unsafe one
+pub const BLOCKED_URL: &str = "about:invalid#blocked";
+
+/// Returns `false` if following the URL would run a script.
+///
+/// The typed `href`, `src`, `action`, and `poster` setters use this to
+/// guard against XSS via attacker-controlled URLs: URLs with a
+/// `javascript:` or `vbscript:` scheme, or `data:` URLs containing HTML,
+/// are replaced with [`BLOCKED_URL`].  The scheme is checked the way
+/// browsers parse it, so tricks like `" JaVa\tScRiPt:..."` are caught too.
+///
+/// To write a URL without checking it, set the attribute with
+/// [`attr_value()`][crate::GlobalAttributes::attr_value] instead.
+///
+/// ```
+/// use html_builder::*;
+///
+/// assert!(is_safe_url("https://example.com/"));
+/// assert!(is_safe_url("/relative/path?q=javascript:"));
+/// assert!(!is_safe_url(" JaVa\tScRiPt:alert(1)"));
+///
+/// let mut buf = Buffer::new();
+/// buf.a().href("javascript:alert(1)");
+/// buf.a().attr_value("href", "javascript:void(0)");
+/// assert_eq!(buf.finish(), "\
+/// <a href=\"about:invalid#blocked\">
+/// </a>
+/// <a href=\"javascript:void(0)\">
+/// </a>
+/// ");
+/// ```
+pub fn is_safe_url(url: &str) -> bool {
+    // Browsers ignore leading and trailing C0 controls and spaces, and tabs
+    // and newlines anywhere
+    let url: String = url
+        .trim_matches(|c: char| c <= ' ')
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+        .collect();
+    let scheme = match url.find(':') {
+        Some(i) => &url[..i],
+        None => return true,
+    };
+    let is_scheme = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    if !is_scheme {
+        // It's a relative URL, eg. "/foo:bar"
+        return true;
+    }
+    let rest = url[scheme.len() + 1..].trim_start().to_ascii_lowercase();
+    match scheme.to_ascii_lowercase().as_str() {
+        "javascript" | "vbscript" => false,
+        "data" => !(rest.starts_with("text/html") || rest.starts_with("application/xhtml+xml")),
+        _ => true,
+    }
+}
+
+/// Returns the URL if it's safe, or [`BLOCKED_URL`] if not
+pub(crate) fn guard(url: String) -> String {
+    if is_safe_url(&url) {
+        url
+    } else {
+        BLOCKED_URL.to_string()
+    }
+}
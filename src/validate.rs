@@ -0,0 +1,227 @@
+use crate::Frame;
+use std::fmt;
+
+/// A structural problem detected by a buffer in
+/// [strict mode][crate::Buffer::strict].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// An element was created somewhere its parent doesn't allow
+    Nesting(NestingError),
+    /// A tag name contained characters which aren't allowed
+    InvalidTagName(String),
+    /// An attribute name contained characters which aren't allowed, so the
+    /// attribute was dropped
+    InvalidAttributeName(String),
+    /// An attribute was added after the element's contents, so it was dropped
+    LateAttribute(String),
+    /// A language tag wasn't a well-formed BCP 47 tag
+    InvalidLanguageTag(String),
+    /// One of AMP's rules was broken, in [AMP mode][crate::Buffer::amp]
+    Amp(String),
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationError::Nesting(e) => e.fmt(f),
+            ValidationError::InvalidTagName(x) => write!(f, "invalid tag name: {:?}", x),
+            ValidationError::InvalidAttributeName(x) => {
+                write!(f, "invalid attribute name: {:?}", x)
+            }
+            ValidationError::LateAttribute(x) => {
+                write!(f, "attribute {:?} added after the element's contents", x)
+            }
+            ValidationError::InvalidLanguageTag(x) => write!(f, "invalid language tag: {:?}", x),
+            ValidationError::Amp(x) => write!(f, "not valid AMP: {}", x),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// An element was created somewhere its parent's content model doesn't
+/// allow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NestingError {
+    parent: Option<String>,
+    child: String,
+    reason: &'static str,
+}
+
+impl NestingError {
+    /// The tag of the parent element, or `None` if the child was created at
+    /// the top level of the document
+    pub fn parent(&self) -> Option<&str> {
+        self.parent.as_deref()
+    }
+
+    /// The tag of the misplaced element
+    pub fn child(&self) -> &str {
+        &self.child
+    }
+}
+
+impl fmt::Display for NestingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.parent {
+            Some(parent) => write!(f, "<{}> inside <{}>: ", self.child, parent)?,
+            None => write!(f, "<{}> at top level: ", self.child)?,
+        }
+        f.write_str(self.reason)
+    }
+}
+
+impl std::error::Error for NestingError {}
+
+/// Elements whose contents are text, which browsers don't parse as markup
+const TEXT_ONLY: &[&str] = &["title", "textarea", "option", "script", "style"];
+
+/// Elements which can only appear inside phrasing content-accepting parents
+/// such as `<p>`.  Opening one of these implicitly closes a `<p>`.
+const NOT_PHRASING: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "main",
+    "menu",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "search",
+    "section",
+    "table",
+    "ul",
+];
+
+/// Checks whether `tag` may be opened given the currently-open elements.
+pub(crate) fn check(stack: &[Frame], tag: &str) -> Result<(), NestingError> {
+    let mut ancestors = stack.iter().rev().filter_map(|frame| match frame {
+        Frame::Element(tag) => Some(tag.as_ref()),
+        Frame::Conditional => None,
+    });
+    let parent = ancestors.clone().next();
+    let err = |reason| NestingError {
+        parent: parent.map(String::from),
+        child: tag.to_string(),
+        reason,
+    };
+    let parent_in = |allowed: &[&str]| parent.is_some_and(|p| allowed.contains(&p));
+    if parent_in(TEXT_ONLY) {
+        return Err(err("the parent can only contain text"));
+    }
+    match tag {
+        "li" if !parent_in(&["ul", "ol", "menu"]) => {
+            return Err(err("list items must be inside <ul>, <ol>, or <menu>"))
+        }
+        "tr" if !parent_in(&["table", "thead", "tbody", "tfoot"]) => {
+            return Err(err("table rows must be inside a table or table section"))
+        }
+        "td" | "th" if !parent_in(&["tr"]) => return Err(err("table cells must be inside <tr>")),
+        "thead" | "tbody" | "tfoot" | "caption" | "colgroup" if !parent_in(&["table"]) => {
+            return Err(err("must be a direct child of <table>"))
+        }
+        "col" if !parent_in(&["colgroup"]) => return Err(err("must be inside <colgroup>")),
+        "dt" | "dd" if !parent_in(&["dl", "div"]) => {
+            return Err(err("description list items must be inside <dl>"))
+        }
+        "option" if !parent_in(&["select", "datalist", "optgroup"]) => {
+            return Err(err(
+                "options must be inside <select>, <datalist>, or <optgroup>",
+            ))
+        }
+        "optgroup" if !parent_in(&["select"]) => return Err(err("must be inside <select>")),
+        "summary" if !parent_in(&["details"]) => return Err(err("must be inside <details>")),
+        "legend" if !parent_in(&["fieldset"]) => return Err(err("must be inside <fieldset>")),
+        "figcaption" if !parent_in(&["figure"]) => return Err(err("must be inside <figure>")),
+        "rt" | "rp" if !parent_in(&["ruby"]) => return Err(err("must be inside <ruby>")),
+        "source" if !parent_in(&["picture", "video", "audio"]) => {
+            return Err(err("must be inside <picture>, <video>, or <audio>"))
+        }
+        "track" if !parent_in(&["video", "audio"]) => {
+            return Err(err("must be inside <video> or <audio>"))
+        }
+        _ => (),
+    }
+    if parent == Some("p") && NOT_PHRASING.contains(&tag) {
+        return Err(err("<p> can only contain phrasing content"));
+    }
+    if matches!(tag, "form" | "a" | "button") && ancestors.any(|x| x == tag) {
+        return Err(err("can't be nested inside an element of the same type"));
+    }
+    Ok(())
+}
+
+/// Checks that `name` is a valid tag name.
+///
+/// Tag names must start with an ASCII letter.  The remaining characters may
+/// be anything except whitespace, control characters, `/`, `>`, `<`, `=`,
+/// and quotes; this admits custom elements (`my-widget`) and namespaced XML
+/// names (`atom:link`).
+pub(crate) fn check_tag_name(name: &str) -> Result<(), ValidationError> {
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| !is_forbidden_in_name(c) && c != '<');
+    if valid {
+        Ok(())
+    } else {
+        Err(ValidationError::InvalidTagName(name.to_string()))
+    }
+}
+
+/// Checks that `name` is a valid attribute name.
+///
+/// Attribute names must be non-empty and may not contain whitespace, control
+/// characters, `/`, `>`, `=`, or quotes.
+pub(crate) fn check_attr_name(name: &str) -> Result<(), ValidationError> {
+    if !name.is_empty() && !name.chars().any(is_forbidden_in_name) {
+        Ok(())
+    } else {
+        Err(ValidationError::InvalidAttributeName(name.to_string()))
+    }
+}
+
+/// Checks that a language tag is well-formed according to BCP 47, eg.
+/// `en`, `pt-BR`, or `zh-Hant-TW`
+///
+/// This only checks the syntax, not whether the subtags are registered.
+pub(crate) fn check_language_tag(tag: &str) -> Result<(), ValidationError> {
+    let mut subtags = tag.split('-');
+    let language = subtags.next().unwrap_or("");
+    let language_ok = match language.len() {
+        // Private use and grandfathered tags, eg. `x-klingon` or `i-navajo`
+        1 => matches!(language, "x" | "X" | "i" | "I"),
+        2..=8 => language.chars().all(|c| c.is_ascii_alphabetic()),
+        _ => false,
+    };
+    let rest_ok =
+        subtags.all(|x| (1..=8).contains(&x.len()) && x.chars().all(|c| c.is_ascii_alphanumeric()));
+    if language_ok && rest_ok && !(language.len() == 1 && tag.len() == 1) {
+        Ok(())
+    } else {
+        Err(ValidationError::InvalidLanguageTag(tag.to_string()))
+    }
+}
+
+fn is_forbidden_in_name(c: char) -> bool {
+    c.is_whitespace() || c.is_control() || matches!(c, '/' | '>' | '=' | '"' | '\'')
+}
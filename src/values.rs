@@ -0,0 +1,282 @@
+//! Typed attribute values.
+//!
+//! The typed setters take any `Display` value, so a string works too; but
+//! with these enums, a misspelled value such as `"submitt"` is a compile
+//! error.
+
+use std::fmt;
+use std::ops::{BitOr, BitOrAssign};
+
+macro_rules! values {
+    ($(
+        $(#[$m:meta])*
+        $ty:ident {
+            $( $(#[$vm:meta])* $variant:ident => $value:literal, )*
+        }
+    )*) => {$(
+        $(#[$m])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $ty {
+            $( $(#[$vm])* $variant, )*
+        }
+
+        impl $ty {
+            /// The value as it's written in the attribute
+            pub fn as_str(self) -> &'static str {
+                match self {
+                    $( $ty::$variant => $value, )*
+                }
+            }
+        }
+
+        impl fmt::Display for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+    )*};
+}
+
+values! {
+    /// The type of an `<input>`, for
+    /// [`InputElement::type_()`][crate::InputElement::type_]
+    ///
+    /// ```
+    /// use html_builder::*;
+    ///
+    /// let mut buf = Buffer::new();
+    /// buf.input().type_(InputType::Email).name("email");
+    /// assert_eq!(buf.finish(), "<input type=\"email\" name=\"email\">\n");
+    /// ```
+    InputType {
+        Button => "button",
+        Checkbox => "checkbox",
+        Color => "color",
+        Date => "date",
+        DatetimeLocal => "datetime-local",
+        Email => "email",
+        File => "file",
+        Hidden => "hidden",
+        Image => "image",
+        Month => "month",
+        Number => "number",
+        Password => "password",
+        Radio => "radio",
+        Range => "range",
+        Reset => "reset",
+        Search => "search",
+        Submit => "submit",
+        Tel => "tel",
+        Text => "text",
+        Time => "time",
+        Url => "url",
+        Week => "week",
+    }
+
+    /// The behaviour of a `<button>`, for
+    /// [`ButtonElement::type_()`][crate::ButtonElement::type_]
+    ButtonType {
+        /// Submits the form
+        Submit => "submit",
+        /// Resets the form's controls to their initial values
+        Reset => "reset",
+        /// Does nothing by default; for buttons handled by scripts
+        Button => "button",
+    }
+
+    /// The HTTP method used to submit a `<form>`, for
+    /// [`FormElement::method()`][crate::FormElement::method]
+    FormMethod {
+        Get => "get",
+        Post => "post",
+        /// Closes the `<dialog>` containing the form
+        Dialog => "dialog",
+    }
+
+    /// Where to display a linked document or a form's response, for the
+    /// `target` setters
+    ///
+    /// To use a named browsing context, eg. an `<iframe>`'s name, pass the
+    /// name as a string instead.
+    Target {
+        /// A new tab or window
+        Blank => "_blank",
+        /// The current browsing context
+        Self_ => "_self",
+        /// The parent of the current browsing context
+        Parent => "_parent",
+        /// The topmost browsing context
+        Top => "_top",
+    }
+
+    /// The relationship of a linked resource, for the `rel` setters of
+    /// [`<link>`][crate::LinkElement::rel] and [`<a>`][crate::AElement::rel]
+    /// elements
+    ///
+    /// For several relationships, write them as a space-separated string.
+    LinkRel {
+        Alternate => "alternate",
+        Author => "author",
+        Bookmark => "bookmark",
+        Canonical => "canonical",
+        DnsPrefetch => "dns-prefetch",
+        External => "external",
+        Help => "help",
+        Icon => "icon",
+        License => "license",
+        Manifest => "manifest",
+        Me => "me",
+        ModulePreload => "modulepreload",
+        Next => "next",
+        NoFollow => "nofollow",
+        NoOpener => "noopener",
+        NoReferrer => "noreferrer",
+        Opener => "opener",
+        Pingback => "pingback",
+        Preconnect => "preconnect",
+        Prefetch => "prefetch",
+        Preload => "preload",
+        Prev => "prev",
+        Search => "search",
+        Stylesheet => "stylesheet",
+        Tag => "tag",
+    }
+
+    /// The kind of current item an element is, for
+    /// [`aria_current()`][crate::GlobalAttributes::aria_current]
+    AriaCurrent {
+        /// The current page, eg. in a navigation menu
+        Page => "page",
+        /// The current step in a process
+        Step => "step",
+        /// The current location, eg. in a flow chart
+        Location => "location",
+        /// The current date, eg. in a calendar
+        Date => "date",
+        /// The current time, eg. in a timetable
+        Time => "time",
+        /// The current item of some other kind
+        True => "true",
+        /// Not the current item
+        False => "false",
+    }
+
+    /// How updates to a live region are announced, for
+    /// [`aria_live()`][crate::GlobalAttributes::aria_live]
+    AriaLive {
+        /// Updates aren't announced
+        Off => "off",
+        /// Updates are announced when the user is idle
+        Polite => "polite",
+        /// Updates are announced immediately
+        Assertive => "assertive",
+    }
+
+    /// How much of the referrer to send with a request, for the
+    /// `referrerpolicy` setters
+    ReferrerPolicy {
+        NoReferrer => "no-referrer",
+        NoReferrerWhenDowngrade => "no-referrer-when-downgrade",
+        Origin => "origin",
+        OriginWhenCrossOrigin => "origin-when-cross-origin",
+        SameOrigin => "same-origin",
+        StrictOrigin => "strict-origin",
+        StrictOriginWhenCrossOrigin => "strict-origin-when-cross-origin",
+        UnsafeUrl => "unsafe-url",
+    }
+}
+
+/// The restrictions lifted for an `<iframe>`'s content, for
+/// [`IframeElement::sandbox()`][crate::IframeElement::sandbox]
+///
+/// The tokens are combined with `|`.  An empty set, which is the default,
+/// applies every restriction.
+///
+/// ```
+/// use html_builder::*;
+///
+/// let mut buf = Buffer::new();
+/// buf.iframe()
+///     .src("/preview")
+///     .sandbox(Sandbox::ALLOW_SCRIPTS | Sandbox::ALLOW_FORMS);
+/// assert_eq!(buf.finish(), "\
+/// <iframe src=\"/preview\" sandbox=\"allow-forms allow-scripts\">
+/// </iframe>
+/// ");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Sandbox(u16);
+
+/// The tokens, in the order they're written
+const SANDBOX_TOKENS: [&str; 14] = [
+    "allow-downloads",
+    "allow-forms",
+    "allow-modals",
+    "allow-orientation-lock",
+    "allow-pointer-lock",
+    "allow-popups",
+    "allow-popups-to-escape-sandbox",
+    "allow-presentation",
+    "allow-same-origin",
+    "allow-scripts",
+    "allow-storage-access-by-user-activation",
+    "allow-top-navigation",
+    "allow-top-navigation-by-user-activation",
+    "allow-top-navigation-to-custom-protocols",
+];
+
+impl Sandbox {
+    /// Every restriction applies
+    pub const NONE: Sandbox = Sandbox(0);
+    pub const ALLOW_DOWNLOADS: Sandbox = Sandbox(1 << 0);
+    pub const ALLOW_FORMS: Sandbox = Sandbox(1 << 1);
+    pub const ALLOW_MODALS: Sandbox = Sandbox(1 << 2);
+    pub const ALLOW_ORIENTATION_LOCK: Sandbox = Sandbox(1 << 3);
+    pub const ALLOW_POINTER_LOCK: Sandbox = Sandbox(1 << 4);
+    pub const ALLOW_POPUPS: Sandbox = Sandbox(1 << 5);
+    pub const ALLOW_POPUPS_TO_ESCAPE_SANDBOX: Sandbox = Sandbox(1 << 6);
+    pub const ALLOW_PRESENTATION: Sandbox = Sandbox(1 << 7);
+    pub const ALLOW_SAME_ORIGIN: Sandbox = Sandbox(1 << 8);
+    pub const ALLOW_SCRIPTS: Sandbox = Sandbox(1 << 9);
+    pub const ALLOW_STORAGE_ACCESS_BY_USER_ACTIVATION: Sandbox = Sandbox(1 << 10);
+    pub const ALLOW_TOP_NAVIGATION: Sandbox = Sandbox(1 << 11);
+    pub const ALLOW_TOP_NAVIGATION_BY_USER_ACTIVATION: Sandbox = Sandbox(1 << 12);
+    pub const ALLOW_TOP_NAVIGATION_TO_CUSTOM_PROTOCOLS: Sandbox = Sandbox(1 << 13);
+
+    /// Whether all of the restrictions lifted by `other` are lifted by
+    /// `self`
+    pub fn contains(self, other: Sandbox) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for Sandbox {
+    type Output = Sandbox;
+
+    fn bitor(self, other: Sandbox) -> Sandbox {
+        Sandbox(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for Sandbox {
+    fn bitor_assign(&mut self, other: Sandbox) {
+        self.0 |= other.0;
+    }
+}
+
+impl fmt::Display for Sandbox {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut tokens = SANDBOX_TOKENS
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.0 & 1 << i != 0)
+            .map(|(_, token)| token);
+        if let Some(first) = tokens.next() {
+            f.write_str(first)?;
+        }
+        for token in tokens {
+            write!(f, " {}", token)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{Buffer, Ctx, GlobalAttributes, Node, Void};
+use std::borrow::Cow;
+use std::fmt::{self, Display, Write};
+
+/// A buffer for writing XML into.
+///
+/// This works much like [`Buffer`], but it derefs to an [`XmlNode`] rather
+/// than a [`Node`], so only generic elements and attributes are available,
+/// not HTML's.  A few things are different to keep the output well-formed
+/// XML:
+///
+/// * The document starts with an XML declaration.
+/// * Empty children are written as self-closing tags (`<link/>`), so every
+///   element is explicitly closed.
+/// * Characters which aren't allowed in XML 1.0, such as most of the ASCII
+///   control characters, are replaced with U+FFFD in text and attribute
+///   values.
+///
+/// Otherwise text is escaped in the same way as in a [`Buffer`]; the default
+/// escaping of `&`, `<`, and `>` is sufficient for XML character data.
+///
+/// ```
+/// use html_builder::*;
+/// use std::fmt::Write;
+///
+/// let mut buf = XmlBuffer::new();
+/// let mut feed = buf.child("feed".into()).xmlns("", "http://www.w3.org/2005/Atom");
+/// writeln!(feed.child("title".into()), "Example feed")?;
+/// feed.void_child("link".into()).attr("href", "https://example.com/");
+/// assert_eq!(buf.finish(), "\
+/// <?xml version=\"1.0\" encoding=\"UTF-8\"?>
+/// <feed xmlns=\"http://www.w3.org/2005/Atom\">
+///  <title>
+/// Example feed
+///  </title>
+///  <link href=\"https://example.com/\"/>
+/// </feed>
+/// ");
+/// # Ok::<(), std::fmt::Error>(())
+/// ```
+pub struct XmlBuffer {
+    root: XmlNode<'static>,
+}
+
+/// An XML element.
+///
+/// Children are added with [`child()`][XmlNode::child] and
+/// [`void_child()`][XmlNode::void_child], and text is written with its
+/// `Write` impl or [`text()`][XmlNode::text].  As with a [`Node`], the
+/// element is closed when something is written to its parent.
+pub struct XmlNode<'a> {
+    node: Node<'a>,
+}
+
+/// An empty XML element, which is written as a self-closing tag.
+pub struct XmlVoid<'a> {
+    void: Void<'a>,
+}
+
+impl XmlBuffer {
+    /// Creates a new buffer containing only the XML declaration.
+    pub fn new() -> XmlBuffer {
+        XmlBuffer::default()
+    }
+
+    /// Closes all open tags and returns the buffer's contents.
+    pub fn finish(self) -> String {
+        Buffer {
+            node: self.root.node,
+        }
+        .finish()
+    }
+
+    /// Closes all open tags, returns the buffer's contents, and empties the
+    /// buffer (apart from the XML declaration) so that it can be used again.
+    ///
+    /// See [`Buffer::finish_and_reset()`].
+    pub fn finish_and_reset(&mut self) -> String {
+        let text = self.root.node.finish_and_reset();
+        self.root.node.ctx.get().wtr.raw(DECLARATION);
+        text
+    }
+}
+
+pub(crate) const DECLARATION: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n";
+
+impl Default for XmlBuffer {
+    fn default() -> XmlBuffer {
+        let mut ctx = Ctx {
+            xml: true,
+            ..Ctx::default()
+        };
+        ctx.wtr.raw(DECLARATION);
+        XmlBuffer {
+            root: XmlNode {
+                node: Buffer::from_ctx(ctx).node,
+            },
+        }
+    }
+}
+
+impl std::ops::Deref for XmlBuffer {
+    type Target = XmlNode<'static>;
+    fn deref(&self) -> &XmlNode<'static> {
+        &self.root
+    }
+}
+
+impl std::ops::DerefMut for XmlBuffer {
+    fn deref_mut(&mut self) -> &mut XmlNode<'static> {
+        &mut self.root
+    }
+}
+
+impl<'a> XmlNode<'a> {
+    /// Creates a child element
+    pub fn child<'b>(&'b mut self, tag: Cow<'static, str>) -> XmlNode<'b> {
+        XmlNode {
+            node: self.node.child(tag),
+        }
+    }
+
+    /// Creates an empty child element
+    pub fn void_child<'b>(&'b mut self, tag: Cow<'static, str>) -> XmlVoid<'b> {
+        XmlVoid {
+            void: self.node.void_child(tag),
+        }
+    }
+
+    /// Sets an attribute to the given value, escaping it
+    pub fn attr(self, name: &str, value: impl Display) -> XmlNode<'a> {
+        let value = value.to_string();
+        XmlNode {
+            node: self.node.attr_value(name, replace_illegal(&value)),
+        }
+    }
+
+    /// Declares an XML namespace on this element
+    ///
+    /// An empty `prefix` declares the default namespace.  The URI is
+    /// escaped for use in a double-quoted attribute.
+    pub fn xmlns(self, prefix: &str, uri: &str) -> XmlNode<'a> {
+        self.attr(&xmlns_name(prefix), uri)
+    }
+
+    /// Writes some text, escaping it, and ends the line
+    pub fn text(&mut self, text: impl Display) -> &mut Self {
+        // Writing to a node never fails
+        writeln!(self, "{}", text).unwrap();
+        self
+    }
+}
+
+impl<'a> Write for XmlNode<'a> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.node.write_str(&replace_illegal(s))
+    }
+}
+
+impl<'a> XmlVoid<'a> {
+    /// Sets an attribute to the given value, escaping it
+    pub fn attr(self, name: &str, value: impl Display) -> XmlVoid<'a> {
+        let value = value.to_string();
+        XmlVoid {
+            void: self.void.attr_value(name, replace_illegal(&value)),
+        }
+    }
+
+    /// Declares an XML namespace on this element
+    ///
+    /// See [`XmlNode::xmlns()`].
+    pub fn xmlns(self, prefix: &str, uri: &str) -> XmlVoid<'a> {
+        self.attr(&xmlns_name(prefix), uri)
+    }
+}
+
+fn xmlns_name(prefix: &str) -> Cow<'static, str> {
+    if prefix.is_empty() {
+        Cow::Borrowed("xmlns")
+    } else {
+        Cow::Owned(format!("xmlns:{}", prefix))
+    }
+}
+
+/// Replaces the characters which aren't allowed in XML 1.0 documents with
+/// U+FFFD
+fn replace_illegal(s: &str) -> Cow<'_, str> {
+    let legal = |c: char| {
+        matches!(c, '\t' | '\n' | '\r' | ' '..='\u{D7FF}' | '\u{E000}'..='\u{FFFD}')
+            || c >= '\u{10000}'
+    };
+    if s.chars().all(legal) {
+        Cow::Borrowed(s)
+    } else {
+        Cow::Owned(
+            s.chars()
+                .map(|c| if legal(c) { c } else { '\u{FFFD}' })
+                .collect(),
+        )
+    }
+}
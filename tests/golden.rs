@@ -110,3 +110,2067 @@ fn pre_post_inner() {
     writeln!(a, "a post").unwrap();
     insta::assert_snapshot!(buf.finish());
 }
+
+#[test]
+fn doctypes() {
+    let mut buf = Buffer::new();
+    buf.doctype();
+    buf.doctype_html4();
+    buf.doctype_xhtml1_strict();
+    buf.doctype_custom("svg");
+    insta::assert_snapshot!(buf.finish());
+}
+
+#[test]
+fn conditional_comment() -> std::fmt::Result {
+    let mut buf = Buffer::new();
+    let mut body = buf.body();
+    let mut mso = body.conditional_comment("if mso");
+    let mut table = mso.table().attr("role='presentation'");
+    writeln!(table.tr().td(), "Outlook only")?;
+    writeln!(body.p(), "Everyone")?;
+    insta::assert_snapshot!(buf.finish());
+    Ok(())
+}
+
+#[test]
+fn typed_elements() -> std::fmt::Result {
+    let mut buf = Buffer::new();
+    let mut body = buf.body();
+    writeln!(
+        body.a().href("/search?q=fish&chips").target("_blank"),
+        "Search"
+    )?;
+    body.img()
+        .src("cat.jpg")
+        .alt("A \"cat\"")
+        .width(640)
+        .height(480);
+    let mut form = body.form().action("/login").method("post");
+    form.input()
+        .type_("checkbox")
+        .name("remember")
+        .checked(true);
+    form.input().type_("text").name("user").disabled(false);
+    writeln!(form.button().type_("submit"), "Log in")?;
+    insta::assert_snapshot!(buf.finish());
+    Ok(())
+}
+
+#[test]
+fn global_attributes() -> std::fmt::Result {
+    let mut buf = Buffer::new();
+    let mut html = buf.html().lang("en").dir("ltr");
+    writeln!(html.head().title(), "Title")?;
+    let mut body = html.body().class("page dark").data_attr("user-id", 42);
+    writeln!(
+        body.div()
+            .id("greeting")
+            .title_attr("Hello & welcome")
+            .tabindex(-1)
+            .hidden(true)
+            .contenteditable(false)
+            .translate(false),
+        "Hi"
+    )?;
+    body.hr().class("divider").style_attr("margin: 0");
+    writeln!(body.a().href("/").id("home"), "Home")?;
+    insta::assert_snapshot!(buf.finish());
+    Ok(())
+}
+
+#[test]
+fn invalid_attribute_names() {
+    let mut buf = Buffer::new();
+    buf.div()
+        .data_attr("x onclick=alert(1) y", 1)
+        .aria("label\"", "x")
+        .data_attr("ok", 2);
+    assert_eq!(buf.finish(), "<div data-ok=\"2\">\n</div>\n");
+
+    let mut buf = Buffer::new().strict();
+    buf.div().data_attr("a b", 1);
+    assert_eq!(buf.validation_errors().len(), 1);
+    assert_eq!(buf.finish(), "<div>\n</div>\n");
+}
+
+#[test]
+fn strict_nesting() {
+    let mut buf = Buffer::new().strict();
+    let mut body = buf.body();
+    body.ul().li();
+    body.li();
+    let mut p = body.p();
+    p.div();
+    p.span();
+    let mut form = body.form();
+    form.div().form();
+    let mut table = body.table();
+    table.tbody().tr().td();
+    table.td();
+    let errors = buf
+        .validation_errors()
+        .iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>();
+    insta::assert_debug_snapshot!(errors);
+}
+
+#[test]
+fn strict_names() {
+    let mut buf = Buffer::new().strict();
+    buf.doctype();
+    let mut body = buf.body();
+    body.child("my-widget".into()).attr_value("data-x", 1);
+    body.child("atom:link".into()).attr_flag("hidden", true);
+    body.child("bad tag".into());
+    body.child("2col".into());
+    body.div()
+        .attr_value("on click", "x")
+        .attr_flag("a=b", true);
+    insta::assert_debug_snapshot!(buf.validation_errors());
+}
+
+#[test]
+fn late_attributes() -> std::fmt::Result {
+    let mut buf = Buffer::new().strict();
+    let mut div = buf.div();
+    div.img();
+    let mut div = div.attr("class='late'");
+    writeln!(div, "text")?;
+    let div = div.id("also-late");
+    drop(div);
+    let errors = buf.validation_errors();
+    insta::assert_snapshot!(buf.finish());
+    insta::assert_debug_snapshot!(errors);
+    Ok(())
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "added after the element's contents")]
+fn late_attribute_panics() {
+    let mut buf = Buffer::new();
+    let mut div = buf.div();
+    div.span();
+    div.attr("class='late'");
+}
+
+#[test]
+fn merged_attributes() -> std::fmt::Result {
+    fn highlight(node: &mut Node) {
+        node.set_attr("class", "highlight");
+        node.set_attr("title", "Highlighted");
+    }
+    let mut buf = Buffer::new();
+    let mut div = buf.div().class("card").title_attr("Card").hidden(true);
+    highlight(&mut div);
+    let mut div = div.hidden(true).attr("data-raw='1'");
+    writeln!(div, "Hello")?;
+    insta::assert_snapshot!(buf.finish());
+    Ok(())
+}
+
+#[test]
+fn fallible() -> Result<(), Error> {
+    let mut buf = Buffer::new().strict();
+    let mut list = buf.try_child("ul".into())?;
+    list.try_child("li".into())?;
+    let err = list.try_child("td".into()).err();
+    list.try_void_child("br".into())?;
+    insta::assert_debug_snapshot!(err);
+    assert_eq!(buf.try_finish()?, "<ul>\n <li>\n </li>\n <br>\n</ul>\n");
+
+    let mut buf = Buffer::new().strict();
+    buf.li();
+    insta::assert_snapshot!(buf.try_finish().unwrap_err());
+    Ok(())
+}
+
+#[test]
+fn tree() {
+    let mut tree = Tree::new();
+    tree.doctype();
+    let html = tree.child("html");
+    html.set_attr("lang", "en");
+    let head = html.child("head");
+    head.child("meta").set_attr("charset", "utf-8");
+    let body = html.child("body");
+    body.child("p").append_text("Salt & pepper");
+    body.child("p").append_text("Removed");
+    body.append_comment("Footer goes here");
+    body.child("input")
+        .set_flag("disabled")
+        .set_attr("value", "\"x\"");
+
+    let body = tree.find_where_mut(&|el| el.tag() == "body").unwrap();
+    body.remove(1);
+    body.add_class("dark").add_class("wide").add_class("dark");
+    let head = tree.find_mut("html > head").unwrap();
+    head.child("script").append_text("if (a < b) {}");
+    head.insert(0, Element::new("title"));
+    insta::assert_snapshot!(tree.render());
+}
+
+#[test]
+fn selectors() {
+    let mut tree = Tree::new();
+    let body = tree.child("body");
+    let nav = body.child("nav");
+    nav.set_attr("id", "top");
+    nav.child("a").set_attr("href", "/").append_text("Home");
+    nav.child("span").child("a").set_attr("href", "/about");
+    let card = body.child("div");
+    card.add_class("card").add_class("wide");
+    card.child("a").set_attr("href", "/card");
+    card.child("input").set_attr("type", "text");
+
+    let hrefs = |sel| {
+        tree.select(sel)
+            .iter()
+            .map(|el| format!("{}[{}]", el.tag(), el.attr("href").unwrap_or("")))
+            .collect::<Vec<_>>()
+    };
+    insta::assert_debug_snapshot!((
+        hrefs("a"),
+        hrefs("#top > a"),
+        hrefs("nav a"),
+        hrefs("div.card.wide > a, span a"),
+        hrefs("[type=\"text\"]"),
+        hrefs("body > *"),
+        hrefs(".missing"),
+    ));
+    for bad in ["", "a >", "a:hover", "[href", "a,,b"] {
+        assert!(Selector::parse(bad).is_err(), "{:?}", bad);
+    }
+
+    tree.select_mut("a[href]", |el| {
+        el.set_attr("rel", "nofollow");
+    });
+    assert_eq!(tree.select("[rel=nofollow]").len(), 3);
+    tree.find_mut("#top").unwrap().remove(0);
+    assert_eq!(tree.find("nav > *").unwrap().tag(), "span");
+}
+
+#[cfg(feature = "parse")]
+#[test]
+fn parse() {
+    let html = r#"<!DOCTYPE html>
+<html lang="en"><head><title>Old &amp; busted</title>
+<script>if (a < b) { go(); }</script></head>
+<body><!-- nav --><div class=card hidden><p>One<p>Two <b>bold</b></div>
+<pre>  keep
+  this</pre></body></html>"#;
+    let mut tree = Tree::parse(html);
+    tree.find_mut("div.card")
+        .unwrap()
+        .child("p")
+        .append_text("Three");
+    insta::assert_snapshot!(tree.render());
+}
+
+#[cfg(feature = "parse")]
+#[test]
+fn parse_document_prefix() {
+    for html in ["<html>", "<HTML>\u{e9}\u{e9}", "<!doctype html>\u{e9}"] {
+        let out = Tree::parse(html).render();
+        assert!(out.contains("<html>"), "{:?}: {}", html, out);
+        assert!(out.contains("<body>"), "{:?}: {}", html, out);
+    }
+    assert!(!Tree::parse("<p>\u{e9}</p>").render().contains("<body>"));
+}
+
+#[test]
+fn placeholders() {
+    let mut buf = Buffer::new().strict();
+    let mut table = buf.table();
+    let header = table.placeholder();
+    let first = table.placeholder();
+    let mut total = 0;
+    for n in [3, 4] {
+        writeln!(table.tr().td(), "{}", n).unwrap();
+        total += n;
+    }
+    let footer = table.placeholder();
+    buf.fill(footer, |table| {
+        writeln!(table.tr().td().class("total"), "{}", total).unwrap();
+    });
+    buf.fill(header, |table| {
+        writeln!(table.caption(), "Numbers").unwrap();
+    });
+    buf.fill(first, |table| {
+        writeln!(table.tr().td(), "first").unwrap();
+    });
+    buf.fill(header, |table| {
+        table.colgroup().col();
+    });
+    buf.fill(first, |table| {
+        table.li();
+    });
+    assert_eq!(buf.validation_errors().len(), 1);
+    insta::assert_snapshot!(buf.finish());
+}
+
+#[test]
+fn head_manager() {
+    let mut buf = Buffer::new();
+    buf.doctype();
+    let mut html = buf.html();
+    let mut head = html.head();
+    head.meta().charset("utf-8");
+    head.head_slot();
+    let mut body = html.body();
+    body.head_manager()
+        .title("Draft")
+        .meta("description", "First")
+        .meta_property("og:title", "Page");
+    for _ in 0..2 {
+        body.head_manager()
+            .link("stylesheet", "/a.css")
+            .meta("description", "A <page>");
+        writeln!(body.p(), "Section").unwrap();
+    }
+    body.head_manager()
+        .title("Final")
+        .link("stylesheet", "/b.css");
+    insta::assert_snapshot!(buf.finish());
+}
+
+#[test]
+fn head_manager_unsafe_link() {
+    let mut buf = Buffer::new();
+    let mut html = buf.html();
+    html.head().head_slot();
+    html.body()
+        .head_manager()
+        .link("icon", "javascript:alert(1)");
+    let out = buf.finish();
+    assert!(!out.contains("javascript:"), "{}", out);
+    assert!(out.contains(BLOCKED_URL), "{}", out);
+}
+
+#[test]
+fn head_manager_in_slot() {
+    let mut buf = Buffer::new();
+    let mut html = buf.html();
+    html.head().head_slot();
+    let slot = html.body().placeholder();
+    buf.fill(slot, |node| {
+        node.head_manager().title("Filled");
+        node.p().text("Content");
+    });
+    assert_eq!(
+        buf.finish(),
+        "\
+<html>
+ <head>
+  <title>
+Filled
+  </title>
+ </head>
+ <body>
+  <p>
+Content
+  </p>
+ </body>
+</html>
+"
+    );
+}
+
+#[derive(Default)]
+struct Events(Vec<String>);
+
+impl Sink for Events {
+    fn start_tag(&mut self, depth: usize, tag: &str, attrs: &[Attribute]) {
+        self.0.push(format!("{} start {} {:?}", depth, tag, attrs));
+    }
+    fn void_tag(&mut self, depth: usize, tag: &str, attrs: &[Attribute], self_closing: bool) {
+        self.0.push(format!(
+            "{} void {} {:?} {}",
+            depth, tag, attrs, self_closing
+        ));
+    }
+    fn end_tag(&mut self, depth: usize, tag: &str) {
+        self.0.push(format!("{} end {}", depth, tag));
+    }
+    fn text(&mut self, text: &str) {
+        self.0.push(format!("text {:?}", text));
+    }
+    fn raw(&mut self, html: &str) {
+        self.0.push(format!("raw {:?}", html));
+    }
+    fn start_comment(&mut self, depth: usize) {
+        self.0.push(format!("{} start comment", depth));
+    }
+    fn end_comment(&mut self, depth: usize) {
+        self.0.push(format!("{} end comment", depth));
+    }
+    fn start_conditional(&mut self, depth: usize, cond: &str) {
+        self.0.push(format!("{} start conditional {}", depth, cond));
+    }
+    fn end_conditional(&mut self, depth: usize) {
+        self.0.push(format!("{} end conditional", depth));
+    }
+}
+
+#[test]
+fn custom_sink() {
+    let mut buf = Buffer::with_sink(Events::default());
+    buf.doctype();
+    let mut html = buf.html().attr("lang='en'");
+    write!(html.comment(), "hi").unwrap();
+    let mut body = html.body().class("a").class("b");
+    write!(body.p().hidden(true), "1 < 2").unwrap();
+    write!(body.p().raw(), "<br>").unwrap();
+    body.img().attr_value("src", "x.png");
+    body.conditional_comment("if IE").script();
+    insta::assert_debug_snapshot!(buf.into_sink::<Events>().0);
+}
+
+#[test]
+fn writer() {
+    fn table(buf: &mut Buffer) {
+        let mut table = buf.table();
+        for i in 0..1000 {
+            writeln!(table.tr().td().class("n"), "{} < {}", i, i + 1).unwrap();
+        }
+    }
+    let mut expected = Buffer::new();
+    table(&mut expected);
+    let mut buf = Buffer::to_writer(Vec::new());
+    table(&mut buf);
+    let bytes = buf.finish_writer::<Vec<u8>>().unwrap();
+    assert_eq!(String::from_utf8(bytes).unwrap(), expected.finish());
+
+    struct Full;
+    impl std::io::Write for Full {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::WriteZero.into())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+    let mut buf = Buffer::to_writer(Full);
+    buf.p();
+    let err = buf.finish_writer::<Full>().err().unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn streaming() {
+    use futures_core::Stream;
+    use std::pin::Pin;
+    use std::task::{Context, Poll, Waker};
+
+    fn try_next(stream: &mut HtmlStream) -> Option<bytes::Bytes> {
+        let mut cx = Context::from_waker(Waker::noop());
+        match Pin::new(stream).poll_next(&mut cx) {
+            Poll::Ready(chunk) => chunk,
+            Poll::Pending => None,
+        }
+    }
+
+    let (mut buf, mut stream) = Buffer::streaming();
+    let mut html = buf.html();
+    writeln!(html.head().title(), "Title").unwrap();
+    assert_eq!(try_next(&mut stream), None);
+    html.flush();
+    assert_eq!(
+        try_next(&mut stream).unwrap(),
+        "<html>\n <head>\n  <title>\nTitle\n  </title>\n </head>\n"
+    );
+    let mut body = html.body();
+    for i in 0..1000 {
+        writeln!(body.p(), "{}", i).unwrap();
+    }
+    assert!(try_next(&mut stream).is_some());
+    buf.finish_stream();
+    while try_next(&mut stream).is_some() {}
+    assert!(matches!(
+        Pin::new(&mut stream).poll_next(&mut Context::from_waker(Waker::noop())),
+        Poll::Ready(None)
+    ));
+}
+
+#[test]
+fn chunks() {
+    let build = || {
+        let mut buf = Buffer::new();
+        let mut div = buf.div();
+        writeln!(div, "{}", "é".repeat(40)).unwrap();
+        for i in 0..50 {
+            writeln!(div.p(), "{}", i).unwrap();
+        }
+        buf
+    };
+    let chunks = build().into_chunks(64).collect::<Vec<_>>();
+    assert_eq!(chunks.concat(), build().finish());
+    for chunk in &chunks {
+        assert!(chunk.ends_with('\n'));
+        assert!(chunk.len() <= 64 || chunk.lines().count() == 1);
+    }
+    assert_eq!(Buffer::new().into_chunks(64).count(), 0);
+}
+
+#[test]
+fn chunks_minified() {
+    let build = || {
+        let mut buf = Buffer::new().format(Format::new().minify(true));
+        let mut ul = buf.ul();
+        for i in 0..20 {
+            writeln!(ul.li(), "{}", i).unwrap();
+        }
+        buf
+    };
+    let chunks = build().into_chunks(32).collect::<Vec<_>>();
+    assert_eq!(chunks.concat(), build().finish());
+    assert!(chunks.len() > 1);
+    for chunk in &chunks {
+        assert!(chunk.len() <= 32);
+        assert!(chunk.ends_with('>'));
+    }
+}
+
+#[cfg(feature = "axum")]
+#[test]
+fn axum_response() {
+    use axum_core::response::IntoResponse;
+
+    let mut buf = Buffer::new();
+    buf.p();
+    let resp = buf.into_response();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers()["content-type"], "text/html; charset=utf-8");
+
+    let resp = Buffer::to_writer(vec![]).into_response();
+    assert_eq!(resp.status(), 500);
+
+    let (buf, stream) = Buffer::streaming();
+    buf.finish_stream();
+    let resp = stream.into_response();
+    assert_eq!(resp.headers()["content-type"], "text/html; charset=utf-8");
+}
+
+#[cfg(feature = "actix")]
+#[test]
+fn actix_response() {
+    use actix_web::Responder;
+
+    let req = actix_web::test::TestRequest::default().to_http_request();
+    let mut buf = Buffer::new();
+    buf.p();
+    let resp = buf.respond_to(&req);
+    assert_eq!(resp.status(), 200);
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "text/html; charset=utf-8"
+    );
+
+    let resp = Buffer::to_writer(vec![]).respond_to(&req);
+    assert_eq!(resp.status(), 500);
+
+    let (buf, stream) = Buffer::streaming();
+    buf.finish_stream();
+    let resp = stream.respond_to(&req);
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "text/html; charset=utf-8"
+    );
+}
+
+#[cfg(feature = "http-body")]
+#[test]
+fn http_body() {
+    use http_body::Body;
+    use std::pin::Pin;
+    use std::task::{Context, Poll, Waker};
+
+    let mut buf = Buffer::new();
+    writeln!(buf.p(), "Hello").unwrap();
+    let mut body = HtmlStream::from(buf);
+    let mut cx = Context::from_waker(Waker::noop());
+    match Pin::new(&mut body).poll_frame(&mut cx) {
+        Poll::Ready(Some(Ok(frame))) => {
+            assert_eq!(frame.into_data().unwrap(), "<p>\nHello\n</p>\n")
+        }
+        _ => panic!("expected a data frame"),
+    }
+    assert!(matches!(
+        Pin::new(&mut body).poll_frame(&mut cx),
+        Poll::Ready(None)
+    ));
+}
+
+#[test]
+fn send() {
+    fn assert_send<T: Send>() {}
+    assert_send::<Buffer>();
+    assert_send::<XmlBuffer>();
+    assert_send::<Node>();
+}
+
+#[test]
+fn reuse() -> std::fmt::Result {
+    let mut buf = Buffer::new().strict();
+    let mut html = buf.html();
+    html.head().head_slot();
+    html.body().head_manager().title("First");
+    html.body().li();
+    let first = buf.finish_and_reset();
+    assert!(first.contains("First"));
+    assert!(buf.validation_errors().is_empty());
+
+    writeln!(buf.p(), "Second")?;
+    assert_eq!(buf.finish_and_reset(), "<p>\nSecond\n</p>\n");
+    buf.li();
+    assert_eq!(buf.validation_errors().len(), 1);
+
+    let mut buf = Buffer::new();
+    writeln!(buf.p(), "Third")?;
+    let mut out = String::new();
+    buf.finish_into(&mut out);
+    let mut buf = Buffer::new();
+    writeln!(buf.p(), "Third")?;
+    assert_eq!(buf.finish_bytes(), out.as_bytes());
+
+    let mut xml = XmlBuffer::new();
+    xml.void_child("a".into());
+    let first = xml.finish_and_reset();
+    xml.void_child("a".into());
+    assert_eq!(xml.finish(), first);
+    Ok(())
+}
+
+#[test]
+fn xml() {
+    let mut buf = XmlBuffer::new();
+    let mut feed = buf
+        .child("feed".into())
+        .xmlns("", "http://www.w3.org/2005/Atom")
+        .xmlns("media", "http://search.yahoo.com/mrss/?a=1&b=2");
+    feed.child("title".into())
+        .attr("type", "text")
+        .text("Fish & chips <3");
+    feed.void_child("media:thumbnail".into())
+        .attr("url", "/a\"b\".png")
+        .attr("media:width", 80);
+    insta::assert_snapshot!(buf.finish());
+}
+
+#[test]
+fn xml_illegal_chars() {
+    let mut buf = XmlBuffer::new();
+    buf.child("note".into())
+        .attr("id", "a\u{1}b")
+        .text("bell\u{7} tab\t ok \u{1F600}");
+    assert_eq!(
+        buf.finish(),
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <note id=\"a\u{FFFD}b\">\nbell\u{FFFD} tab\t ok \u{1F600}\n</note>\n"
+    );
+}
+
+#[test]
+fn segmented() -> std::fmt::Result {
+    fn write(buf: &mut Buffer) -> std::fmt::Result {
+        let mut list = buf.ul();
+        for i in 0..1000 {
+            writeln!(list.li().class("item"), "Item {} & more", i)?;
+        }
+        Ok(())
+    }
+    let mut expected = Buffer::new();
+    write(&mut expected)?;
+    let expected = expected.finish();
+
+    let mut buf = Buffer::segmented(256);
+    write(&mut buf)?;
+    let segments = buf.finish_segments();
+    assert!(segments.len() > 10);
+    assert!(segments.iter().all(|x| x.len() < 512));
+    assert_eq!(segments.concat(), expected);
+
+    let mut buf = Buffer::segmented(256);
+    write(&mut buf)?;
+    assert_eq!(buf.finish(), expected);
+    Ok(())
+}
+
+#[test]
+fn fragment_cache() -> std::fmt::Result {
+    fn footer(node: &mut Node) {
+        let mut footer = node.footer();
+        writeln!(footer.p().class("small"), "© Example & co").unwrap();
+        footer.hr();
+        footer.comment().write_str("end of footer").unwrap();
+    }
+    let mut cache = FragmentCache::new();
+    let mut buf = Buffer::new();
+    let mut body = buf.body();
+    body.cached(&mut cache, 1, footer);
+    body.div()
+        .cached(&mut cache, 1, |_| panic!("should be cached"));
+    let cached = buf.finish();
+
+    let mut buf = Buffer::new();
+    let mut body = buf.body();
+    footer(&mut body);
+    footer(&mut body.div());
+    assert_eq!(cached, buf.finish());
+    assert_eq!(cache.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn cached_nonce() {
+    fn page(cache: &mut FragmentCache, nonce: &str) -> String {
+        let mut buf = Buffer::new().csp_nonce(nonce);
+        buf.body().cached(cache, 0, |node| {
+            node.script().raw().text("track();");
+        });
+        buf.finish()
+    }
+    let mut cache = FragmentCache::new();
+    assert!(page(&mut cache, "AAA").contains("<script nonce=\"AAA\">"));
+    let second = page(&mut cache, "BBB");
+    assert!(second.contains("<script nonce=\"BBB\">"));
+    assert!(!second.contains("AAA"));
+}
+
+#[test]
+fn cached_head_items() {
+    fn page(cache: &mut FragmentCache) -> String {
+        let mut buf = Buffer::new();
+        let mut html = buf.html();
+        html.head().head_slot();
+        html.body().cached(cache, 0, |node| {
+            node.head_manager()
+                .link("stylesheet", "/footer.css")
+                .meta("generator", "footer");
+            node.footer().text("Footer");
+        });
+        buf.finish()
+    }
+    let mut cache = FragmentCache::new();
+    let first = page(&mut cache);
+    assert_eq!(
+        first,
+        "\
+<html>
+ <head>
+  <meta name=\"generator\" content=\"footer\">
+  <link rel=\"stylesheet\" href=\"/footer.css\">
+ </head>
+ <body>
+  <footer>
+Footer
+  </footer>
+ </body>
+</html>
+"
+    );
+    assert_eq!(page(&mut cache), first);
+}
+
+#[test]
+fn render_each() -> std::fmt::Result {
+    let mut buf = Buffer::new();
+    let mut table = buf.table();
+    table.render_each(1..=3, |table, i| {
+        table
+            .tr()
+            .render_each(1..=3, |tr, j| write!(tr.td(), "{}", i * j))
+    })?;
+    let mut expected = Buffer::new();
+    let mut table = expected.table();
+    for i in 1..=3 {
+        let mut tr = table.tr();
+        for j in 1..=3 {
+            write!(tr.td(), "{}", i * j)?;
+        }
+    }
+    assert_eq!(buf.finish(), expected.finish());
+    Ok(())
+}
+
+#[cfg(feature = "macros")]
+#[test]
+fn html_macro() -> std::fmt::Result {
+    fn card(parent: &mut Node, n: usize, urgent: bool) -> std::fmt::Result {
+        html!(parent, section.card-body data-n=(n) hidden[n == 0] {
+            @if n == 0 {
+                p { "None" }
+            } @else if urgent {
+                p.alert { (n) " left!" }
+            } @else {
+                p { (n) }
+            }
+            hr;
+        })
+    }
+    let mut buf = Buffer::new();
+    html!(buf, my - widget {})?;
+    for (n, urgent) in [(0, false), (1, true), (2, false)] {
+        card(&mut buf, n, urgent)?;
+    }
+
+    let mut expected = Buffer::new();
+    expected.child("my-widget".into());
+    for (n, urgent) in [(0, false), (1, true), (2, false)] {
+        let mut section = expected
+            .section()
+            .class("card-body")
+            .attr_value("data-n", n)
+            .hidden(n == 0);
+        if n == 0 {
+            writeln!(section.p(), "None")?;
+        } else if urgent {
+            let mut p = section.p().class("alert");
+            writeln!(p, "{}", n)?;
+            writeln!(p, " left!")?;
+        } else {
+            writeln!(section.p(), "{}", n)?;
+        }
+        section.hr();
+    }
+    assert_eq!(buf.finish(), expected.finish());
+    Ok(())
+}
+
+#[test]
+fn attrs_macro() {
+    let label = Some("Say \"hi\"");
+    let mut buf = Buffer::new();
+    buf.button().attr(
+        attrs!(type = "button", aria-label ?= label, data-count = 3, hidden if false, autofocus,),
+    );
+    buf.input().attr(attrs!());
+    assert_eq!(
+        buf.finish(),
+        "<button type=\"button\" aria-label=\"Say &quot;hi&quot;\" data-count=\"3\" autofocus>\n</button>\n<input>\n"
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serialize() -> Result<(), Error> {
+    use serde::Serialize;
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize)]
+    enum Shape {
+        Point,
+        Circle(f64),
+        Line(i32, i32),
+        Rect { w: u32, h: u32 },
+    }
+
+    #[derive(Serialize)]
+    struct Page {
+        title: &'static str,
+        draft: bool,
+        parent: Option<u32>,
+        shapes: Vec<Shape>,
+        tags: BTreeMap<&'static str, usize>,
+    }
+
+    let page = Page {
+        title: "<Shapes>",
+        draft: false,
+        parent: None,
+        shapes: vec![
+            Shape::Point,
+            Shape::Circle(1.5),
+            Shape::Line(1, 2),
+            Shape::Rect { w: 3, h: 4 },
+        ],
+        tags: BTreeMap::from([("geometry", 2), ("maths", 1)]),
+    };
+    let mut buf = Buffer::new();
+    buf.body().serialize(&page)?;
+    insta::assert_snapshot!(buf.finish());
+    Ok(())
+}
+
+#[cfg(feature = "macros")]
+#[test]
+fn derive_table() {
+    #[derive(ToHtmlTable)]
+    struct Row<T: std::fmt::Display> {
+        id: u32,
+        #[html(rename = "Value (escaped)")]
+        value: T,
+        #[html(skip)]
+        #[allow(dead_code)]
+        secret: bool,
+    }
+    let rows = [
+        Row {
+            id: 1,
+            value: "<b>",
+            secret: true,
+        },
+        Row {
+            id: 2,
+            value: "ok",
+            secret: false,
+        },
+    ];
+    let mut buf = Buffer::new();
+    Row::to_html_table(&rows, &mut buf.body());
+    insta::assert_snapshot!(buf.finish());
+}
+
+#[cfg(feature = "macros")]
+#[test]
+fn derive_form() {
+    #[derive(HtmlForm)]
+    struct Product {
+        name: String,
+        #[html(textarea)]
+        description: String,
+        price: f64,
+        stock: Option<u32>,
+        #[html(rename = "On sale", type = "checkbox")]
+        #[allow(dead_code)]
+        #[html(skip)]
+        internal: bool,
+        visible: bool,
+    }
+    let product = Product {
+        name: "Fish & chips".into(),
+        description: "Served with <mushy> peas".into(),
+        price: 4.5,
+        stock: None,
+        internal: true,
+        visible: false,
+    };
+    let mut buf = Buffer::new();
+    product.to_html_form(&mut buf, "/products/1");
+    insta::assert_snapshot!(buf.finish());
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn json() {
+    let value = serde_json::json!({
+        "users": [
+            { "name": "Alice", "admin": true },
+            { "name": "Bob", "admin": false },
+        ],
+        "mixed": [1, null, { "a": "<b>" }],
+    });
+    let mut buf = Buffer::new();
+    buf.json(&value);
+    insta::assert_snapshot!(buf.finish());
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time_datetime() {
+    let when = time::macros::datetime!(2021-04-12 14:30 +02:00);
+    let mut buf = Buffer::new();
+    buf.p().time_datetime(when);
+    assert_eq!(
+        buf.finish(),
+        "\
+<p>
+ <time datetime=\"2021-04-12T14:30:00+02:00\">
+12 April 2021 14:30
+ </time>
+</p>
+"
+    );
+}
+
+#[test]
+fn unsafe_urls() {
+    for url in [
+        "javascript:alert(1)",
+        "  JAVASCRIPT:alert(1)",
+        "java\nscript:alert(1)",
+        "vbscript:msgbox(1)",
+        "data:text/html,<script>alert(1)</script>",
+        "DATA: TEXT/HTML;base64,PHNjcmlwdD4=",
+    ]
+    .iter()
+    {
+        assert!(!is_safe_url(url), "{:?}", url);
+    }
+    for url in [
+        "https://example.com/",
+        "mailto:alice@example.com",
+        "/search?q=javascript:alert(1)",
+        "#javascript:alert(1)",
+        "data:image/png;base64,iVBORw0KGgo=",
+        "",
+    ]
+    .iter()
+    {
+        assert!(is_safe_url(url), "{:?}", url);
+    }
+    let mut buf = Buffer::new();
+    buf.img().src("data:text/html,hi");
+    buf.form().action("javascript:go()");
+    buf.a_text("https://example.com/", "Example");
+    assert_eq!(
+        buf.finish(),
+        "\
+<img src=\"about:invalid#blocked\">
+<form action=\"about:invalid#blocked\">
+</form>
+<a href=\"https://example.com/\">
+Example
+</a>
+"
+    );
+}
+
+#[test]
+fn noopener() {
+    let mut buf = Buffer::new().noopener();
+    buf.a().href("/a").target("_BLANK");
+    buf.a().href("/b").target("_blank").rel("noopener");
+    buf.a().href("/c").target("_self");
+    assert_eq!(
+        buf.finish(),
+        "\
+<a href=\"/a\" target=\"_BLANK\" rel=\"noopener noreferrer\">
+</a>
+<a href=\"/b\" target=\"_blank\" rel=\"noopener noreferrer\">
+</a>
+<a href=\"/c\" target=\"_self\">
+</a>
+"
+    );
+
+    let mut buf = Buffer::new();
+    buf.a().href("/a").target("_blank");
+    assert_eq!(buf.finish(), "<a href=\"/a\" target=\"_blank\">\n</a>\n");
+}
+
+#[test]
+fn csp_nonce() {
+    let mut buf = Buffer::new().csp_nonce("abc123");
+    assert_eq!(buf.nonce(), Some("abc123"));
+    let mut head = buf.head();
+    let slot = head.placeholder();
+    head.style().raw().text("p { color: red }");
+    head.script().attr_value("nonce", "other").src("/app.js");
+    head.meta().charset("utf-8");
+    buf.fill(slot, |node| {
+        node.script().src("/late.js");
+    });
+    assert_eq!(
+        buf.finish(),
+        "\
+<head>
+ <script src=\"/late.js\" nonce=\"abc123\">
+ </script>
+ <style nonce=\"abc123\">
+p { color: red }
+ </style>
+ <script nonce=\"other\" src=\"/app.js\">
+ </script>
+ <meta charset=\"utf-8\">
+</head>
+"
+    );
+}
+
+#[test]
+fn social_cards() {
+    let og = OpenGraph::new("Release notes")
+        .type_("article")
+        .site_name("Example");
+    let mut buf = Buffer::new();
+    buf.head().open_graph(&og).twitter_card(&og);
+    assert_eq!(
+        buf.finish(),
+        "\
+<head>
+ <meta property=\"og:type\" content=\"article\">
+ <meta property=\"og:title\" content=\"Release notes\">
+ <meta property=\"og:site_name\" content=\"Example\">
+ <meta name=\"twitter:card\" content=\"summary\">
+ <meta name=\"twitter:title\" content=\"Release notes\">
+</head>
+"
+    );
+}
+
+#[test]
+fn language_tags() {
+    let mut buf = Buffer::new().strict();
+    buf.alternate_languages(vec![
+        ("zh-Hant-TW", "/zh/"),
+        ("x-klingon", "/tlh/"),
+        ("de-CH-1996", "/de/"),
+        ("en_GB", "/en/"),
+        ("english", "/en/"),
+        ("x", "/x/"),
+        ("fr-", "/fr/"),
+    ]);
+    assert_eq!(
+        buf.validation_errors(),
+        vec![
+            ValidationError::InvalidLanguageTag("en_GB".into()),
+            ValidationError::InvalidLanguageTag("x".into()),
+            ValidationError::InvalidLanguageTag("fr-".into()),
+        ]
+    );
+}
+
+#[test]
+fn head_helpers() {
+    let mut buf = Buffer::new().csp_nonce("n0nce");
+    let mut html = buf.html().lang("en");
+    let mut head = html.head();
+    head.meta_charset_utf8()
+        .meta_viewport_default()
+        .meta_description("A <small> site")
+        .canonical("https://example.com/")
+        .icons(&Icons::new("/icons/", &[32]))
+        .manifest("/site.webmanifest")
+        .theme_color("white", "black")
+        .stylesheet(Stylesheet::new("/fonts.css").preload());
+    head.preconnect("https://cdn.example.com");
+    head.script_src("/app.js").module();
+    insta::assert_snapshot!(buf.finish());
+}
+
+#[test]
+fn inline_svg() {
+    let mut buf = Buffer::new();
+    let mut svg = buf.svg().attr_value("viewBox", "0 0 10 10");
+    let mut defs = svg.defs();
+    let mut gradient = defs.linear_gradient().id("fade");
+    gradient.stop().attr_value("offset", 0);
+    gradient.stop().attr_value("offset", 1);
+    svg.circle()
+        .attr_value("r", 5)
+        .attr_value("fill", "url(#fade)");
+    insta::assert_snapshot!(buf.finish());
+}
+
+#[test]
+fn encodings() {
+    let render = |encoding| {
+        let mut buf = Buffer::new().encoding(encoding);
+        buf.p().class("naïve").text("<Ünïcödé> ✓");
+        buf.finish()
+    };
+    assert_eq!(
+        render(Encoding::Minimal),
+        "<p class=\"naïve\">\n&lt;Ünïcödé&gt; ✓\n</p>\n"
+    );
+    assert_eq!(
+        render(Encoding::Named),
+        "<p class=\"na&iuml;ve\">\n&lt;&Uuml;n&iuml;c&ouml;d&eacute;&gt; ✓\n</p>\n"
+    );
+    assert_eq!(
+        render(Encoding::Numeric),
+        "<p class=\"na&#239;ve\">\n&lt;&#220;n&#239;c&#246;d&#233;&gt; &#10003;\n</p>\n"
+    );
+    assert_eq!(
+        render(Encoding::Ascii),
+        "<p class=\"na&iuml;ve\">\n&lt;&Uuml;n&iuml;c&ouml;d&eacute;&gt; &#10003;\n</p>\n"
+    );
+}
+
+#[test]
+fn encodings_safe() {
+    let mut buf = Buffer::new().encoding(Encoding::Numeric);
+    let mut p = buf.p().safe();
+    write!(p, "é & 'ü' </p>").unwrap();
+    let (path, quoted) = ("a/b", "\"c\"");
+    write!(p, "{}✓{}", path, quoted).unwrap();
+    assert_eq!(
+        buf.finish(),
+        "<p>\n&#233; &amp; &#x27;&#252;&#x27; &lt;&#x2F;p&gt;a&#x2F;b&#10003;&quot;c&quot;</p>\n"
+    );
+}
+
+#[test]
+fn strict_text_only() {
+    let mut buf = Buffer::new().strict();
+    let mut head = buf.head();
+    head.title().b();
+    head.script().child("div".into());
+    assert!(matches!(
+        head.textarea().try_child("span".into()),
+        Err(Error::Invalid(_))
+    ));
+    head.style().text("p > b { color: red }");
+    let errors = buf
+        .validation_errors()
+        .iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        [
+            "<b> inside <title>: the parent can only contain text",
+            "<div> inside <script>: the parent can only contain text",
+        ]
+    );
+    assert_eq!(
+        buf.finish(),
+        "\
+<head>
+ <title>
+  <b>
+  </b>
+ </title>
+ <script>
+  <div>
+  </div>
+ </script>
+ <textarea>
+ </textarea>
+ <style>
+p &gt; b { color: red }
+ </style>
+</head>
+"
+    );
+}
+
+#[test]
+fn preview() {
+    let mut buf = Buffer::new();
+    buf.div().class("a").input().name("q");
+    buf.ul().li().class("pending");
+    assert_eq!(
+        buf.to_string(),
+        "\
+<div class=\"a\">
+ <input name=\"q\">
+</div>
+<ul>
+ <li class=\"pending\">
+ </li>
+</ul>
+"
+    );
+    buf.ul().li().text("More");
+    let preview = buf.preview();
+    assert_eq!(buf.finish(), preview);
+}
+
+#[test]
+fn stats() {
+    let mut cache = FragmentCache::new();
+    let mut buf = Buffer::new();
+    buf.doctype();
+    let mut body = buf.body();
+    let slot = body.placeholder();
+    for _ in 0..2 {
+        body.div().cached(&mut cache, 0, |node| {
+            node.nav().ul().li();
+        });
+    }
+    buf.fill(slot, |node| {
+        node.p();
+    });
+    let stats = buf.stats();
+    assert_eq!(stats.elements, 10);
+    assert_eq!(stats.max_depth, 5);
+    assert_eq!(
+        stats.tags.into_iter().collect::<Vec<_>>(),
+        [
+            ("body".to_string(), 1),
+            ("div".to_string(), 2),
+            ("li".to_string(), 2),
+            ("nav".to_string(), 2),
+            ("p".to_string(), 1),
+            ("ul".to_string(), 2),
+        ]
+    );
+    // The end tags haven't been written yet
+    let text = buf.finish();
+    assert_eq!(stats.bytes, Some(text.len() - " </div>\n</body>\n".len()));
+}
+
+#[cfg(feature = "source-locations")]
+#[test]
+fn source_locations() {
+    let mut buf = Buffer::new().source_locations();
+    let line = line!() + 1;
+    let mut body = buf.body();
+    body.child("main".into()).br();
+    write!(body.comment(), "not an element").unwrap();
+    assert_eq!(
+        buf.finish(),
+        format!(
+            "\
+<!-- tests/golden.rs:{} -->
+<body>
+ <!-- tests/golden.rs:{} -->
+ <main>
+  <!-- tests/golden.rs:{} -->
+  <br>
+ </main>
+ <!-- not an element -->
+</body>
+",
+            line,
+            line + 1,
+            line + 1,
+        )
+    );
+}
+
+#[test]
+fn max_depth() {
+    let mut buf = Buffer::new().max_depth(2);
+    buf.doctype();
+    let mut body = buf.body();
+    let mut div = body.div();
+    assert_eq!(div.try_child("p".into()).err(), Some(Error::TooDeep(2)));
+    assert_eq!(
+        div.try_void_child("br".into()).err(),
+        Some(Error::TooDeep(2))
+    );
+    body.p().text("sibling");
+    assert_eq!(
+        buf.finish(),
+        "\
+<!DOCTYPE html>
+<body>
+ <div>
+ </div>
+ <p>
+sibling
+ </p>
+</body>
+"
+    );
+}
+
+#[test]
+#[should_panic(expected = "elements nested more than 1 deep")]
+fn max_depth_panics() {
+    let mut buf = Buffer::new().max_depth(1);
+    buf.body().div();
+}
+
+#[test]
+fn document_ending() {
+    fn build(buf: &mut Buffer) {
+        let mut ul = buf.ul();
+        for i in 0..1000 {
+            writeln!(ul.li(), "{}", i).unwrap();
+        }
+    }
+    let expected = {
+        let mut buf = Buffer::new();
+        build(&mut buf);
+        buf.finish()
+    };
+    let trimmed = expected.trim_end();
+    let format = Format::new().trailing_newline(false);
+
+    let mut buf = Buffer::new().format(format.clone());
+    build(&mut buf);
+    assert_eq!(buf.preview(), trimmed);
+    assert_eq!(buf.finish(), trimmed);
+
+    // The output is split exactly after the last end tag
+    let mut buf = Buffer::segmented(expected.len()).format(format.clone());
+    build(&mut buf);
+    assert_eq!(buf.finish_segments(), [trimmed]);
+
+    let mut buf = Buffer::to_writer(vec![]).format(format.clone());
+    build(&mut buf);
+    assert_eq!(buf.finish_writer::<Vec<u8>>().unwrap(), trimmed.as_bytes());
+
+    // Flushing doesn't write out the final newline
+    let mut buf = Buffer::to_writer(vec![]).format(format);
+    build(&mut buf);
+    buf.flush();
+    assert_eq!(buf.finish_writer::<Vec<u8>>().unwrap(), trimmed.as_bytes());
+
+    let mut buf = Buffer::segmented(64).format(Format::new().blank_line_at_end(true));
+    build(&mut buf);
+    assert_eq!(buf.finish_segments().concat(), format!("{}\n", expected));
+}
+
+#[test]
+fn indented_text() -> std::fmt::Result {
+    let mut buf = Buffer::new().format(Format::new().indent_text(true));
+    let mut body = buf.body();
+    let mut p = body.p();
+    write!(p, "One, ")?;
+    writeln!(p, "two,\n\nthree")?;
+    writeln!(p, "four")?;
+    p.br();
+    writeln!(p.safe(), "five\nsix")?;
+    body.textarea().text("  seven\n  eight");
+    writeln!(body.raw(), "<hr>\n<hr>")?;
+    assert_eq!(
+        buf.finish(),
+        "\
+<body>
+ <p>
+  One, two,
+
+  three
+  four
+  <br>
+  five
+  six
+ </p>
+ <textarea>
+  seven
+  eight
+ </textarea>
+<hr>
+<hr>
+</body>
+"
+    );
+    Ok(())
+}
+
+#[test]
+fn wrapped_attributes() {
+    fn build(buf: &mut Buffer) {
+        let mut form = buf.form().action("/search").method("get");
+        form.input()
+            .type_("search")
+            .name("q")
+            .placeholder("Search the docs")
+            .autofocus(true);
+        form.button().type_("submit").text("Go");
+    }
+    let format = Format::new().wrap_attributes(30);
+    let mut buf = Buffer::new().format(format.clone());
+    build(&mut buf);
+    let text = buf.finish();
+    assert_eq!(
+        text,
+        "\
+<form
+  action=\"/search\"
+  method=\"get\">
+ <input
+   type=\"search\"
+   name=\"q\"
+   placeholder=\"Search the docs\"
+   autofocus>
+ <button type=\"submit\">
+Go
+ </button>
+</form>
+"
+    );
+    let mut buf = Buffer::to_writer(vec![]).format(format);
+    build(&mut buf);
+    assert_eq!(buf.finish_writer::<Vec<u8>>().unwrap(), text.as_bytes());
+}
+
+#[test]
+fn sorted_attributes() {
+    let mut buf = Buffer::new().sort_attributes().csp_nonce("abc");
+    buf.script().type_("module").src("/app.js");
+    buf.a()
+        .href("/")
+        .attr(r#"data-b="2" data-a="1""#)
+        .class("home")
+        .text("Home");
+    buf.hr().id("rule").class("thin");
+    assert_eq!(
+        buf.finish(),
+        "\
+<script nonce=\"abc\" src=\"/app.js\" type=\"module\">
+</script>
+<a class=\"home\" data-b=\"2\" data-a=\"1\" href=\"/\">
+Home
+</a>
+<hr class=\"thin\" id=\"rule\">
+"
+    );
+}
+
+#[cfg(feature = "html5ever")]
+#[test]
+fn html5ever_tokens() {
+    use html5ever::tokenizer::{Token, TokenSink, TokenSinkResult};
+
+    #[derive(Default)]
+    struct Lines(Vec<String>);
+
+    impl TokenSink for Lines {
+        type Handle = ();
+
+        fn process_token(&mut self, token: Token, line: u64) -> TokenSinkResult<()> {
+            let token = match token {
+                Token::TagToken(tag) => {
+                    let attrs = tag
+                        .attrs
+                        .iter()
+                        .map(|attr| format!(" {}={:?}", attr.name.local, &*attr.value))
+                        .collect::<String>();
+                    format!("{:?} {}{}", tag.kind, tag.name, attrs)
+                }
+                token => format!("{:?}", token),
+            };
+            self.0.push(format!("{}: {}", line, token));
+            TokenSinkResult::Continue
+        }
+    }
+
+    let mut buf = Buffer::tokens();
+    buf.doctype();
+    let mut div = buf.div().attr(r#"data-x="1" hidden"#);
+    writeln!(div.comment(), "note").unwrap();
+    div.p().text("a < b");
+    writeln!(div.raw(), "<em>raw</em>").unwrap();
+    let lines = buf.finish_tokens(Lines::default()).0;
+    assert_eq!(
+        lines,
+        [
+            "1: DoctypeToken(Doctype { name: Some(Tendril<UTF8>(inline: \"html\")), public_id: None, system_id: None, force_quirks: false })",
+            "2: StartTag div data-x=\"1\" hidden=\"\"",
+            "3: CommentToken(Tendril<UTF8>(inline: \"note\\n\"))",
+            "5: StartTag p",
+            "6: CharacterTokens(Tendril<UTF8>(inline: \"a < b\"))",
+            "6: CharacterTokens(Tendril<UTF8>(inline: \"\\n\"))",
+            "7: EndTag p",
+            "8: StartTag em",
+            "8: CharacterTokens(Tendril<UTF8>(inline: \"raw\"))",
+            "8: EndTag em",
+            "8: CharacterTokens(Tendril<UTF8>(inline: \"\\n\"))",
+            "9: EndTag div",
+            "10: EOFToken",
+        ]
+    );
+}
+
+#[test]
+fn observed_events() {
+    use std::sync::{Arc, Mutex};
+
+    let events = Arc::new(Mutex::new(vec![]));
+    let seen = events.clone();
+    let mut cache = FragmentCache::new();
+    let mut buf = Buffer::with_sink(())
+        .observe(move |event| seen.lock().unwrap().push(format!("{:?}", event)))
+        .observe(|event| {
+            if let Event::VoidElement { attrs, .. } = event {
+                attrs.clear();
+            }
+        });
+    let mut body = buf.body();
+    for _ in 0..2 {
+        body.cached(&mut cache, 0, |node| {
+            node.nav().hr().class("rule");
+        });
+    }
+    write!(body.comment(), "note").unwrap();
+    body.conditional_comment("if mso").raw().text("<table>");
+    buf.into_sink::<()>();
+    assert_eq!(
+        *events.lock().unwrap(),
+        [
+            r#"StartElement { tag: "body", attrs: [] }"#,
+            r#"StartElement { tag: "nav", attrs: [] }"#,
+            r#"VoidElement { tag: "hr", attrs: [Value("class", "rule")] }"#,
+            r#"EndElement { tag: "nav" }"#,
+            r#"StartElement { tag: "nav", attrs: [] }"#,
+            r#"VoidElement { tag: "hr", attrs: [Value("class", "rule")] }"#,
+            r#"EndElement { tag: "nav" }"#,
+            r#"Comment("note")"#,
+            r#"StartConditional { cond: "if mso" }"#,
+            r#"Raw("<table>")"#,
+            r#"Raw("\n")"#,
+            r#"EndConditional"#,
+            r#"EndElement { tag: "body" }"#,
+        ]
+    );
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn html_eq() {
+    let mut buf = Buffer::new();
+    let mut ul = buf.ul().class("items").id("list");
+    ul.li().text("One");
+    ul.li().text("Two  and\na half");
+    assert_html_eq!(
+        buf.finish(),
+        r#"<ul id="list" class="items"><li>One<li>Two and a half</li></ul>"#,
+    );
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+#[should_panic(expected = "HTML differs: in the list")]
+fn html_eq_differs() {
+    let mut buf = Buffer::new();
+    buf.ul().li().text("One");
+    assert_html_eq!(buf.finish(), "<ul><li>Two</li></ul>", "in the {}", "list");
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn html_eq_inline_whitespace() {
+    let mut buf = Buffer::new();
+    let mut p = buf.p();
+    p.b().text("a");
+    p.i().text("b");
+    assert_html_eq!(buf.finish(), "<p><b>a</b> <i>b</i></p>");
+    let result = std::panic::catch_unwind(|| {
+        assert_html_eq!("<p><b>a</b> <i>b</i></p>", "<p><b>a</b><i>b</i></p>");
+    });
+    assert!(result.is_err());
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn rendered_select() {
+    let mut buf = Buffer::new();
+    let mut table = buf.table().id("scores");
+    for (name, score) in [("Ann", 3), ("Bob", 5)] {
+        let mut tr = table.tr().class("row");
+        tr.td().text(name);
+        tr.td().class("score").text(score);
+    }
+    let rendered = Rendered::from(buf.finish());
+    assert_eq!(rendered.select("#scores tr.row").len(), 2);
+    let scores: Vec<_> = rendered
+        .select("tr > td.score")
+        .into_iter()
+        .map(|td| td.children().to_vec())
+        .collect();
+    assert_eq!(
+        scores,
+        [
+            vec![Content::Text("3".into())],
+            vec![Content::Text("5".into())]
+        ]
+    );
+    assert!(rendered.find("table > tr").is_none());
+    assert!(rendered.find("tbody > tr").is_some());
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn snapshot_rules() {
+    let mut buf = Buffer::new().csp_nonce("abc123");
+    let mut head = buf.head();
+    head.meta()
+        .http_equiv("Content-Security-Policy")
+        .content("script-src 'nonce-abc123'");
+    head.link()
+        .rel("stylesheet")
+        .href("/main-0123abcd4567.css")
+        .attr(r#"integrity="sha384-oqVuAfXRKap7fdgcCY5uykM6+R9GqQ8K/uxy9rx7HNQlGYl1kPzQho1wx4JwY8wC""#);
+    head.script().src("/app.js");
+    buf.time()
+        .datetime("2024-03-01 09:15:00+01:00")
+        .text("1 March");
+    let rules = SnapshotRules::default().replace(r"(\d+) March", "$1 [month]");
+    insta::assert_snapshot!(normalize_for_snapshot(&buf.finish(), &rules));
+}
+
+#[cfg(feature = "digest")]
+#[test]
+fn digest() {
+    use sha2::Digest;
+    fn page(buf: &mut Buffer) {
+        let mut ul = buf.ul();
+        for i in 0..1000 {
+            writeln!(ul.li().class("item"), "Item {}", i).unwrap();
+        }
+        write!(buf.comment(), "end").unwrap();
+    }
+    let format = Format::new().trailing_newline(false);
+    let mut buf = Buffer::with_digest().format(format.clone());
+    page(&mut buf);
+    let (html, digest) = buf.finish_with_digest();
+    let mut expected = Buffer::new().format(format);
+    page(&mut expected);
+    let expected = expected.finish();
+    assert_eq!(html, expected);
+    assert_eq!(digest, <[u8; 32]>::from(sha2::Sha256::digest(&expected)));
+}
+
+#[test]
+fn minified() -> std::fmt::Result {
+    let mut buf = Buffer::new().format(Format::new().minify(true));
+    buf.doctype();
+    let mut html = buf.html().attr("lang=en");
+    let mut head = html.head();
+    head.meta().charset("utf-8");
+    writeln!(head.style(), "p {{\n  color: red;\n}}")?;
+    let mut body = html.body();
+    write!(body.comment(), "Main content")?;
+    let mut p = body.p();
+    write!(p, "Some   ")?;
+    writeln!(p.b(), "bold")?;
+    writeln!(p, " and\n  plain text")?;
+    p.input().type_("text").value("").name("q");
+    writeln!(body.pre(), "  keep\n  this")?;
+    writeln!(body.conditional_comment("if IE").p(), "Old browser")?;
+    body.raw().write_str("<span> raw </span>\n")?;
+    insta::assert_snapshot!(buf.finish());
+    Ok(())
+}
+
+#[test]
+fn indented_raw() -> std::fmt::Result {
+    let mut buf = Buffer::new().format(Format::new().indent_raw(true));
+    let mut main = buf.main();
+    let mut section = main.section().raw();
+    section.write_str("<ul>\n\t<li>One</li>\n\n\t<li>Two</li>\n</ul>\n")?;
+    write!(section, "<p>Inline</p>")?;
+    writeln!(section, " <em>after</em>\n    <hr>")?;
+    section.write_str("<pre>\n  as is\n</pre>\n")?;
+    writeln!(main.pre().raw(), "  <b>as is</b>\n  too")?;
+    insta::assert_snapshot!(buf.finish());
+    Ok(())
+}
+
+#[cfg(feature = "macros")]
+#[test]
+fn included_html() -> std::fmt::Result {
+    let mut buf = Buffer::new();
+    let mut main = buf.main();
+    let mut section = main.section();
+    include_html!(&mut section, "tests/partials/card.html")?;
+    main.p().text("After");
+    insta::assert_snapshot!(buf.finish());
+    Ok(())
+}
+
+#[test]
+fn spliced_static() {
+    let header = Static::new(|node| {
+        let mut header = node.header();
+        header.img().src("/logo.png").alt("Logo");
+        header
+            .a()
+            .href("https://example.com")
+            .target("_blank")
+            .text("Café");
+    });
+    let mut buf = Buffer::new()
+        .lazy_images()
+        .noopener()
+        .encoding(Encoding::Ascii);
+    let mut html = buf.html();
+    html.splice(&header);
+    let mut main = html.main();
+    main.splice(&header);
+    insta::assert_snapshot!(buf.finish());
+}
+
+#[test]
+fn appended_buffer() -> std::fmt::Result {
+    let mut article = Buffer::new();
+    let mut section = article.section();
+    writeln!(section.h2(), "Code")?;
+    writeln!(section.pre(), "<b>\n  x\n</b>")?;
+    section.raw().write_str("<p>Raw</p>\n")?;
+    let mut buf = Buffer::new();
+    let mut body = buf.body();
+    let mut main = body.main();
+    main.append_buffer(article);
+    main.append_buffer(Buffer::new());
+    body.footer();
+    insta::assert_snapshot!(buf.finish());
+
+    let mut bad = Buffer::new().strict();
+    bad.li();
+    let mut buf = Buffer::new().strict();
+    buf.div().append_buffer(bad);
+    assert!(matches!(buf.try_finish(), Err(Error::Invalid(_))));
+    Ok(())
+}
+
+#[test]
+fn forked() -> std::fmt::Result {
+    let mut buf = Buffer::new();
+    let mut html = buf.html();
+    html.head().head_slot();
+    let mut body = html.body();
+    let mut ul = body.ul();
+    writeln!(ul.li(), "Shared")?;
+    let mut b = ul.fork();
+    writeln!(ul.li(), "Only A")?;
+    ul.head_manager().title("Variant A");
+    writeln!(b.li(), "Only B")?;
+    b.head_manager().title("Variant B");
+    let a = buf.finish();
+    assert!(a.contains("Variant A") && a.contains("Only A") && !a.contains("Only B"));
+    insta::assert_snapshot!(b.finish());
+    Ok(())
+}
+
+#[test]
+fn from_prefix() -> std::fmt::Result {
+    let prelude = r#"<!DOCTYPE html>
+<html lang="en">
+<head><title>A <b> tag</title>
+<script>document.write("<div>")</script>
+<link rel="stylesheet" href="/style.css"><meta charset="utf-8" />
+</head>
+<body class="a>b"><!-- <section> -->
+<div id="content"><p>Intro</span></p>"#;
+    let mut buf = Buffer::from_prefix(prelude);
+    let mut ul = buf.ul();
+    writeln!(ul.li(), "One")?;
+    insta::assert_snapshot!(buf.finish());
+
+    let mut buf = Buffer::from_prefix("<ul>\n").strict();
+    buf.li();
+    assert!(buf.validation_errors().is_empty());
+    buf.td();
+    assert_eq!(buf.validation_errors().len(), 1);
+    buf.finish_and_reset();
+    writeln!(buf.p(), "Again")?;
+    assert_eq!(buf.finish(), "<p>\nAgain\n</p>\n");
+    Ok(())
+}
+
+#[test]
+fn io_write() {
+    use std::io::{ErrorKind, Write as _};
+
+    let mut buf = Buffer::new();
+    let mut p = buf.p();
+    for byte in "Caf\u{e9} <\u{1f375}>\n".bytes() {
+        assert_eq!(p.write(&[byte]).unwrap(), 1);
+    }
+    std::io::Write::flush(&mut p).unwrap();
+    assert_eq!(buf.finish(), "<p>\nCaf\u{e9} &lt;\u{1f375}&gt;\n</p>\n");
+
+    let mut buf = Buffer::new();
+    let mut p = buf.p();
+    assert_eq!(p.write(b"ok\xFFbad").unwrap(), 2);
+    let err = p.write(b"\xFFbad").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    p.write_all(b"\xF0\x9F").unwrap();
+    let err = std::io::Write::flush(&mut p).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(buf.finish(), "<p>\nok</p>\n");
+}
+
+#[test]
+fn formatted_escaping() -> std::fmt::Result {
+    struct Quote<'a>(&'a str);
+    impl std::fmt::Display for Quote<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            for word in self.0.split(' ') {
+                write!(f, "\"{}\" ", word)?;
+            }
+            Ok(())
+        }
+    }
+    let mut buf = Buffer::new().encoding(Encoding::Ascii);
+    let tick = '\u{2713}';
+    writeln!(buf.p(), "{} & {}", Quote("caf\u{e9} </p>"), 1.5)?;
+    writeln!(buf.p().safe(), "{} & {}", Quote("caf\u{e9} </p>"), tick)?;
+    assert_eq!(
+        buf.finish(),
+        "<p>\n\"caf&eacute;\" \"&lt;/p&gt;\"  &amp; 1.5\n</p>\n\
+         <p>\n&quot;caf&eacute;&quot; &quot;&lt;&#x2F;p&gt;&quot;  &amp; &#10003;\n</p>\n",
+    );
+    Ok(())
+}
+
+#[test]
+fn dynamic_tags() -> std::fmt::Result {
+    let mut buf = Buffer::new();
+    let short = format!("x-{}", "card");
+    let long = format!("x-{}", "a-very-long-custom-element-name");
+    let mut outer = buf.child(short.into());
+    let mut inner = outer.child(long.into());
+    let slot = inner.placeholder();
+    writeln!(inner, "Body")?;
+    buf.fill(slot, |node| {
+        node.child(String::from("x-slot").into());
+    });
+    assert_eq!(
+        buf.finish(),
+        "<x-card>\n <x-a-very-long-custom-element-name>\n  <x-slot>\n  </x-slot>\n\
+         Body\n </x-a-very-long-custom-element-name>\n</x-card>\n",
+    );
+    Ok(())
+}
+
+#[test]
+fn typed_values() {
+    let mut buf = Buffer::new();
+    let mut form = buf.form().method(FormMethod::Post).target(Target::Blank);
+    form.input().type_(InputType::DatetimeLocal).name("when");
+    form.button().type_(ButtonType::Submit).text("Go");
+    buf.link()
+        .rel(LinkRel::Preload)
+        .href("/font.woff2")
+        .referrerpolicy(ReferrerPolicy::StrictOriginWhenCrossOrigin);
+    let mut sandbox = Sandbox::default();
+    sandbox |= Sandbox::ALLOW_SAME_ORIGIN;
+    buf.iframe().sandbox(sandbox);
+    buf.iframe().sandbox(Sandbox::NONE);
+    assert!((sandbox | Sandbox::ALLOW_POPUPS).contains(Sandbox::ALLOW_SAME_ORIGIN));
+    insta::assert_snapshot!(buf.finish());
+}
+
+#[test]
+fn aria_states() {
+    let mut buf = Buffer::new();
+    let mut tabs = buf.div().role("tablist").aria_label("Sections");
+    for (i, name) in ["Intro", "Usage"].iter().enumerate() {
+        tabs.button()
+            .role("tab")
+            .id(format!("tab-{}", i))
+            .aria_selected(i == 0)
+            .aria_controls(format!("panel-{}", i))
+            .text(name);
+    }
+    buf.div()
+        .role("tabpanel")
+        .id("panel-0")
+        .aria_labelledby("tab-0")
+        .aria_busy(true);
+    buf.p()
+        .aria_live(AriaLive::Polite)
+        .aria_describedby("hint")
+        .aria_disabled(false)
+        .aria_current(AriaCurrent::Step);
+    insta::assert_snapshot!(buf.finish());
+}
+
+#[cfg(feature = "legacy")]
+#[test]
+fn legacy_elements() {
+    let mut buf = Buffer::new();
+    buf.doctype_custom(r#"HTML PUBLIC "-//W3C//DTD HTML 4.01 Frameset//EN""#);
+    let mut html = buf.html();
+    html.head().title().text("Guide");
+    let mut frameset = html.frameset().attr_value("cols", "25%,*");
+    frameset.frame().attr_value("src", "menu.html");
+    frameset.frame().attr_value("src", "main.html");
+    let mut noframes = frameset.noframes();
+    let mut center = noframes.center();
+    center.marquee().big().text("Now showing");
+    center.basefont().attr_value("size", 3);
+    center.tt().text("CH 4");
+    center.strike().text("Sold out");
+    insta::assert_snapshot!(buf.finish());
+}
+
+#[test]
+fn amp() {
+    let mut buf = Buffer::new().amp("https://example.com/recipes/soup");
+    buf.doctype();
+    let mut html = buf.html().lang("en");
+    let mut head = html.head();
+    head.head_slot();
+    head.title().text("Soup");
+    head.style()
+        .attr_flag("amp-custom", true)
+        .raw()
+        .text("h1 { color: teal }");
+    head.head_manager().meta("description", "A soup recipe");
+    let mut body = html.body();
+    body.h1().text("Soup");
+    body.img()
+        .src("/soup.jpg")
+        .alt("A bowl of soup")
+        .width(800)
+        .height(600);
+    body.iframe()
+        .src("https://video.example.com/soup")
+        .attr_value("layout", "fill");
+    insta::assert_snapshot!(buf.finish());
+}
+
+#[test]
+fn amp_errors() {
+    let mut buf = Buffer::new().amp("https://example.com/");
+    let mut html = buf.html();
+    let mut head = html.head();
+    head.script().src("/app.js");
+    head.style().raw().text("p { margin: 0 }");
+    head.link().rel(LinkRel::Stylesheet).href("/site.css");
+    head.link()
+        .rel(LinkRel::Stylesheet)
+        .href("https://fonts.googleapis.com/css?family=Lato");
+    let mut body = html.body();
+    body.button().attr_value("onclick", "go()").text("Go");
+    body.img().src("/unsized.jpg");
+    body.embed();
+    body.video().src("/clip.mp4").width(640).height(360);
+    let errors = match buf.try_finish() {
+        Err(Error::Invalid(errors)) => errors,
+        x => panic!("{:?}", x),
+    };
+    let errors = errors.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+    insta::assert_debug_snapshot!(errors);
+}
+
+#[test]
+fn amp_cached() {
+    let mut cache = FragmentCache::new();
+    let page = |cache: &mut FragmentCache, amp: bool| {
+        let mut buf = Buffer::new();
+        if amp {
+            buf = buf.amp("https://example.com/");
+        }
+        let mut html = buf.html();
+        html.head().head_slot();
+        let mut body = html.body();
+        body.cached(cache, 0, |node| {
+            node.video().src("/clip.mp4").width(640).height(360);
+        });
+        body.cached(cache, 1, |node| {
+            node.embed();
+        });
+        buf.try_finish()
+    };
+    let errors = match page(&mut cache, true) {
+        Err(Error::Invalid(errors)) => errors,
+        x => panic!("{:?}", x),
+    };
+    let errors = errors.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+    assert_eq!(errors, ["not valid AMP: <embed> isn't allowed"]);
+    let html = page(&mut cache, false).unwrap();
+    assert!(html.contains("<video src=\"/clip.mp4\" width=\"640\" height=\"360\">"));
+    assert!(html.contains("<embed>"));
+
+    let mut buf = Buffer::new().amp("https://example.com/");
+    let mut html = buf.html();
+    html.head().head_slot();
+    html.body().cached(&mut cache, 0, |_| unreachable!());
+    let html = buf.try_finish().unwrap();
+    assert!(html.contains("<amp-video src=\"/clip.mp4\" width=\"640\" height=\"360\">"));
+    assert!(html.contains("</amp-video>"));
+    assert!(html.contains("custom-element=\"amp-video\""));
+}
+
+#[test]
+fn iframe_srcdoc() {
+    let mut buf = Buffer::new();
+    let mut body = buf.body();
+    body.iframe_srcdoc(|doc| {
+        doc.doctype();
+        let mut html = doc.html();
+        html.head().title().text("Preview");
+        let mut body = html.body();
+        body.p().text("<script>alert(1)</script> & \"quotes\"");
+        body.a()
+            .href("/?a=1&b=2")
+            .attr_value("title", "it's \"quoted\"")
+            .text("link");
+    })
+    .sandbox(Sandbox::NONE)
+    .attr_value("title", "Preview");
+    insta::assert_snapshot!(buf.finish());
+}